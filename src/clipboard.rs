@@ -0,0 +1,23 @@
+use std::{
+    io::{self, Write},
+    process::{Command, Stdio},
+};
+
+// ---------------------------------------------------------------------------
+// Clipboard (through clip.exe, which ships with Windows)
+// ---------------------------------------------------------------------------
+
+/// Replaces the clipboard contents with `text`.
+pub fn copy(text: &str) -> io::Result<()> {
+    let mut child = Command::new("clip").stdin(Stdio::piped()).spawn()?;
+    // clip.exe reads the console code page unless the input starts with a
+    // UTF-16 byte order mark.
+    let mut bytes = vec![0xFF, 0xFE];
+    bytes.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+    child.stdin.take().expect("stdin is piped").write_all(&bytes)?;
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(io::Error::other(format!("clip.exe exited with {status}")));
+    }
+    Ok(())
+}
@@ -0,0 +1,267 @@
+use crate::GraphicsSettings;
+
+// ---------------------------------------------------------------------------
+// Preset codes (settings packed into a short string for chat)
+// ---------------------------------------------------------------------------
+
+const PREFIX: &str = "HSR-";
+/// Bumped whenever the payload layout changes.
+const VERSION: u8 = 1;
+/// URL-safe base64, so codes survive chat clients that mangle `+` and `/`.
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Why a code didn't decode, in the order the checks run.
+pub enum CodeError {
+    /// Doesn't start with `PREFIX`.
+    NotACode,
+    /// A character outside the base64 alphabet, e.g. from autocorrect.
+    BadCharacter(char),
+    /// Written by a newer version of this tool.
+    Newer(u8),
+    /// A format version that never existed.
+    UnknownVersion(u8),
+    /// Shorter than its length byte says: cut off while copying.
+    Truncated,
+    /// Complete, but the checksum or layout doesn't match: a typo.
+    Damaged,
+}
+
+/// Packs every modelled setting. Keys this tool doesn't model are left
+/// out; a code only ever carries what the list can show.
+///
+/// Layout: version byte, payload length, payload, checksum. The version
+/// comes first and is checked before anything else, so a code from a newer
+/// layout is reported as such rather than as damaged.
+pub fn encode_code(s: &GraphicsSettings) -> String {
+    let mut bytes = Vec::new();
+    for v in [
+        s.fps,
+        milli(s.render_scale),
+        s.resolution_quality,
+        s.shadow_quality,
+        s.light_quality,
+        s.character_quality,
+        s.env_detail_quality,
+        s.reflection_quality,
+        s.sfx_quality,
+        s.bloom_quality,
+        s.aa_mode,
+        s.enable_self_shadow,
+        s.dlss_quality,
+        s.particle_trail_smoothness,
+    ] {
+        write_varint(&mut bytes, v);
+    }
+    bytes.push(s.enable_vsync as u8);
+    bytes.splice(0..0, [VERSION, bytes.len() as u8]);
+    bytes.push(checksum(&bytes));
+    format!("{PREFIX}{}", base64_encode(&bytes))
+}
+
+/// Unpacks a code from `encode_code`. Surrounding whitespace and the
+/// prefix's case are forgiven.
+pub fn decode_code(code: &str) -> Result<GraphicsSettings, CodeError> {
+    let code = code.trim();
+    let body = code
+        .get(..PREFIX.len())
+        .filter(|p| p.eq_ignore_ascii_case(PREFIX))
+        .map(|_| &code[PREFIX.len()..])
+        .ok_or(CodeError::NotACode)?;
+    let mut bytes = base64_decode(body)?;
+    match bytes.first() {
+        None => return Err(CodeError::Truncated),
+        Some(&VERSION) => {}
+        Some(&v) if v > VERSION => return Err(CodeError::Newer(v)),
+        Some(&v) => return Err(CodeError::UnknownVersion(v)),
+    }
+    let len = *bytes.get(1).ok_or(CodeError::Truncated)? as usize;
+    if bytes.len() < len + 3 {
+        return Err(CodeError::Truncated);
+    }
+    let sum = bytes.pop().ok_or(CodeError::Truncated)?;
+    if bytes.len() != len + 2 || checksum(&bytes) != sum {
+        return Err(CodeError::Damaged);
+    }
+    let mut reader = Reader { bytes: &bytes, pos: 2 };
+
+    let mut ints = [0i64; 14];
+    for slot in &mut ints {
+        *slot = reader.varint()?;
+    }
+    let [
+        fps,
+        render_scale,
+        resolution_quality,
+        shadow_quality,
+        light_quality,
+        character_quality,
+        env_detail_quality,
+        reflection_quality,
+        sfx_quality,
+        bloom_quality,
+        aa_mode,
+        enable_self_shadow,
+        dlss_quality,
+        particle_trail_smoothness,
+    ] = ints;
+    let enable_vsync = match reader.byte()? {
+        0 => false,
+        1 => true,
+        _ => return Err(CodeError::Damaged),
+    };
+    let s = GraphicsSettings {
+        fps,
+        enable_vsync,
+        render_scale: unmilli(render_scale),
+        resolution_quality,
+        shadow_quality,
+        light_quality,
+        character_quality,
+        env_detail_quality,
+        reflection_quality,
+        sfx_quality,
+        bloom_quality,
+        aa_mode,
+        enable_metal_fxsu: false,
+        enable_half_res_transparent: false,
+        enable_self_shadow,
+        dlss_quality,
+        particle_trail_smoothness,
+        extra: serde_json::Map::new(),
+    };
+    if reader.pos != bytes.len() {
+        return Err(CodeError::Damaged);
+    }
+    Ok(s)
+}
+
+/// Floats travel as thousandths, the precision the option lists compare at.
+fn milli(v: f64) -> i64 {
+    (v * 1000.0).round() as i64
+}
+
+fn unmilli(v: i64) -> f64 {
+    v as f64 / 1000.0
+}
+
+/// Zigzag LEB128: small magnitudes of either sign take one byte.
+fn write_varint(out: &mut Vec<u8>, v: i64) {
+    let mut n = ((v << 1) ^ (v >> 63)) as u64;
+    while n >= 0x80 {
+        out.push(n as u8 | 0x80);
+        n >>= 7;
+    }
+    out.push(n as u8);
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    /// The checksum already passed, so running out here means the payload
+    /// doesn't have the layout its version promises.
+    fn byte(&mut self) -> Result<u8, CodeError> {
+        let b = *self.bytes.get(self.pos).ok_or(CodeError::Damaged)?;
+        self.pos += 1;
+        Ok(b)
+    }
+
+    fn varint(&mut self) -> Result<i64, CodeError> {
+        let mut n = 0u64;
+        for shift in (0..64).step_by(7) {
+            let b = self.byte()?;
+            n |= ((b & 0x7f) as u64) << shift;
+            if b & 0x80 == 0 {
+                return Ok((n >> 1) as i64 ^ -((n & 1) as i64));
+            }
+        }
+        Err(CodeError::Damaged)
+    }
+}
+
+fn checksum(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0u8, |acc, &b| acc.rotate_left(1) ^ b)
+}
+
+/// Unpadded base64 over `ALPHABET`.
+fn base64_encode(bytes: &[u8]) -> String {
+    let mut out = String::new();
+    for chunk in bytes.chunks(3) {
+        let n = chunk.iter().enumerate().fold(0u32, |acc, (i, &b)| acc | (b as u32) << (16 - 8 * i));
+        for i in 0..=chunk.len() {
+            out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+    }
+    out
+}
+
+fn base64_decode(text: &str) -> Result<Vec<u8>, CodeError> {
+    let mut out = Vec::new();
+    let mut acc = 0u32;
+    let mut bits = 0;
+    for c in text.chars() {
+        let v = ALPHABET.iter().position(|&a| a as char == c).ok_or(CodeError::BadCharacter(c))? as u32;
+        acc = acc << 6 | v;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((acc >> bits) as u8);
+            acc &= (1 << bits) - 1;
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> GraphicsSettings {
+        GraphicsSettings {
+            fps: 120,
+            enable_vsync: true,
+            render_scale: 1.4,
+            shadow_quality: 3,
+            aa_mode: 2,
+            ..GraphicsSettings::default()
+        }
+    }
+
+    fn with_version(version: u8) -> String {
+        let code = encode_code(&settings());
+        let mut bytes = base64_decode(&code[PREFIX.len()..]).ok().unwrap();
+        bytes[0] = version;
+        let last = bytes.len() - 1;
+        bytes[last] = checksum(&bytes[..last]);
+        format!("{PREFIX}{}", base64_encode(&bytes))
+    }
+
+    #[test]
+    fn codes_round_trip_every_listed_setting() {
+        let code = encode_code(&settings());
+        let decoded = decode_code(&format!("  hsr-{}\n", &code[PREFIX.len()..])).ok().unwrap();
+        assert_eq!(serde_json::to_value(&decoded).unwrap(), serde_json::to_value(settings()).unwrap());
+    }
+
+    #[test]
+    fn versions_are_checked_before_the_payload() {
+        assert!(decode_code(&with_version(VERSION)).is_ok());
+        assert!(matches!(decode_code(&with_version(VERSION + 1)), Err(CodeError::Newer(_))));
+        assert!(matches!(decode_code(&with_version(0)), Err(CodeError::UnknownVersion(0))));
+    }
+
+    #[test]
+    fn damage_is_told_apart_from_truncation() {
+        let code = encode_code(&settings());
+        assert!(matches!(decode_code("hello"), Err(CodeError::NotACode)));
+        assert!(matches!(decode_code(&format!("{code}!")), Err(CodeError::BadCharacter('!'))));
+        assert!(matches!(decode_code(&code[..code.len() - 3]), Err(CodeError::Truncated)));
+        // The first character of a group only carries bits of one byte.
+        let at = PREFIX.len() + 4;
+        let typo = if &code[at..=at] == "A" { 'B' } else { 'A' };
+        let damaged = format!("{}{typo}{}", &code[..at], &code[at + 1..]);
+        assert!(matches!(decode_code(&damaged), Err(CodeError::Damaged)));
+    }
+}
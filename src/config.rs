@@ -0,0 +1,366 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{theme::ThemeName, Field, Group, Lang, Preset};
+
+const CONFIG_DIR: &str = "hsr-settings";
+const CONFIG_FILE: &str = "config.toml";
+/// Where a config file that didn't parse is copied before it's replaced.
+const BACKUP_FILE: &str = "config.toml.bak";
+
+// ---------------------------------------------------------------------------
+// App configuration (%APPDATA%\hsr-settings\config.toml)
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub collapsed: Vec<Group>,
+    /// Settings protected from edits, imports and bulk changes.
+    pub locked: Vec<Field>,
+    /// Settings pinned to the favorites section at the top of the list.
+    pub favorites: Vec<Field>,
+    /// Offer to turn VSync off when a manual FPS cap is picked.
+    pub fps_vsync_autofix: bool,
+    /// Save immediately on S instead of reviewing the changes first.
+    pub skip_save_confirmation: bool,
+    /// The first-run hardware preset suggestion has been shown.
+    pub preset_hint_shown: bool,
+    /// FPS caps the FPS setting cycles through; 30, 60 and 120 when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fps_options: Option<Vec<i64>>,
+    /// The monitor's refresh rate in Hz, which VSync caps the FPS to;
+    /// the primary display's when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_rate: Option<u32>,
+    /// Seconds between automatic saves of pending changes; 0 turns it off.
+    pub autosave_secs: u64,
+    /// Function keys that stage a preset, e.g. `F2 = "high"`.
+    pub hotkeys: BTreeMap<String, Preset>,
+    /// Color theme: dark, light, solarized or high_contrast.
+    pub theme: ThemeName,
+    /// UI language by code; the startup picker asks when unset. Read
+    /// through `language()`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Start in the last language used instead of asking, once one is
+    /// known. `language` skips the picker on its own.
+    pub skip_language_picker: bool,
+    /// Always start in the plain layout `--accessible` selects.
+    pub accessible: bool,
+    /// Moving past the last row of the list lands on the first, and back.
+    pub wrap_navigation: bool,
+    /// Blank lines between rows of the settings list; = toggles 0 and 1.
+    pub row_spacing: u16,
+    /// Tint setting labels by category, e.g. display or effects.
+    pub category_colors: bool,
+    /// Show quality tiers by name, as the game does, instead of 1-5.
+    pub show_tier_names: bool,
+    /// On startup, compare the Global and CN clients' settings and offer to
+    /// copy one over the other when they differ.
+    pub sync_clients: bool,
+    /// Keys per action, replacing its defaults, e.g. `save = "ctrl+s"` or
+    /// `move-down = ["down", "J"]`.
+    pub keybindings: BTreeMap<String, Keys>,
+    /// Where the last run left off, restored unless `--fresh` is given.
+    pub session: Session,
+    /// Keys this version doesn't know, kept so saving doesn't drop them.
+    #[serde(flatten)]
+    pub unknown: toml::Table,
+    /// The file on disk didn't parse and couldn't be backed up, so `save`
+    /// leaves it for the user to fix rather than replacing it.
+    #[serde(skip)]
+    keep_file: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            collapsed: Vec::new(),
+            locked: Vec::new(),
+            favorites: Vec::new(),
+            fps_vsync_autofix: false,
+            skip_save_confirmation: false,
+            preset_hint_shown: false,
+            fps_options: None,
+            refresh_rate: None,
+            autosave_secs: 0,
+            hotkeys: BTreeMap::new(),
+            theme: ThemeName::default(),
+            language: None,
+            skip_language_picker: false,
+            accessible: false,
+            wrap_navigation: false,
+            row_spacing: 0,
+            category_colors: false,
+            show_tier_names: true,
+            sync_clients: false,
+            keybindings: BTreeMap::new(),
+            session: Session::default(),
+            unknown: toml::Table::new(),
+            keep_file: false,
+        }
+    }
+}
+
+/// UI state remembered between runs. Written on quit, not on every change.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    /// Preselected in the startup language picker, by code. Read through
+    /// `language()`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// The selected setting by its game key, e.g. `FPS`, so it survives
+    /// settings being added or reordered. A key that is gone is ignored.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    pub show_description: bool,
+    pub show_preview: bool,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self { language: None, cursor: None, show_description: true, show_preview: false }
+    }
+}
+
+impl Session {
+    pub fn language(&self) -> Option<Lang> {
+        language(&self.language)
+    }
+}
+
+/// A language by code. One whose translation file has since been removed
+/// reads as unset, but the code is kept, so saving doesn't erase it and the
+/// language comes back with its file.
+fn language(code: &Option<String>) -> Option<Lang> {
+    code.as_deref().and_then(Lang::parse)
+}
+
+/// Something in the config file that was ignored on load.
+#[derive(Debug)]
+pub enum Warning {
+    /// A top-level key that no setting uses, e.g. a typo.
+    UnknownKey(String),
+    /// The file isn't valid TOML or a value has the wrong type; every
+    /// setting fell back to its default. `backed_up` says whether the file
+    /// was copied to `BACKUP_FILE`; when it wasn't, saves are refused.
+    Invalid { message: String, backed_up: bool },
+}
+
+/// One key description or several.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Keys {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Keys {
+    pub fn as_slice(&self) -> &[String] {
+        match self {
+            Keys::One(key) => std::slice::from_ref(key),
+            Keys::Many(keys) => keys,
+        }
+    }
+}
+
+/// `%APPDATA%\hsr-settings`, which also holds the `lang` folder.
+pub fn config_dir() -> Option<PathBuf> {
+    let appdata = std::env::var_os("APPDATA")?;
+    Some(PathBuf::from(appdata).join(CONFIG_DIR))
+}
+
+fn config_path() -> Option<PathBuf> {
+    Some(config_dir()?.join(CONFIG_FILE))
+}
+
+impl Config {
+    pub fn language(&self) -> Option<Lang> {
+        language(&self.language)
+    }
+
+    pub fn set_language(&mut self, lang: Option<Lang>) {
+        self.language = lang.map(|lang| lang.code().to_string());
+    }
+
+    /// Missing or unreadable config files fall back to defaults.
+    pub fn load() -> Self {
+        Self::load_checked().0
+    }
+
+    /// Like `load`, also reporting what was ignored. A missing file is not
+    /// a warning; it just means nothing was configured yet.
+    pub fn load_checked() -> (Self, Vec<Warning>) {
+        match config_path() {
+            Some(path) => Self::load_from(&path),
+            None => (Self::default(), Vec::new()),
+        }
+    }
+
+    /// A file that doesn't parse is copied next to itself first, so the
+    /// defaults saved over it later don't lose what the user wrote.
+    fn load_from(path: &Path) -> (Self, Vec<Warning>) {
+        let Ok(text) = fs::read_to_string(path) else {
+            return (Self::default(), Vec::new());
+        };
+        let (mut config, mut warnings) = Self::parse(&text);
+        if let Some(Warning::Invalid { backed_up, .. }) = warnings.first_mut() {
+            *backed_up = fs::write(path.with_file_name(BACKUP_FILE), &text).is_ok();
+            config.keep_file = !*backed_up;
+        }
+        (config, warnings)
+    }
+
+    /// Missing keys take their defaults; unknown keys are kept and reported.
+    pub fn parse(text: &str) -> (Self, Vec<Warning>) {
+        match toml::from_str::<Self>(text) {
+            Ok(config) => {
+                let warnings = config.unknown.keys().cloned().map(Warning::UnknownKey).collect();
+                (config, warnings)
+            }
+            Err(e) => {
+                let warning = Warning::Invalid { message: e.message().to_string(), backed_up: false };
+                (Self::default(), vec![warning])
+            }
+        }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = config_path()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "APPDATA is not set"))?;
+        self.save_to(&path)
+    }
+
+    fn save_to(&self, path: &Path) -> io::Result<()> {
+        if self.keep_file {
+            return Err(io::Error::other("config.toml has errors and couldn't be backed up; fix or remove it"));
+        }
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let text = toml::to_string(self).map_err(io::Error::other)?;
+        fs::write(path, text)
+    }
+}
+
+/// Writes `session` into the config file, leaving every other key as it
+/// is on disk, so preferences edited in another window while this one
+/// ran are kept. A file that no longer parses is left alone.
+pub fn save_session(session: &Session) -> io::Result<()> {
+    let path = config_path().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "APPDATA is not set"))?;
+    save_session_to(&path, session)
+}
+
+fn save_session_to(path: &Path, session: &Session) -> io::Result<()> {
+    let mut table = match fs::read_to_string(path) {
+        Ok(text) => text.parse::<toml::Table>().map_err(io::Error::other)?,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => toml::Table::new(),
+        Err(e) => return Err(e),
+    };
+    table.insert("session".into(), toml::Value::try_from(session).map_err(io::Error::other)?);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, toml::to_string(&table).map_err(io::Error::other)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An empty directory of its own under the system temp directory.
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("hsr-settings-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn missing_keys_take_their_defaults() {
+        let (config, warnings) = Config::parse("autosave_secs = 30\n[session]\nshow_preview = true\n");
+        assert!(warnings.is_empty());
+        assert_eq!(config.autosave_secs, 30);
+        assert!(config.session.show_preview && config.session.show_description);
+        assert!(config.show_tier_names && !config.wrap_navigation);
+        assert_eq!(config.theme, ThemeName::default());
+    }
+
+    #[test]
+    fn unknown_keys_are_reported_and_kept() {
+        let (config, warnings) = Config::parse("autosav_secs = 30\n");
+        assert!(matches!(&warnings[..], [Warning::UnknownKey(key)] if key == "autosav_secs"));
+        assert_eq!(config.autosave_secs, 0);
+        assert!(toml::to_string(&config).unwrap().contains("autosav_secs = 30"));
+    }
+
+    #[test]
+    fn invalid_files_are_backed_up_before_being_replaced() {
+        let dir = temp_dir("invalid");
+        let path = dir.join(CONFIG_FILE);
+        let text = "autosave_secs = \"soon\"\ntheme = \"light\"\n";
+        fs::write(&path, text).unwrap();
+        let (config, warnings) = Config::load_from(&path);
+        assert!(matches!(&warnings[..], [Warning::Invalid { backed_up: true, .. }]));
+        assert_eq!(config.theme, ThemeName::default());
+        config.save_to(&path).unwrap();
+        assert_eq!(fs::read_to_string(dir.join(BACKUP_FILE)).unwrap(), text);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn invalid_files_that_cant_be_backed_up_are_not_replaced() {
+        let dir = temp_dir("unbacked");
+        let path = dir.join(CONFIG_FILE);
+        fs::write(&path, "[[[").unwrap();
+        // A directory in the backup's place makes the copy fail.
+        fs::create_dir(dir.join(BACKUP_FILE)).unwrap();
+        let (config, warnings) = Config::load_from(&path);
+        assert!(matches!(&warnings[..], [Warning::Invalid { backed_up: false, .. }]));
+        assert!(config.save_to(&path).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "[[[");
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn sessions_only_replace_their_own_table() {
+        let dir = temp_dir("session");
+        let path = dir.join(CONFIG_FILE);
+        fs::write(&path, "theme = \"light\"\n\n[session]\nshow_preview = true\n").unwrap();
+        let session = Session { cursor: Some("FPS".into()), ..Session::default() };
+        save_session_to(&path, &session).unwrap();
+        let (config, warnings) = Config::load_from(&path);
+        assert!(warnings.is_empty());
+        assert_eq!(config.theme, ThemeName::Light);
+        assert_eq!(config.session.cursor.as_deref(), Some("FPS"));
+        assert!(!config.session.show_preview);
+
+        fs::write(&path, "[[[").unwrap();
+        assert!(save_session_to(&path, &session).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "[[[");
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn unknown_language_codes_survive_a_save() {
+        let text = "language = \"xx-yy\"\n\n[session]\nlanguage = \"xx-yy\"\n";
+        let (config, warnings) = Config::parse(text);
+        assert!(warnings.is_empty());
+        assert_eq!((config.language(), config.session.language()), (None, None));
+        assert_eq!(toml::to_string(&config).unwrap().matches("language = \"xx-yy\"").count(), 2);
+    }
+
+    #[test]
+    fn unknown_setting_names_are_reported() {
+        let (config, warnings) = Config::parse("favorites = [\"fps\"]\nlocked = [\"v_snyc\"]\n");
+        assert!(matches!(&warnings[..], [Warning::Invalid { message, .. }] if message.contains("v_snyc")), "{warnings:?}");
+        assert!(config.favorites.is_empty() && config.locked.is_empty());
+    }
+}
@@ -0,0 +1,141 @@
+use crate::{Field, Lang};
+
+// ---------------------------------------------------------------------------
+// Setting descriptions (shown in the side panel)
+// ---------------------------------------------------------------------------
+
+/// Rough GPU/CPU cost of raising a setting.
+#[derive(Clone, Copy)]
+pub enum Cost {
+    Low,
+    Medium,
+    High,
+}
+
+pub fn cost(f: Field) -> Cost {
+    match f {
+        Field::Fps
+        | Field::RenderScale
+        | Field::ShadowQuality
+        | Field::ReflectionQuality => Cost::High,
+        Field::ResolutionQuality
+        | Field::LightQuality
+        | Field::CharacterQuality
+        | Field::EnvDetailQuality
+        | Field::SfxQuality => Cost::Medium,
+        Field::VSync
+        | Field::BloomQuality
+        | Field::AaMode
+        | Field::SelfShadow
+        | Field::DlssQuality
+        | Field::ParticleTrail => Cost::Low,
+    }
+}
+
+pub fn description(lang: Lang, f: Field) -> &'static str {
+    match lang {
+        // Translation files carry UI strings only.
+        Lang::En | Lang::Community(_) => match f {
+            Field::Fps               => "Upper limit on frames per second. Higher limits feel smoother but raise GPU load, heat and battery drain.",
+            Field::VSync             => "Synchronizes frames with the monitor's refresh rate to prevent screen tearing, at the cost of slightly higher input latency.",
+            Field::RenderScale       => "Internal rendering resolution relative to the window. Below 1.0 runs faster but looks blurry; above 1.0 supersamples for sharper edges.",
+            Field::ResolutionQuality => "Resolution of intermediate buffers such as post-processing. Lower values soften the image.",
+            Field::ShadowQuality     => "Resolution and draw distance of shadows cast by characters and the environment.",
+            Field::LightQuality      => "Quality of dynamic lights and indirect lighting in scenes.",
+            Field::CharacterQuality  => "Detail of character models and textures.",
+            Field::EnvDetailQuality  => "Density and draw distance of environment objects such as foliage and props.",
+            Field::ReflectionQuality => "Quality of reflections on water, floors and glass.",
+            Field::SfxQuality        => "Detail of skill and combat visual effects.",
+            Field::BloomQuality      => "Glow around bright light sources.",
+            Field::AaMode            => "Smooths jagged edges. Turning it off sharpens the image but edges shimmer in motion.",
+            Field::SelfShadow        => "Lets characters cast shadows onto themselves, adding depth to models.",
+            Field::DlssQuality       => "NVIDIA DLSS upscaling (RTX GPUs only). Renders at a lower resolution and reconstructs the image; replaces render scale and anti-aliasing.",
+            Field::ParticleTrail     => "Smoothness of the trails left behind by moving particle effects.",
+        },
+        Lang::Ko => match f {
+            Field::Fps               => "초당 프레임 수의 상한입니다. 높을수록 부드럽지만 GPU 부하, 발열, 배터리 소모가 늘어납니다.",
+            Field::VSync             => "프레임을 모니터 주사율에 맞춰 화면 찢어짐을 막습니다. 대신 입력 지연이 약간 늘어납니다.",
+            Field::RenderScale       => "창 크기 대비 내부 렌더링 해상도입니다. 1.0 미만은 빠르지만 흐릿하고, 1.0 초과는 슈퍼샘플링으로 더 선명해집니다.",
+            Field::ResolutionQuality => "후처리 등 중간 버퍼의 해상도입니다. 낮을수록 화면이 부드럽게 뭉개집니다.",
+            Field::ShadowQuality     => "캐릭터와 환경이 드리우는 그림자의 해상도와 표시 거리입니다.",
+            Field::LightQuality      => "장면의 동적 조명과 간접 조명 품질입니다.",
+            Field::CharacterQuality  => "캐릭터 모델과 텍스처의 디테일입니다.",
+            Field::EnvDetailQuality  => "식물, 소품 등 환경 오브젝트의 밀도와 표시 거리입니다.",
+            Field::ReflectionQuality => "물, 바닥, 유리에 비치는 반사의 품질입니다.",
+            Field::SfxQuality        => "스킬과 전투 시각 효과의 디테일입니다.",
+            Field::BloomQuality      => "밝은 광원 주변의 빛 번짐 효과입니다.",
+            Field::AaMode            => "계단 현상을 부드럽게 합니다. 끄면 화면이 선명해지지만 움직일 때 가장자리가 떨립니다.",
+            Field::SelfShadow        => "캐릭터가 자기 몸에 그림자를 드리워 모델에 입체감을 더합니다.",
+            Field::DlssQuality       => "NVIDIA DLSS 업스케일링 (RTX GPU 전용). 낮은 해상도로 렌더링한 뒤 복원하며, 렌더 스케일과 안티앨리어싱을 대체합니다.",
+            Field::ParticleTrail     => "움직이는 파티클 효과가 남기는 궤적의 부드러움입니다.",
+        },
+        Lang::Ja => match f {
+            Field::Fps               => "1秒あたりのフレーム数の上限です。高いほど滑らかですが、GPU負荷・発熱・バッテリー消費が増えます。",
+            Field::VSync             => "フレームをモニターのリフレッシュレートに同期させ、ティアリングを防ぎます。入力遅延がわずかに増えます。",
+            Field::RenderScale       => "ウィンドウに対する内部描画解像度です。1.0未満は軽くなりますがぼやけ、1.0超はスーパーサンプリングで鮮明になります。",
+            Field::ResolutionQuality => "ポストプロセスなど中間バッファの解像度です。低いほど画面が柔らかくなります。",
+            Field::ShadowQuality     => "キャラクターや環境が落とす影の解像度と描画距離です。",
+            Field::LightQuality      => "シーン内の動的ライトと間接光の品質です。",
+            Field::CharacterQuality  => "キャラクターモデルとテクスチャの精細さです。",
+            Field::EnvDetailQuality  => "植物や小物など環境オブジェクトの密度と描画距離です。",
+            Field::ReflectionQuality => "水面・床・ガラスに映る反射の品質です。",
+            Field::SfxQuality        => "スキルや戦闘エフェクトの精細さです。",
+            Field::BloomQuality      => "明るい光源の周りの光のにじみです。",
+            Field::AaMode            => "ジャギーを滑らかにします。オフにすると鮮明になりますが、動きの中で輪郭がちらつきます。",
+            Field::SelfShadow        => "キャラクター自身に影を落とし、モデルに立体感を加えます。",
+            Field::DlssQuality       => "NVIDIA DLSSアップスケーリング（RTX GPU専用）。低解像度で描画して復元します。レンダースケールとアンチエイリアスを置き換えます。",
+            Field::ParticleTrail     => "動くパーティクルエフェクトが残す軌跡の滑らかさです。",
+        },
+        Lang::Ru => match f {
+            Field::Fps               => "Ограничение кадров в секунду. Чем выше, тем плавнее, но растут нагрузка на GPU, нагрев и расход батареи.",
+            Field::VSync             => "Синхронизирует кадры с частотой обновления монитора и убирает разрывы изображения. Немного увеличивает задержку ввода.",
+            Field::RenderScale       => "Внутреннее разрешение относительно окна. Ниже 1,0 быстрее, но размыто; выше 1,0 \u{2014} суперсэмплинг и чёткость.",
+            Field::ResolutionQuality => "Разрешение промежуточных буферов, например постобработки. Чем ниже, тем мягче картинка.",
+            Field::ShadowQuality     => "Разрешение и дальность теней от персонажей и окружения.",
+            Field::LightQuality      => "Качество динамического и непрямого освещения сцены.",
+            Field::CharacterQuality  => "Детализация моделей и текстур персонажей.",
+            Field::EnvDetailQuality  => "Плотность и дальность прорисовки растительности и мелких объектов.",
+            Field::ReflectionQuality => "Качество отражений в воде, на полу и стекле.",
+            Field::SfxQuality        => "Детализация эффектов умений и боя.",
+            Field::BloomQuality      => "Ореол вокруг ярких источников света.",
+            Field::AaMode            => "Сглаживает ступенчатые края. Без него картинка резче, но контуры мерцают в движении.",
+            Field::SelfShadow        => "Персонажи отбрасывают тени на самих себя, что придаёт моделям объём.",
+            Field::DlssQuality       => "Масштабирование NVIDIA DLSS (только GPU RTX): рендеринг в низком разрешении с восстановлением. Заменяет масштаб рендеринга и сглаживание.",
+            Field::ParticleTrail     => "Плавность шлейфов, которые оставляют движущиеся частицы.",
+        },
+        Lang::Es => match f {
+            Field::Fps               => "Límite de fotogramas por segundo. Más alto es más fluido, pero aumenta la carga de la GPU, el calor y el consumo de batería.",
+            Field::VSync             => "Sincroniza los fotogramas con la frecuencia del monitor para evitar el tearing. Añade algo de retraso de entrada.",
+            Field::RenderScale       => "Resolución interna respecto a la ventana. Por debajo de 1,0 es más rápido pero borroso; por encima de 1,0 hace supermuestreo y gana nitidez.",
+            Field::ResolutionQuality => "Resolución de los búferes intermedios, como el posprocesado. Más bajo suaviza la imagen.",
+            Field::ShadowQuality     => "Resolución y distancia de las sombras de personajes y entorno.",
+            Field::LightQuality      => "Calidad de la iluminación dinámica e indirecta de la escena.",
+            Field::CharacterQuality  => "Detalle de los modelos y texturas de los personajes.",
+            Field::EnvDetailQuality  => "Densidad y distancia de dibujado de vegetación y objetos pequeños.",
+            Field::ReflectionQuality => "Calidad de los reflejos en agua, suelos y cristal.",
+            Field::SfxQuality        => "Detalle de los efectos de habilidades y combate.",
+            Field::BloomQuality      => "Resplandor alrededor de las fuentes de luz intensas.",
+            Field::AaMode            => "Suaviza los bordes dentados. Sin él la imagen es más nítida, pero los contornos parpadean en movimiento.",
+            Field::SelfShadow        => "Los personajes proyectan sombras sobre sí mismos, lo que da volumen a los modelos.",
+            Field::DlssQuality       => "Escalado NVIDIA DLSS (solo GPU RTX): renderiza a menor resolución y la reconstruye. Sustituye a la escala de renderizado y al antialiasing.",
+            Field::ParticleTrail     => "Suavidad de las estelas que dejan los efectos de partículas en movimiento.",
+        },
+        Lang::PtBr => match f {
+            Field::Fps               => "Limite de quadros por segundo. Mais alto é mais fluido, mas aumenta a carga da GPU, o calor e o consumo de bateria.",
+            Field::VSync             => "Sincroniza os quadros com a taxa de atualização do monitor para evitar tearing. Adiciona um pouco de atraso de entrada.",
+            Field::RenderScale       => "Resolução interna em relação à janela. Abaixo de 1,0 é mais rápido, mas borrado; acima de 1,0 faz supersampling e fica mais nítido.",
+            Field::ResolutionQuality => "Resolução dos buffers intermediários, como o pós-processamento. Mais baixo suaviza a imagem.",
+            Field::ShadowQuality     => "Resolução e distância das sombras de personagens e do ambiente.",
+            Field::LightQuality      => "Qualidade da iluminação dinâmica e indireta da cena.",
+            Field::CharacterQuality  => "Detalhe dos modelos e texturas dos personagens.",
+            Field::EnvDetailQuality  => "Densidade e distância de desenho de vegetação e objetos pequenos.",
+            Field::ReflectionQuality => "Qualidade dos reflexos na água, no chão e no vidro.",
+            Field::SfxQuality        => "Detalhe dos efeitos de habilidades e de combate.",
+            Field::BloomQuality      => "Brilho ao redor de fontes de luz intensas.",
+            Field::AaMode            => "Suaviza bordas serrilhadas. Sem ele a imagem fica mais nítida, mas os contornos tremulam em movimento.",
+            Field::SelfShadow        => "Os personagens projetam sombras sobre si mesmos, dando volume aos modelos.",
+            Field::DlssQuality       => "Upscaling NVIDIA DLSS (só GPUs RTX): renderiza em resolução menor e a reconstrói. Substitui a escala de renderização e o anti-aliasing.",
+            Field::ParticleTrail     => "Suavidade dos rastros deixados pelos efeitos de partículas em movimento.",
+        },
+    }
+}
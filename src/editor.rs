@@ -0,0 +1,143 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+    Frame,
+};
+
+// ---------------------------------------------------------------------------
+// Multi-line text editor
+// ---------------------------------------------------------------------------
+
+/// A minimal multi-line editor: insert, delete, arrow movement. Positions are
+/// in chars, not bytes, so non-ASCII text edits correctly.
+pub struct TextArea {
+    lines: Vec<Vec<char>>,
+    row: usize,
+    col: usize,
+}
+
+impl TextArea {
+    pub fn new(text: &str) -> Self {
+        let mut lines: Vec<Vec<char>> = text.lines().map(|l| l.chars().collect()).collect();
+        if lines.is_empty() {
+            lines.push(Vec::new());
+        }
+        Self { lines, row: 0, col: 0 }
+    }
+
+    pub fn text(&self) -> String {
+        self.lines
+            .iter()
+            .map(|l| l.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Moves the cursor to a 0-based line and column, clamped to the text.
+    pub fn set_cursor(&mut self, row: usize, col: usize) {
+        self.row = row.min(self.lines.len() - 1);
+        self.col = col.min(self.lines[self.row].len());
+    }
+
+    pub fn insert(&mut self, c: char) {
+        self.lines[self.row].insert(self.col, c);
+        self.col += 1;
+    }
+
+    pub fn newline(&mut self) {
+        let rest = self.lines[self.row].split_off(self.col);
+        self.lines.insert(self.row + 1, rest);
+        self.row += 1;
+        self.col = 0;
+    }
+
+    pub fn backspace(&mut self) {
+        if self.col > 0 {
+            self.col -= 1;
+            self.lines[self.row].remove(self.col);
+        } else if self.row > 0 {
+            let line = self.lines.remove(self.row);
+            self.row -= 1;
+            self.col = self.lines[self.row].len();
+            self.lines[self.row].extend(line);
+        }
+    }
+
+    pub fn delete(&mut self) {
+        if self.col < self.lines[self.row].len() {
+            self.lines[self.row].remove(self.col);
+        } else if self.row + 1 < self.lines.len() {
+            let next = self.lines.remove(self.row + 1);
+            self.lines[self.row].extend(next);
+        }
+    }
+
+    pub fn left(&mut self) {
+        if self.col > 0 {
+            self.col -= 1;
+        } else if self.row > 0 {
+            self.row -= 1;
+            self.col = self.lines[self.row].len();
+        }
+    }
+
+    pub fn right(&mut self) {
+        if self.col < self.lines[self.row].len() {
+            self.col += 1;
+        } else if self.row + 1 < self.lines.len() {
+            self.row += 1;
+            self.col = 0;
+        }
+    }
+
+    /// Moves `delta` lines up (negative) or down, keeping the column where possible.
+    pub fn vertical(&mut self, delta: isize) {
+        let row = (self.row as isize + delta).clamp(0, self.lines.len() as isize - 1);
+        self.set_cursor(row as usize, self.col);
+    }
+
+    pub fn home(&mut self) {
+        self.col = 0;
+    }
+
+    pub fn end(&mut self) {
+        self.col = self.lines[self.row].len();
+    }
+
+    /// Renders the visible window, scrolled so the cursor stays in view.
+    pub fn render(&self, frame: &mut Frame, area: Rect, block: Block) {
+        let inner = block.inner(area);
+        let height = inner.height as usize;
+        let width = inner.width as usize;
+        let top = if self.row >= height { self.row + 1 - height } else { 0 };
+        let left = if self.col >= width { self.col + 1 - width } else { 0 };
+        let cursor_style = Style::default().add_modifier(Modifier::REVERSED);
+
+        let lines: Vec<Line> = self
+            .lines
+            .iter()
+            .enumerate()
+            .skip(top)
+            .take(height)
+            .map(|(i, chars)| {
+                let visible: String = chars.iter().skip(left).collect();
+                if i != self.row {
+                    return Line::raw(visible);
+                }
+                let at = self.col - left;
+                let before: String = chars.iter().skip(left).take(at).collect();
+                let under = chars.get(self.col).map_or(" ".to_string(), |c| c.to_string());
+                let after: String = chars.iter().skip(self.col + 1).collect();
+                Line::from(vec![
+                    Span::raw(before),
+                    Span::styled(under, cursor_style),
+                    Span::raw(after),
+                ])
+            })
+            .collect();
+
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+}
@@ -0,0 +1,145 @@
+use std::borrow::Cow;
+
+use ratatui::symbols::scrollbar;
+
+// ---------------------------------------------------------------------------
+// Glyph sets (Unicode, or plain ASCII for fonts that lack the symbols)
+// ---------------------------------------------------------------------------
+
+/// Every symbol the UI draws besides text and borders.
+pub struct Glyphs {
+    /// Ahead of the selected row, including the trailing space.
+    pub pointer: &'static str,
+    /// Around a value that can be cycled.
+    pub prev: &'static str,
+    pub next: &'static str,
+    /// After a group header's label.
+    pub expanded: &'static str,
+    pub collapsed: &'static str,
+    /// A value that differs from the game's default.
+    pub modified: &'static str,
+    pub recommended: &'static str,
+    pub locked: &'static str,
+    /// Ahead of toasts, so severity doesn't rest on color alone.
+    pub info: &'static str,
+    pub success: &'static str,
+    pub warning: &'static str,
+    pub error: &'static str,
+    /// Between an old and a new value.
+    pub arrow: &'static str,
+    pub ellipsis: &'static str,
+    /// The knob of an on/off switch.
+    pub knob: &'static str,
+    /// The text cursor of single-line inputs.
+    pub cursor: &'static str,
+    pub up: &'static str,
+    pub down: &'static str,
+    pub left: &'static str,
+    pub right: &'static str,
+    pub spinner: &'static [char],
+    /// Gauges fill in eighths of a cell rather than whole cells.
+    pub fine_gauge: bool,
+    pub scrollbar: scrollbar::Set,
+    /// Typographic characters in translated messages and their stand-ins.
+    substitutions: &'static [(char, &'static str)],
+}
+
+pub const UNICODE: Glyphs = Glyphs {
+    pointer: "\u{25b8} ",
+    prev: "\u{25c2}",
+    next: "\u{25b8}",
+    expanded: "\u{25be}",
+    collapsed: "\u{25b8}",
+    modified: "\u{2022}",
+    recommended: "\u{2605}",
+    locked: "\u{1f512}",
+    info: "\u{2139}",
+    success: "\u{2714}",
+    warning: "\u{26a0}",
+    error: "\u{2716}",
+    arrow: "\u{2192}",
+    ellipsis: "\u{2026}",
+    knob: "\u{25cf}",
+    cursor: "\u{2588}",
+    up: "\u{2191}",
+    down: "\u{2193}",
+    left: "\u{2190}",
+    right: "\u{2192}",
+    spinner: &['\u{280b}', '\u{2819}', '\u{2839}', '\u{2838}', '\u{283c}', '\u{2834}', '\u{2826}', '\u{2827}', '\u{2807}', '\u{280f}'],
+    fine_gauge: true,
+    scrollbar: scrollbar::DOUBLE_VERTICAL,
+    substitutions: &[],
+};
+
+pub const ASCII: Glyphs = Glyphs {
+    pointer: "> ",
+    prev: "<",
+    next: ">",
+    expanded: "v",
+    collapsed: ">",
+    modified: "+",
+    recommended: "(r)",
+    locked: "(L)",
+    info: "i",
+    success: "+",
+    warning: "!",
+    error: "x",
+    arrow: "->",
+    ellipsis: "~",
+    knob: "o",
+    cursor: "_",
+    up: "Up",
+    down: "Down",
+    left: "Left",
+    right: "Right",
+    spinner: &['|', '/', '-', '\\'],
+    fine_gauge: false,
+    scrollbar: scrollbar::Set { track: "|", thumb: "#", begin: "^", end: "v" },
+    substitutions: &[
+        ('\u{2014}', "-"),
+        ('\u{2026}', "..."),
+        ('\u{2192}', "->"),
+        ('\u{2605}', "(r)"),
+    ],
+};
+
+impl Glyphs {
+    /// `text` with any symbol this set can't show replaced by its stand-in.
+    pub fn text<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if !text.chars().any(|c| self.substitutions.iter().any(|&(from, _)| from == c)) {
+            return Cow::Borrowed(text);
+        }
+        let mut out = String::with_capacity(text.len());
+        for c in text.chars() {
+            match self.substitutions.iter().find(|&&(from, _)| from == c) {
+                Some((_, to)) => out.push_str(to),
+                None => out.push(c),
+            }
+        }
+        Cow::Owned(out)
+    }
+}
+
+/// Best guess at whether the terminal can show the Unicode set. Windows
+/// Terminal, ConEmu and editor terminals announce themselves; the legacy
+/// console host doesn't, and its raster fonts lack most of the symbols.
+/// SSH sessions depend on the client's font, so PuTTY and friends get ASCII,
+/// as does a `TERM` naming a terminal without Unicode, wherever it runs.
+pub fn detect() -> &'static Glyphs {
+    let set = |name: &str| std::env::var_os(name).is_some_and(|v| !v.is_empty());
+    let term = std::env::var("TERM").unwrap_or_default().to_ascii_lowercase();
+    if set("SSH_CONNECTION") || basic_term(&term) {
+        return &ASCII;
+    }
+    if set("WT_SESSION") || set("TERM_PROGRAM") || set("ConEmuANSI") {
+        &UNICODE
+    } else {
+        &ASCII
+    }
+}
+
+/// Terminal types known to lack the symbols: `dumb`, the DEC VT family
+/// and console drivers limited to their own code page.
+fn basic_term(term: &str) -> bool {
+    matches!(term, "dumb" | "linux" | "cons25" | "ansi") || term.starts_with("vt")
+}
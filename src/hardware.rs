@@ -0,0 +1,92 @@
+use winreg::{enums::*, RegKey};
+
+/// Display adapter class key; each installed adapter is a numbered subkey.
+const DISPLAY_CLASS: &str =
+    r"SYSTEM\CurrentControlSet\Control\Class\{4d36e968-e325-11ce-bfc1-08002be10318}";
+
+// ---------------------------------------------------------------------------
+// GPU detection (driver registry entries, no graphics API needed)
+// ---------------------------------------------------------------------------
+
+pub struct Gpu {
+    pub name: String,
+    /// Dedicated video memory in bytes.
+    pub vram: u64,
+}
+
+/// The display adapter with the most dedicated memory, or `None` when no
+/// driver entry is readable.
+pub fn detect_gpu() -> Option<Gpu> {
+    let class = RegKey::predef(HKEY_LOCAL_MACHINE).open_subkey(DISPLAY_CLASS).ok()?;
+    class
+        .enum_keys()
+        .filter_map(Result::ok)
+        .filter_map(|sub| {
+            let key = class.open_subkey(&sub).ok()?;
+            let name: String = key.get_value("DriverDesc").ok()?;
+            let vram = adapter_memory(&key)?;
+            Some(Gpu { name, vram })
+        })
+        .max_by_key(|gpu| gpu.vram)
+}
+
+/// Current drivers write a QWORD `qwMemorySize`; older ones only a 32-bit
+/// `MemorySize`, which tops out at 4 GB.
+fn adapter_memory(key: &RegKey) -> Option<u64> {
+    if let Ok(size) = key.get_value::<u64, _>("HardwareInformation.qwMemorySize") {
+        return Some(size);
+    }
+    let raw = key.get_raw_value("HardwareInformation.MemorySize").ok()?;
+    let bytes: [u8; 4] = raw.bytes.get(..4)?.try_into().ok()?;
+    Some(u32::from_le_bytes(bytes) as u64)
+}
+
+// ---------------------------------------------------------------------------
+// Refresh rate (what VSync caps the frame rate to)
+// ---------------------------------------------------------------------------
+
+/// The primary display's current refresh rate in Hz, which the game runs
+/// on unless moved. `None` when Windows only reports the hardware default.
+#[cfg(windows)]
+pub fn refresh_rate() -> Option<i64> {
+    /// `DEVMODEW`, with the fields this doesn't read left as padding.
+    #[repr(C)]
+    struct DevMode {
+        device_name: [u16; 32],
+        spec_version: u16,
+        driver_version: u16,
+        size: u16,
+        driver_extra: u16,
+        fields: u32,
+        position: [u32; 4],
+        printer: [i16; 5],
+        form_name: [u16; 32],
+        log_pixels: u16,
+        bits_per_pel: u32,
+        pels_width: u32,
+        pels_height: u32,
+        display_flags: u32,
+        display_frequency: u32,
+        rest: [u32; 8],
+    }
+    const _: () = assert!(std::mem::size_of::<DevMode>() == 220);
+    #[link(name = "user32")]
+    unsafe extern "system" {
+        fn EnumDisplaySettingsW(device: *const u16, mode: u32, devmode: *mut DevMode) -> i32;
+    }
+    const ENUM_CURRENT_SETTINGS: u32 = u32::MAX;
+    // SAFETY: all-zero is a valid DEVMODEW.
+    let mut mode: DevMode = unsafe { std::mem::zeroed() };
+    mode.size = std::mem::size_of::<DevMode>() as u16;
+    // SAFETY: a null device means the primary display; `mode` is sized above.
+    if unsafe { EnumDisplaySettingsW(std::ptr::null(), ENUM_CURRENT_SETTINGS, &mut mode) } == 0 {
+        return None;
+    }
+    // 0 and 1 stand for the hardware's default rate.
+    Some(mode.display_frequency).filter(|&hz| hz > 1).map(i64::from)
+}
+
+#[cfg(not(windows))]
+pub fn refresh_rate() -> Option<i64> {
+    None
+}
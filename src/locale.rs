@@ -0,0 +1,58 @@
+use crate::Lang;
+
+// ---------------------------------------------------------------------------
+// System language detection (picker preselection on first launch)
+// ---------------------------------------------------------------------------
+
+/// A supported language matching the system's UI language.
+pub struct Detected {
+    pub lang: Lang,
+    /// The match is exact enough to skip the picker; otherwise it is only
+    /// preselected, e.g. Brazilian Portuguese for a Portugal locale.
+    pub confident: bool,
+}
+
+/// The user's display language, not the regional format: someone reading
+/// an English UI with Korean dates still wants English.
+pub fn detect() -> Option<Detected> {
+    system_tag().as_deref().and_then(from_tag)
+}
+
+/// Maps a BCP 47 tag (`ko-KR`) or POSIX locale (`pt_BR.UTF-8`) to a
+/// language. Translation files named after the full tag win over a
+/// built-in language matching only its first part.
+fn from_tag(tag: &str) -> Option<Detected> {
+    let tag = tag.split(['.', '@']).next().unwrap_or_default().replace('_', "-").to_ascii_lowercase();
+    if let Some(lang) = Lang::parse(&tag) {
+        return Some(Detected { lang, confident: true });
+    }
+    let (primary, _) = tag.split_once('-')?;
+    let lang = Lang::parse(primary)?;
+    // `pt` resolves to Brazilian Portuguese, which isn't what a pt-PT user reads.
+    Some(Detected { lang, confident: primary != "pt" })
+}
+
+#[cfg(windows)]
+fn system_tag() -> Option<String> {
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn GetUserDefaultUILanguage() -> u16;
+        fn LCIDToLocaleName(locale: u32, name: *mut u16, len: i32, flags: u32) -> i32;
+    }
+    const LOCALE_NAME_MAX_LENGTH: usize = 85;
+    let mut name = [0u16; LOCALE_NAME_MAX_LENGTH];
+    // SAFETY: both take plain values, and `name` is as long as the length passed.
+    let len = unsafe { LCIDToLocaleName(GetUserDefaultUILanguage().into(), name.as_mut_ptr(), name.len() as i32, 0) };
+    // The length counts the terminating NUL; 0 means failure.
+    let len = usize::try_from(len).ok().filter(|&n| n > 1)?;
+    String::from_utf16(&name[..len - 1]).ok()
+}
+
+/// Elsewhere, the usual POSIX precedence.
+#[cfg(not(windows))]
+fn system_tag() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|v| !v.is_empty() && v != "C" && v != "POSIX")
+}
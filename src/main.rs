@@ -1,136 +1,2140 @@
-use std::io;
+mod clipboard;
+mod code;
+mod config;
+mod descriptions;
+mod editor;
+mod glyphs;
+mod hardware;
+mod locale;
+mod message;
+mod script;
+mod slider;
+mod snapshot;
+mod theme;
+mod translations;
+mod watch;
 
-use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use std::{
+    borrow::Cow,
+    collections::VecDeque,
+    fmt, io,
+    rc::Rc,
+    sync::{mpsc, OnceLock},
+    time::{Duration, Instant},
+};
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 use ratatui::{
-    layout::{Constraint, Layout},
+    backend::TestBackend,
+    layout::{Alignment, Constraint, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
-    Frame,
+    widgets::{Block, Borders, Clear, Padding, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, Wrap},
+    Frame, Terminal,
 };
 use serde::{Deserialize, Serialize};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 use winreg::{enums::*, RegKey, RegValue};
 
+use code::CodeError;
+use config::{Config, Session};
+use editor::TextArea;
+use glyphs::Glyphs;
+use theme::{Theme, ThemeName};
+
 const REG_PATH: &str = r"Software\Cognosphere\Star Rail";
+/// The mainland China client keeps the same value, in the same format, here.
+const CN_REG_PATH: &str = r"Software\miHoYo\崩坏：星穹铁道";
 const REG_VALUE: &str = "GraphicsSettings_Model_h2986158309";
+/// Unity appends a hash of the PlayerPrefs key name; a game update that
+/// changes it leaves the old value behind under the same prefix.
+const REG_VALUE_PREFIX: &str = "GraphicsSettings_Model_h";
+
+/// Held-arrow acceleration: after this many repeats the step doubles, after
+/// `ACCEL_FAST` it triples. A gap longer than `ACCEL_RESET` starts over.
+const ACCEL_THRESHOLD: u32 = 6;
+const ACCEL_FAST: u32 = 14;
+const ACCEL_RESET: Duration = Duration::from_millis(300);
+/// Lists shorter than this don't accelerate; the quality tiers are the
+/// shortest that do.
+const ACCEL_MIN_OPTIONS: isize = 5;
+/// Options skipped by Shift+←/→ (0.6 on the render scale).
+const COARSE_STEP: isize = 3;
+/// Undo steps kept; the oldest is dropped past this.
+const UNDO_DEPTH: usize = 100;
+/// How long info and success toasts stay up; they dim for the last `TOAST_FADE`.
+const TOAST_TTL: Duration = Duration::from_secs(5);
+const TOAST_FADE: Duration = Duration::from_secs(1);
+/// Older toasts are dropped beyond this many.
+const MAX_TOASTS: usize = 4;
+/// Event log entries kept; the oldest is dropped past this.
+const LOG_CAPACITY: usize = 200;
+/// Height of the event log panel, borders included.
+const LOG_HEIGHT: u16 = 8;
+/// How often the status-bar spinner advances while registry I/O is running.
+const SPINNER_TICK: Duration = Duration::from_millis(80);
+/// How often the main loop wakes without input, for autosave, I/O
+/// completion and anything else timed. Frames are drawn only on change.
+const TICK: Duration = Duration::from_millis(100);
+
+/// FPS caps to cycle through when `fps_options` isn't configured.
+const FPS_OPTIONS: [i64; 3] = [30, 60, 120];
+
+/// Display width of the label column in the settings list, at least.
+const LABEL_WIDTH: usize = 24;
+/// Width of the render scale gauge, and while its row is selected.
+const GAUGE_WIDTH: usize = 10;
+const GAUGE_WIDTH_SELECTED: usize = 16;
+
+/// Below this frame size the settings screen is replaced by a notice.
+const MIN_WIDTH: u16 = 50;
+const MIN_HEIGHT: u16 = 15;
+
+/// List interiors wider than this are split into two columns.
+const TWO_COLUMN_MIN_WIDTH: u16 = 100;
+/// Width of the side panel (description or JSON preview), and the narrowest terminal that still fits it.
+const SIDE_PANEL_WIDTH: u16 = 40;
+const SIDE_PANEL_MIN_TOTAL: u16 = 84;
+
+/// Exit code of the command-line modes when the game has no settings value.
+const EXIT_MISSING: i32 = 3;
 
 // ---------------------------------------------------------------------------
 // Localization
 // ---------------------------------------------------------------------------
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 enum Lang {
     En,
     Ko,
     Ja,
+    Ru,
+    Es,
+    PtBr,
+    /// An entry of `translations::all()`.
+    Community(usize),
+}
+
+impl Lang {
+    const BUILT_IN: [Lang; 6] = [Lang::En, Lang::Ko, Lang::Ja, Lang::Ru, Lang::Es, Lang::PtBr];
+
+    /// The built-in languages, then any loaded from translation files.
+    fn all() -> Vec<Lang> {
+        let community = (0..translations::all().len()).map(Lang::Community);
+        Lang::BUILT_IN.into_iter().chain(community).collect()
+    }
+
+    /// Parses a `--lang` argument or a configured `language`.
+    fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "en" => Some(Lang::En),
+            "ko" => Some(Lang::Ko),
+            "ja" => Some(Lang::Ja),
+            "ru" => Some(Lang::Ru),
+            "es" => Some(Lang::Es),
+            "pt-br" | "pt_br" | "pt" => Some(Lang::PtBr),
+            _ => translations::all().iter().position(|t| t.code.eq_ignore_ascii_case(s)).map(Lang::Community),
+        }
+    }
+
+    /// What `parse` takes and the config file stores.
+    fn code(self) -> &'static str {
+        match self {
+            Lang::En => "en",
+            Lang::Ko => "ko",
+            Lang::Ja => "ja",
+            Lang::Ru => "ru",
+            Lang::Es => "es",
+            Lang::PtBr => "pt-br",
+            Lang::Community(i) => &translations::all()[i].code,
+        }
+    }
+
+    /// The language's own name for itself.
+    fn name(self) -> &'static str {
+        match self {
+            Lang::En => "English",
+            Lang::Ko => "한국어",
+            Lang::Ja => "日本語",
+            Lang::Ru => "Русский",
+            Lang::Es => "Español",
+            Lang::PtBr => "Português (Brasil)",
+            Lang::Community(i) => &translations::all()[i].name,
+        }
+    }
+
+    /// Strings shown in a fallback language.
+    fn missing(self) -> usize {
+        match self {
+            Lang::Community(i) => translations::all()[i].missing,
+            lang => built_in_tables()[lang.built_in_index()].1,
+        }
+    }
+
+    /// Position in `BUILT_IN`; 0 (English) for community languages.
+    fn built_in_index(self) -> usize {
+        Lang::BUILT_IN.iter().position(|&l| l == self).unwrap_or(0)
+    }
+
+    /// A built-in language's strings, laid over `ENGLISH`; none for
+    /// English itself and community languages.
+    fn translated(self) -> Option<&'static [(&'static str, &'static str)]> {
+        match self {
+            Lang::Ko => Some(KOREAN),
+            Lang::Ja => Some(JAPANESE),
+            Lang::Ru => Some(RUSSIAN),
+            Lang::Es => Some(SPANISH),
+            Lang::PtBr => Some(PORTUGUESE_BR),
+            Lang::En | Lang::Community(_) => None,
+        }
+    }
+}
+
+impl Serialize for Lang {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.code())
+    }
+}
+
+/// Field names double as the keys of translation files, so don't rename them.
+/// `ENGLISH` is written as `L10n<&'static str>`; every table in use is
+/// owned, built once by laying a language's strings over it.
+#[derive(Clone, Serialize, Deserialize)]
+struct L10n<S = String> {
+    title: S,
+    saved_bytes: S,
+    save_failed: S,
+    no_registry: S,
+    on: S,
+    off: S,
+    fps: S,
+    vsync: S,
+    render_scale: S,
+    resolution_quality: S,
+    shadow_quality: S,
+    light_quality: S,
+    character_quality: S,
+    env_detail: S,
+    reflection_quality: S,
+    sfx_quality: S,
+    bloom_quality: S,
+    anti_aliasing: S,
+    self_shadow: S,
+    dlss_quality: S,
+    particle_trail: S,
+    group_display: S,
+    group_quality: S,
+    group_advanced: S,
+    group_count: S,
+    config_save_failed: S,
+    safe_mode_badge: S,
+    safe_mode_on: S,
+    safe_mode_off: S,
+    safe_mode_blocked: S,
+    safe_mode_rejected: S,
+    recommended_shown: S,
+    recommended_hidden: S,
+    default_value: S,
+    unsaved_changes: S,
+    conflict_dlss_render_scale: S,
+    conflict_dlss_aa: S,
+    conflict_vsync_fps: S,
+    confirm_disable_vsync: S,
+    prompt_merge: S,
+    merged: S,
+    import_failed: S,
+    preview_title: S,
+    desc_title: S,
+    desc_cost: S,
+    desc_recommended: S,
+    desc_group: S,
+    cost_low: S,
+    cost_medium: S,
+    cost_high: S,
+    json_edit_title: S,
+    json_applied: S,
+    json_invalid: S,
+    confirm_discard_json: S,
+    working: S,
+    too_small: S,
+    hint_navigate: S,
+    hint_change: S,
+    hint_set: S,
+    hint_toggle: S,
+    hint_fold: S,
+    hint_unfold: S,
+    hint_column: S,
+    hint_scroll: S,
+    hint_info: S,
+    hint_preview: S,
+    hint_edit: S,
+    hint_save: S,
+    hint_quit: S,
+    hint_close: S,
+    hint_confirm: S,
+    hint_cancel: S,
+    hint_apply: S,
+    hint_yes: S,
+    hint_no: S,
+    hint_dismiss: S,
+    no_unsaved_changes: S,
+    no_changes: S,
+    confirm_save: S,
+    quit_unsaved: S,
+    quit_save: S,
+    quit_discard: S,
+    quit_cancel: S,
+    hint_save_quit: S,
+    prompt_export: S,
+    exported: S,
+    export_failed: S,
+    hint_format: S,
+    title_unsaved: S,
+    saved_key_created: S,
+    read_only_badge: S,
+    read_only_refused: S,
+    help_title: S,
+    help_navigation: S,
+    help_editing: S,
+    help_files: S,
+    help_application: S,
+    help_move_up: S,
+    help_move_down: S,
+    help_activate: S,
+    help_switch_column: S,
+    help_left_column: S,
+    help_right_column: S,
+    help_scroll_up: S,
+    help_scroll_down: S,
+    help_cycle_next: S,
+    help_cycle_prev: S,
+    help_set_tier: S,
+    help_safe_mode: S,
+    help_edit_json: S,
+    help_save: S,
+    help_save_quit: S,
+    help_merge_import: S,
+    help_export: S,
+    help_recommended: S,
+    help_description: S,
+    help_defaults: S,
+    help_preview: S,
+    help_help: S,
+    help_back: S,
+    help_quit: S,
+    hint_help: S,
+    /// Shown in place of the dot in displayed decimals (render scale).
+    decimal_separator: S,
+    quality_level_question: S,
+    quality_level_set: S,
+    help_set_all_quality: S,
+    locked_refused: S,
+    help_lock: S,
+    undid: S,
+    redid: S,
+    n_changes: S,
+    nothing_to_undo: S,
+    nothing_to_redo: S,
+    help_undo: S,
+    help_redo: S,
+    preset_low: S,
+    preset_medium: S,
+    preset_high: S,
+    preset_applied: S,
+    preset_suggested: S,
+    help_apply_suggested: S,
+    unknown_gpu: S,
+    confirm_reset_all: S,
+    reset_field: S,
+    reset_all: S,
+    help_reset_field: S,
+    help_reset_all: S,
+    reverted_field: S,
+    reverted_all: S,
+    help_revert_field: S,
+    help_revert_all: S,
+    copied: S,
+    copy_failed: S,
+    help_copy_value: S,
+    copied_bytes: S,
+    help_copy_json: S,
+    help_copy_markdown: S,
+    help_json_keys: S,
+    help_mark: S,
+    mark_incompatible: S,
+    hint_mark: S,
+    hint_change_marked: S,
+    hint_clear_marks: S,
+    legacy_ignored: S,
+    legacy_migrated: S,
+    legacy_migrate_failed: S,
+    confirm_migrate_legacy: S,
+    autosave_badge: S,
+    help_cycle_next_coarse: S,
+    help_cycle_prev_coarse: S,
+    help_cycle_max: S,
+    help_cycle_min: S,
+    hint_coarse: S,
+    hint_min_max: S,
+    help_log: S,
+    log_title: S,
+    prompt_set_value: S,
+    set_value_usage: S,
+    no_such_setting: S,
+    ambiguous_setting: S,
+    invalid_value: S,
+    help_set_value: S,
+    preset_badge: S,
+    group_favorites: S,
+    help_favorite: S,
+    help_favorites_only: S,
+    registry_not_utf8: S,
+    registry_invalid: S,
+    choose_theme: S,
+    theme_dark: S,
+    theme_light: S,
+    theme_solarized: S,
+    help_theme: S,
+    help_spacing: S,
+    help_copy_code: S,
+    help_apply_code: S,
+    prompt_code: S,
+    code_copied: S,
+    code_not_a_code: S,
+    code_damaged: S,
+    code_newer: S,
+    code_applied: S,
+    theme_high_contrast: S,
+    code_bad_character: S,
+    code_unknown_version: S,
+    code_truncated: S,
+    client_global: S,
+    client_cn: S,
+    sync_clients_question: S,
+    sync_keep_both: S,
+    clients_synced: S,
+    clients_sync_failed: S,
+    help_show_message: S,
+    hint_full_message: S,
+    message_title: S,
+    message_close: S,
+    help_preferences: S,
+    prefs_title: S,
+    pref_language: S,
+    pref_ask: S,
+    pref_theme: S,
+    pref_wrap: S,
+    pref_confirm_saves: S,
+    pref_vsync_autofix: S,
+    pref_sync_clients: S,
+    pref_row_spacing: S,
+    pref_autosave: S,
+    pref_seconds: S,
+    config_unknown_key: S,
+    config_invalid: S,
+    hive_notice: S,
+    choose_language: S,
+    help_language: S,
+    pref_skip_picker: S,
+    external_change_question: S,
+    external_merge: S,
+    external_overwrite: S,
+    external_merged: S,
+    translation_missing: S,
+    translation_unknown: S,
+    translation_built_in: S,
+    translation_invalid: S,
+    save_creates_key: S,
+    pref_untranslated: S,
+    pref_category_colors: S,
+    /// How `{n|…}` forms are picked: `one`, `zero_one`, `slavic` or `none`.
+    plural_rule: S,
+    /// Joins a failed action and its cause: `{what}` and `{error}`.
+    failure: S,
+    translation_placeholders: S,
+    may_run_as: S,
+    saving: S,
+    save_in_progress: S,
+    quit_when_saved: S,
+    external_reloaded: S,
+    tier_very_low: S,
+    tier_low: S,
+    tier_medium: S,
+    tier_high: S,
+    tier_very_high: S,
+    pref_tier_names: S,
+    help_tier_names: S,
+    help_reload: S,
+    confirm_reload: S,
+    reloaded: S,
+    code_out_of_range: S,
+    config_invalid_kept: S,
 }
 
-struct L10n {
-    title: &'static str,
-    hint: &'static str,
-    saved: &'static str,
-    save_failed: &'static str,
-    no_registry: &'static str,
-    on: &'static str,
-    off: &'static str,
-    fps: &'static str,
-    vsync: &'static str,
-    render_scale: &'static str,
-    resolution_quality: &'static str,
-    shadow_quality: &'static str,
-    light_quality: &'static str,
-    character_quality: &'static str,
-    env_detail: &'static str,
-    reflection_quality: &'static str,
-    sfx_quality: &'static str,
-    bloom_quality: &'static str,
-    anti_aliasing: &'static str,
-    self_shadow: &'static str,
-    dlss_quality: &'static str,
-    particle_trail: &'static str,
+impl L10n {
+    /// `template` with its `{placeholders}` filled in; see `message::fill`.
+    fn fill(&self, template: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+        message::fill(template, &self.plural_rule, args)
+    }
+
+    /// What failed, then why, in the order the language puts them.
+    fn failure(&self, what: &str, error: impl fmt::Display) -> String {
+        self.fill(&self.failure, &[("what", &what), ("error", &error)])
+    }
+
+    /// The game's name for quality tier `level` (1-5).
+    fn tier(&self, level: i64) -> Option<&str> {
+        match level {
+            1 => Some(&self.tier_very_low),
+            2 => Some(&self.tier_low),
+            3 => Some(&self.tier_medium),
+            4 => Some(&self.tier_high),
+            5 => Some(&self.tier_very_high),
+            _ => None,
+        }
+    }
 }
 
 fn l10n(lang: Lang) -> &'static L10n {
     match lang {
-        Lang::En => &L10n {
-            title: " Star Rail Graphics Settings ",
-            hint: " \u{2191}\u{2193} Navigate  \u{2190}\u{2192} Change  S Save  Q Quit ",
-            saved: "Settings saved.",
-            save_failed: "Save failed",
-            no_registry: "Registry key not found \u{2014} using defaults. Save to create it.",
-            on: "On",
-            off: "Off",
-            fps: "FPS",
-            vsync: "VSync",
-            render_scale: "Render Scale",
-            resolution_quality: "Resolution Quality",
-            shadow_quality: "Shadow Quality",
-            light_quality: "Light Quality",
-            character_quality: "Character Quality",
-            env_detail: "Environment Detail",
-            reflection_quality: "Reflection Quality",
-            sfx_quality: "SFX Quality",
-            bloom_quality: "Bloom Quality",
-            anti_aliasing: "Anti-Aliasing",
-            self_shadow: "Self Shadow",
-            dlss_quality: "DLSS Quality",
-            particle_trail: "Particle Trail",
-        },
-        Lang::Ko => &L10n {
-            title: " 붕괴 : 스타레일 그래픽 설정 ",
-            hint: " \u{2191}\u{2193} 이동  \u{2190}\u{2192} 변경  S 저장  Q 종료 ",
-            saved: "설정이 저장되었습니다.",
-            save_failed: "저장 실패",
-            no_registry: "레지스트리 키를 찾을 수 없습니다 \u{2014} 기본값 사용 중. 저장하여 생성하세요.",
-            on: "켜기",
-            off: "끄기",
-            fps: "FPS",
-            vsync: "수직 동기화",
-            render_scale: "렌더 스케일",
-            resolution_quality: "해상도 품질",
-            shadow_quality: "그림자 품질",
-            light_quality: "조명 품질",
-            character_quality: "캐릭터 품질",
-            env_detail: "환경 디테일",
-            reflection_quality: "반사 품질",
-            sfx_quality: "효과 품질",
-            bloom_quality: "블룸 품질",
-            anti_aliasing: "안티앨리어싱",
-            self_shadow: "셀프 쉘도우",
-            dlss_quality: "DLSS 품질",
-            particle_trail: "파티클 트레일",
-        },
-        Lang::Ja => &L10n {
-            title: " 崩壊：スターレイル グラフィック設定 ",
-            hint: " \u{2191}\u{2193} 移動  \u{2190}\u{2192} 変更  S 保存  Q 終了 ",
-            saved: "設定が保存されました。",
-            save_failed: "保存失敗",
-            no_registry: "レジストリキーが見つかりません \u{2014} デフォルト値を使用中。保存して作成してください。",
-            on: "オン",
-            off: "オフ",
-            fps: "FPS",
-            vsync: "垂直同期",
-            render_scale: "レンダースケール",
-            resolution_quality: "解像度品質",
-            shadow_quality: "影の品質",
-            light_quality: "ライト品質",
-            character_quality: "キャラクター品質",
-            env_detail: "環境ディテール",
-            reflection_quality: "反射品質",
-            sfx_quality: "エフェクト品質",
-            bloom_quality: "ブルーム品質",
-            anti_aliasing: "アンチエイリアス",
-            self_shadow: "セルフシャドウ",
-            dlss_quality: "DLSS品質",
-            particle_trail: "パーティクルトレイル",
-        },
+        Lang::Community(i) => &translations::all()[i].table,
+        _ => &built_in_tables()[lang.built_in_index()].0,
     }
 }
 
+/// Each built-in language's strings, owned, with how many of them fell
+/// back to English. Built the first time a string is needed.
+fn built_in_tables() -> &'static [(L10n, usize)] {
+    static TABLES: OnceLock<Vec<(L10n, usize)>> = OnceLock::new();
+    TABLES.get_or_init(|| {
+        let english: L10n =
+            serde_json::to_value(&ENGLISH).and_then(serde_json::from_value).expect("L10n round-trips through JSON");
+        Lang::BUILT_IN
+            .into_iter()
+            .map(|lang| match lang.translated() {
+                Some(strings) => translations::lay_over(&english, strings),
+                None => (english.clone(), 0),
+            })
+            .collect()
+    })
+}
+
+/// The English strings. Complete, as every other table falls back to them.
+const ENGLISH: L10n<&str> = L10n {
+    title: " Star Rail Graphics Settings ",
+    saved_bytes: "Settings saved ({n} bytes).",
+    save_failed: "Save failed",
+    no_registry: "Registry key not found \u{2014} using defaults. Save to create it.",
+    on: "On",
+    off: "Off",
+    fps: "FPS",
+    vsync: "VSync",
+    render_scale: "Render Scale",
+    resolution_quality: "Resolution Quality",
+    shadow_quality: "Shadow Quality",
+    light_quality: "Light Quality",
+    character_quality: "Character Quality",
+    env_detail: "Environment Detail",
+    reflection_quality: "Reflection Quality",
+    sfx_quality: "SFX Quality",
+    bloom_quality: "Bloom Quality",
+    anti_aliasing: "Anti-Aliasing",
+    self_shadow: "Self Shadow",
+    dlss_quality: "DLSS Quality",
+    particle_trail: "Particle Trail",
+    group_display: "Display",
+    group_quality: "Quality",
+    group_advanced: "Advanced",
+    group_count: "{n} {n|setting|settings}",
+    config_save_failed: "Config save failed",
+    safe_mode_badge: "[Safe] ",
+    safe_mode_on: "Safe mode on \u{2014} only compatible values can be selected.",
+    safe_mode_off: "Safe mode off.",
+    safe_mode_blocked: "No other value is compatible with the current settings.",
+    safe_mode_rejected: "That value conflicts with other settings (safe mode).",
+    recommended_shown: "Recommended values marked with \u{2605}.",
+    recommended_hidden: "Recommended markers hidden.",
+    default_value: "default",
+    unsaved_changes: "{n} unsaved {n|change|changes}",
+    conflict_dlss_render_scale: "DLSS ignores render scale",
+    conflict_dlss_aa: "DLSS replaces anti-aliasing",
+    conflict_vsync_fps: "VSync overrides the FPS cap",
+    confirm_disable_vsync: "VSync will ignore this FPS cap. Turn VSync off?",
+    prompt_merge: "Merge import from: ",
+    merged: "Merged {n} changed {n|setting|settings}. Press S to save.",
+    import_failed: "Import failed",
+    preview_title: " JSON preview ",
+    desc_title: " About ",
+    desc_cost: "Performance cost",
+    desc_recommended: "Recommended",
+    desc_group: "Select a setting to see what it does.",
+    cost_low: "Low",
+    cost_medium: "Medium",
+    cost_high: "High",
+    json_edit_title: " Edit JSON ",
+    json_applied: "JSON applied. Press S to save.",
+    json_invalid: "Invalid JSON",
+    confirm_discard_json: "Discard your JSON edits?",
+    working: "Working\u{2026}",
+    too_small: "Terminal too small \u{2014} need at least {w}x{h}",
+    hint_navigate: "Navigate",
+    hint_change: "Change",
+    hint_set: "Set",
+    hint_toggle: "Toggle",
+    hint_fold: "Fold",
+    hint_unfold: "Unfold",
+    hint_column: "Column",
+    hint_scroll: "Scroll",
+    hint_info: "Info",
+    hint_preview: "JSON",
+    hint_edit: "Edit",
+    hint_save: "Save",
+    hint_quit: "Quit",
+    hint_close: "Close",
+    hint_confirm: "Confirm",
+    hint_cancel: "Cancel",
+    hint_apply: "Apply",
+    hint_yes: "Yes",
+    hint_no: "No",
+    hint_dismiss: "Dismiss",
+    no_unsaved_changes: "No unsaved changes",
+    no_changes: "No changes to save.",
+    confirm_save: "Save these changes?",
+    quit_unsaved: "You have unsaved changes.",
+    quit_save: "[S]ave and quit",
+    quit_discard: "[D]iscard",
+    quit_cancel: "[C]ancel",
+    hint_save_quit: "Save & quit",
+    prompt_export: "Export ({format}) to: ",
+    exported: "Exported to {path}",
+    export_failed: "Export failed",
+    hint_format: "Format",
+    title_unsaved: "(unsaved)",
+    saved_key_created: "Settings saved ({n} bytes, registry key created).",
+    read_only_badge: "[read-only]",
+    read_only_refused: "Read-only mode: changes are disabled.",
+    help_title: " Keybindings ",
+    help_navigation: "Navigation",
+    help_editing: "Editing",
+    help_files: "Files",
+    help_application: "Application",
+    help_move_up: "Move up",
+    help_move_down: "Move down",
+    help_activate: "Fold a group / flip a toggle",
+    help_switch_column: "Switch column",
+    help_left_column: "Jump to the left column",
+    help_right_column: "Jump to the right column",
+    help_scroll_up: "Scroll the JSON preview up",
+    help_scroll_down: "Scroll the JSON preview down",
+    help_cycle_next: "Next value",
+    help_cycle_prev: "Previous value",
+    help_set_tier: "Set a quality tier directly",
+    help_safe_mode: "Toggle safe mode",
+    help_edit_json: "Edit the raw JSON",
+    help_save: "Save to the registry",
+    help_save_quit: "Save and quit",
+    help_merge_import: "Merge a settings file",
+    help_export: "Export to a file",
+    help_recommended: "Show recommended markers",
+    help_description: "Show the description panel",
+    help_defaults: "Show default values",
+    help_preview: "Show the JSON preview",
+    help_help: "Show this help",
+    help_back: "Dismiss / close panel / quit",
+    help_quit: "Quit",
+    hint_help: "Help",
+    decimal_separator: ".",
+    quality_level_question: "Set every quality setting to:",
+    quality_level_set: "Set {n} quality {n|setting|settings} to {level}.",
+    help_set_all_quality: "Set all quality settings at once",
+    locked_refused: "This setting is locked (F to unlock).",
+    help_lock: "Lock / unlock the setting",
+    undid: "Undid {change}.",
+    redid: "Redid {change}.",
+    n_changes: "{n} changes",
+    nothing_to_undo: "Nothing to undo.",
+    nothing_to_redo: "Nothing to redo.",
+    help_undo: "Undo",
+    help_redo: "Redo",
+    preset_low: "Low",
+    preset_medium: "Medium",
+    preset_high: "High",
+    preset_applied: "Applied the {preset} preset ({n} {n|change|changes}).",
+    preset_suggested: "Your GPU ({gpu}) suggests the {preset} preset (a rough guess). Press G to apply it.",
+    help_apply_suggested: "Apply the preset suggested for your GPU",
+    unknown_gpu: "not detected",
+    confirm_reset_all: "Reset every setting to the game default?",
+    reset_field: "Reset {label} to {value}.",
+    reset_all: "Reset {n} {n|setting|settings} to defaults.",
+    help_reset_field: "Reset the setting to its default",
+    help_reset_all: "Reset every setting to defaults",
+    reverted_field: "Reverted {label} to the saved value.",
+    reverted_all: "Reverted {n} pending {n|change|changes}.",
+    help_revert_field: "Revert the setting to the saved value",
+    help_revert_all: "Revert all unsaved changes",
+    copied: "Copied {text}",
+    copy_failed: "Copy failed",
+    help_copy_value: "Copy the setting as \"Key\": value",
+    copied_bytes: "Copied {n} bytes to the clipboard.",
+    help_copy_json: "Copy the settings JSON",
+    help_copy_markdown: "Copy a Markdown summary",
+    help_json_keys: "Show raw JSON keys next to labels",
+    help_mark: "Mark the row for batch edits",
+    mark_incompatible: "Only 1-5 quality settings can be marked for batch edits.",
+    hint_mark: "Mark",
+    hint_change_marked: "Change marked",
+    hint_clear_marks: "Clear marks",
+    legacy_ignored: "Found settings from an older game version ({names}); they are ignored.",
+    legacy_migrated: "Copied settings from {name}.",
+    legacy_migrate_failed: "Could not copy the older settings",
+    confirm_migrate_legacy: "Only settings from an older game version were found. Copy them to the current registry name?",
+    autosave_badge: "[autosave {n}s]",
+    help_cycle_next_coarse: "Next value, in large steps",
+    help_cycle_prev_coarse: "Previous value, in large steps",
+    help_cycle_max: "Highest value",
+    help_cycle_min: "Lowest value",
+    hint_coarse: "Large step",
+    hint_min_max: "Min/Max",
+    help_log: "Show the event log",
+    log_title: " Event log ",
+    prompt_set_value: "Set a value, e.g. \"fps 120\" or \"render scale 1.2\":",
+    set_value_usage: "Type a setting name, a space and a value.",
+    no_such_setting: "No setting matches \"{query}\".",
+    ambiguous_setting: "Several settings match: {names}",
+    invalid_value: "{setting} accepts {options}.",
+    help_set_value: "Set a value by name",
+    preset_badge: "[{preset}]",
+    group_favorites: "Favorites",
+    help_favorite: "Pin or unpin the setting to Favorites",
+    help_favorites_only: "Show only Favorites",
+    registry_not_utf8: "The registry value is not valid UTF-8 (bad byte at offset {n}); the blob is corrupt. Using defaults.",
+    registry_invalid: "The registry value is not valid settings JSON ({error}). Using defaults.",
+    choose_theme: "Color theme:",
+    theme_dark: "Dark",
+    theme_light: "Light",
+    theme_solarized: "Solarized",
+    help_theme: "Choose the color theme",
+    help_spacing: "Compact or comfortable row spacing",
+    help_copy_code: "Copy a shareable preset code",
+    help_apply_code: "Apply a preset code",
+    prompt_code: "Paste a preset code:",
+    code_copied: "Preset code copied: {code}",
+    code_not_a_code: "Not a preset code; codes start with HSR-",
+    code_damaged: "Preset code is damaged; check for typos",
+    code_newer: "This preset code is from a newer version of hsr-settings (format {n}); update to use it",
+    code_applied: "Preset code applied: {n} {n|change|changes}",
+    theme_high_contrast: "High contrast",
+    code_bad_character: "Preset code contains '{c}', which never appears in a code",
+    code_unknown_version: "Unknown preset code format {n}; the code is damaged",
+    code_truncated: "Preset code is incomplete; copy the whole code",
+    client_global: "Global",
+    client_cn: "CN",
+    sync_clients_question: "The Global and CN clients' settings differ. Copy which one over the other?",
+    sync_keep_both: "Keep both",
+    clients_synced: "Copied settings over the {client} client",
+    clients_sync_failed: "Could not copy settings between clients",
+    help_show_message: "Show the latest message in full",
+    hint_full_message: "Full message",
+    message_title: "Message",
+    message_close: "Press any key to close",
+    help_preferences: "Preferences",
+    prefs_title: "Preferences (saved to config.toml)",
+    pref_language: "Language",
+    pref_ask: "Ask at startup",
+    pref_theme: "Theme",
+    pref_wrap: "Wrap-around navigation",
+    pref_confirm_saves: "Review changes before saving",
+    pref_vsync_autofix: "Offer to turn off VSync for FPS caps",
+    pref_sync_clients: "Compare Global and CN clients",
+    pref_row_spacing: "Row spacing",
+    pref_autosave: "Autosave",
+    pref_seconds: "{n} s",
+    config_unknown_key: "Unknown key in config.toml, ignored: {key}",
+    config_invalid: "config.toml could not be read and was copied to config.toml.bak; using defaults",
+    hive_notice: "Settings read from {read}; saves go to {write}.",
+    choose_language: "Language",
+    help_language: "Choose the language",
+    pref_skip_picker: "Skip the language picker",
+    external_change_question: "The game changed these settings since they were loaded. Saving now would overwrite them.",
+    external_merge: "Reload and merge",
+    external_overwrite: "Overwrite",
+    external_merged: "Loaded the game's changes and kept your {n} {n|edit|edits}. Press S to save.",
+    translation_missing: "{file}: {n} {n|string is|strings are} missing and {n|shows|show} in {lang}",
+    translation_unknown: "{file}: unknown keys ignored: {keys}",
+    translation_built_in: "{file} is ignored; that language is built in",
+    translation_invalid: "{file} could not be read",
+    save_creates_key: "The game's registry key doesn't exist yet \u{2014} saving creates it.",
+    pref_untranslated: "({n} untranslated)",
+    pref_category_colors: "Color rows by category",
+    plural_rule: "one",
+    failure: "{what}: {error}",
+    translation_placeholders: "{file}: wrong placeholders, kept the fallback text: {keys}",
+    may_run_as: "(may run as {value})",
+    saving: "Saving\u{2026}",
+    save_in_progress: "A save is already in progress.",
+    quit_when_saved: "Quitting once the save finishes\u{2026}",
+    external_reloaded: "The game changed the settings; reloaded them.",
+    tier_very_low: "Very Low",
+    tier_low: "Low",
+    tier_medium: "Medium",
+    tier_high: "High",
+    tier_very_high: "Very High",
+    pref_tier_names: "Quality tier names",
+    help_tier_names: "Quality tiers as names or numbers",
+    help_reload: "Reload from the registry, discarding edits",
+    confirm_reload: "Reload from the registry and discard unsaved changes?",
+    reloaded: "Reloaded the settings from the registry.",
+    code_out_of_range: "Preset code sets {setting} to {value}, which the game can't use",
+    config_invalid_kept: "config.toml could not be read; using defaults without saving over it",
+};
+
+const KOREAN: &[(&str, &str)] = &[
+    ("title", " 붕괴 : 스타레일 그래픽 설정 "),
+    ("saved_bytes", "설정이 저장되었습니다 ({n}바이트)."),
+    ("save_failed", "저장 실패"),
+    ("no_registry", "레지스트리 키를 찾을 수 없습니다 \u{2014} 기본값 사용 중. 저장하여 생성하세요."),
+    ("on", "켜기"),
+    ("off", "끄기"),
+    ("fps", "FPS"),
+    ("vsync", "수직 동기화"),
+    ("render_scale", "렌더 스케일"),
+    ("resolution_quality", "해상도 품질"),
+    ("shadow_quality", "그림자 품질"),
+    ("light_quality", "조명 품질"),
+    ("character_quality", "캐릭터 품질"),
+    ("env_detail", "환경 디테일"),
+    ("reflection_quality", "반사 품질"),
+    ("sfx_quality", "효과 품질"),
+    ("bloom_quality", "블룸 품질"),
+    ("anti_aliasing", "안티앨리어싱"),
+    ("self_shadow", "셀프 쉘도우"),
+    ("dlss_quality", "DLSS 품질"),
+    ("particle_trail", "파티클 트레일"),
+    ("group_display", "디스플레이"),
+    ("group_quality", "품질"),
+    ("group_advanced", "고급"),
+    ("group_count", "설정 {n}개"),
+    ("config_save_failed", "설정 파일 저장 실패"),
+    ("safe_mode_badge", "[안전] "),
+    ("safe_mode_on", "안전 모드 켜짐 \u{2014} 호환되는 값만 선택할 수 있습니다."),
+    ("safe_mode_off", "안전 모드 꺼짐."),
+    ("safe_mode_blocked", "현재 설정과 호환되는 다른 값이 없습니다."),
+    ("safe_mode_rejected", "이 값은 다른 설정과 충돌합니다 (안전 모드)."),
+    ("recommended_shown", "권장 값이 \u{2605}로 표시됩니다."),
+    ("recommended_hidden", "권장 값 표시 숨김."),
+    ("default_value", "기본"),
+    ("unsaved_changes", "저장되지 않은 변경 {n}개"),
+    ("conflict_dlss_render_scale", "DLSS 사용 시 렌더 스케일 무시됨"),
+    ("conflict_dlss_aa", "DLSS가 안티앨리어싱을 대체함"),
+    ("conflict_vsync_fps", "수직 동기화가 FPS 제한을 무시함"),
+    ("confirm_disable_vsync", "수직 동기화로 인해 이 FPS 제한이 무시됩니다. 수직 동기화를 끌까요?"),
+    ("prompt_merge", "병합할 파일 경로: "),
+    ("merged", "설정 {n}개를 병합했습니다. S를 눌러 저장하세요."),
+    ("import_failed", "가져오기 실패"),
+    ("preview_title", " JSON 미리보기 "),
+    ("desc_title", " 설명 "),
+    ("desc_cost", "성능 부담"),
+    ("desc_recommended", "권장 값"),
+    ("desc_group", "설정을 선택하면 설명이 표시됩니다."),
+    ("cost_low", "낮음"),
+    ("cost_medium", "보통"),
+    ("cost_high", "높음"),
+    ("json_edit_title", " JSON 편집 "),
+    ("json_applied", "JSON이 적용되었습니다. S를 눌러 저장하세요."),
+    ("json_invalid", "잘못된 JSON"),
+    ("confirm_discard_json", "JSON 편집 내용을 버릴까요?"),
+    ("working", "처리 중\u{2026}"),
+    ("too_small", "터미널이 너무 작습니다 \u{2014} 최소 {w}x{h} 필요"),
+    ("hint_navigate", "이동"),
+    ("hint_change", "변경"),
+    ("hint_set", "설정"),
+    ("hint_toggle", "전환"),
+    ("hint_fold", "접기"),
+    ("hint_unfold", "펼치기"),
+    ("hint_column", "열 이동"),
+    ("hint_scroll", "스크롤"),
+    ("hint_info", "설명"),
+    ("hint_preview", "JSON"),
+    ("hint_edit", "편집"),
+    ("hint_save", "저장"),
+    ("hint_quit", "종료"),
+    ("hint_close", "닫기"),
+    ("hint_confirm", "확인"),
+    ("hint_cancel", "취소"),
+    ("hint_apply", "적용"),
+    ("hint_yes", "예"),
+    ("hint_no", "아니요"),
+    ("hint_dismiss", "닫기"),
+    ("no_unsaved_changes", "저장되지 않은 변경 없음"),
+    ("no_changes", "저장할 변경 사항이 없습니다."),
+    ("confirm_save", "변경 사항을 저장할까요?"),
+    ("quit_unsaved", "저장하지 않은 변경 사항이 있습니다."),
+    ("quit_save", "저장 후 종료 [S]"),
+    ("quit_discard", "저장 안 함 [D]"),
+    ("quit_cancel", "취소 [C]"),
+    ("hint_save_quit", "저장 후 종료"),
+    ("prompt_export", "내보낼 파일 경로 ({format}): "),
+    ("exported", "{path}에 내보냈습니다"),
+    ("export_failed", "내보내기 실패"),
+    ("hint_format", "형식"),
+    ("title_unsaved", "(저장 안 됨)"),
+    ("saved_key_created", "설정이 저장되었습니다 ({n}바이트, 레지스트리 키 생성됨)."),
+    ("read_only_badge", "[읽기 전용]"),
+    ("read_only_refused", "읽기 전용 모드: 변경할 수 없습니다."),
+    ("help_title", " 단축키 "),
+    ("help_navigation", "이동"),
+    ("help_editing", "편집"),
+    ("help_files", "파일"),
+    ("help_application", "앱"),
+    ("help_move_up", "위로 이동"),
+    ("help_move_down", "아래로 이동"),
+    ("help_activate", "그룹 접기 / 토글 전환"),
+    ("help_switch_column", "열 전환"),
+    ("help_left_column", "왼쪽 열로 이동"),
+    ("help_right_column", "오른쪽 열로 이동"),
+    ("help_scroll_up", "JSON 미리보기 위로 스크롤"),
+    ("help_scroll_down", "JSON 미리보기 아래로 스크롤"),
+    ("help_cycle_next", "다음 값"),
+    ("help_cycle_prev", "이전 값"),
+    ("help_set_tier", "품질 단계 바로 설정"),
+    ("help_safe_mode", "안전 모드 전환"),
+    ("help_edit_json", "JSON 직접 편집"),
+    ("help_save", "레지스트리에 저장"),
+    ("help_save_quit", "저장 후 종료"),
+    ("help_merge_import", "설정 파일 병합"),
+    ("help_export", "파일로 내보내기"),
+    ("help_recommended", "추천 값 표시"),
+    ("help_description", "설명 패널 표시"),
+    ("help_defaults", "기본값 표시"),
+    ("help_preview", "JSON 미리보기 표시"),
+    ("help_help", "이 도움말 표시"),
+    ("help_back", "알림 닫기 / 패널 닫기 / 종료"),
+    ("help_quit", "종료"),
+    ("hint_help", "도움말"),
+    ("decimal_separator", "."),
+    ("quality_level_question", "모든 품질 설정을 다음 단계로:"),
+    ("quality_level_set", "품질 설정 {n}개를 {level}(으)로 변경했습니다."),
+    ("help_set_all_quality", "모든 품질 설정 일괄 변경"),
+    ("locked_refused", "잠긴 설정입니다 (F로 잠금 해제)."),
+    ("help_lock", "설정 잠금 / 해제"),
+    ("undid", "되돌림: {change}"),
+    ("redid", "다시 실행: {change}"),
+    ("n_changes", "변경 {n}개"),
+    ("nothing_to_undo", "되돌릴 항목이 없습니다."),
+    ("nothing_to_redo", "다시 실행할 항목이 없습니다."),
+    ("help_undo", "실행 취소"),
+    ("help_redo", "다시 실행"),
+    ("preset_low", "낮음"),
+    ("preset_medium", "중간"),
+    ("preset_high", "높음"),
+    ("preset_applied", "{preset} 프리셋을 적용했습니다 (변경 {n}개)."),
+    ("preset_suggested", "GPU({gpu}) 기준 추천 프리셋: {preset} (대략적인 추정). G를 눌러 적용하세요."),
+    ("help_apply_suggested", "GPU 추천 프리셋 적용"),
+    ("unknown_gpu", "감지 안 됨"),
+    ("confirm_reset_all", "모든 설정을 게임 기본값으로 되돌릴까요?"),
+    ("reset_field", "{label}을(를) {value}(으)로 되돌렸습니다."),
+    ("reset_all", "설정 {n}개를 기본값으로 되돌렸습니다."),
+    ("help_reset_field", "설정을 기본값으로"),
+    ("help_reset_all", "모든 설정을 기본값으로"),
+    ("reverted_field", "{label}을(를) 저장된 값으로 되돌렸습니다."),
+    ("reverted_all", "저장하지 않은 변경 {n}개를 되돌렸습니다."),
+    ("help_revert_field", "설정을 저장된 값으로 되돌리기"),
+    ("help_revert_all", "저장하지 않은 변경 모두 되돌리기"),
+    ("copied", "복사됨: {text}"),
+    ("copy_failed", "복사 실패"),
+    ("help_copy_value", "설정을 \"Key\": value 형식으로 복사"),
+    ("copied_bytes", "클립보드에 {n}바이트를 복사했습니다."),
+    ("help_copy_json", "설정 JSON 복사"),
+    ("help_copy_markdown", "Markdown 요약 복사"),
+    ("help_json_keys", "라벨 옆에 JSON 키 표시"),
+    ("help_mark", "일괄 편집할 행 선택"),
+    ("mark_incompatible", "일괄 편집은 1-5 품질 설정만 선택할 수 있습니다."),
+    ("hint_mark", "선택"),
+    ("hint_change_marked", "선택 항목 변경"),
+    ("hint_clear_marks", "선택 해제"),
+    ("legacy_ignored", "이전 게임 버전의 설정({names})이 있지만 사용하지 않습니다."),
+    ("legacy_migrated", "{name}에서 설정을 복사했습니다."),
+    ("legacy_migrate_failed", "이전 설정을 복사하지 못했습니다"),
+    ("confirm_migrate_legacy", "이전 게임 버전의 설정만 있습니다. 현재 레지스트리 이름으로 복사할까요?"),
+    ("autosave_badge", "[자동 저장 {n}초]"),
+    ("help_cycle_next_coarse", "다음 값 (큰 단위)"),
+    ("help_cycle_prev_coarse", "이전 값 (큰 단위)"),
+    ("help_cycle_max", "최댓값"),
+    ("help_cycle_min", "최솟값"),
+    ("hint_coarse", "크게"),
+    ("hint_min_max", "최소/최대"),
+    ("help_log", "이벤트 기록 표시"),
+    ("log_title", " 이벤트 기록 "),
+    ("prompt_set_value", "값 설정 (예: \"fps 120\", \"render scale 1.2\"):"),
+    ("set_value_usage", "설정 이름, 공백, 값을 입력하세요."),
+    ("no_such_setting", "\"{query}\"에 해당하는 설정이 없습니다."),
+    ("ambiguous_setting", "여러 설정이 일치합니다: {names}"),
+    ("invalid_value", "{setting} 값은 {options} 중 하나여야 합니다."),
+    ("help_set_value", "이름으로 값 설정"),
+    ("preset_badge", "[{preset}]"),
+    ("group_favorites", "즐겨찾기"),
+    ("help_favorite", "설정을 즐겨찾기에 고정/해제"),
+    ("help_favorites_only", "즐겨찾기만 표시"),
+    ("registry_not_utf8", "레지스트리 값이 올바른 UTF-8이 아닙니다 (오프셋 {n}의 잘못된 바이트). 데이터가 손상되었습니다. 기본값을 사용합니다."),
+    ("registry_invalid", "레지스트리 값이 올바른 설정 JSON이 아닙니다 ({error}). 기본값을 사용합니다."),
+    ("choose_theme", "색상 테마:"),
+    ("theme_dark", "다크"),
+    ("theme_light", "라이트"),
+    ("theme_solarized", "Solarized"),
+    ("help_theme", "색상 테마 선택"),
+    ("help_spacing", "촘촘한/여유로운 행 간격 전환"),
+    ("help_copy_code", "공유용 프리셋 코드 복사"),
+    ("help_apply_code", "프리셋 코드 적용"),
+    ("prompt_code", "프리셋 코드 붙여넣기:"),
+    ("code_copied", "프리셋 코드 복사됨: {code}"),
+    ("code_not_a_code", "프리셋 코드가 아닙니다. 코드는 HSR-로 시작합니다"),
+    ("code_damaged", "프리셋 코드가 손상되었습니다. 오타를 확인하세요"),
+    ("code_newer", "이 프리셋 코드는 더 새로운 버전의 hsr-settings에서 만들어졌습니다 (형식 {n}). 업데이트 후 사용하세요"),
+    ("code_applied", "프리셋 코드 적용됨: {n}개 변경"),
+    ("theme_high_contrast", "고대비"),
+    ("code_bad_character", "프리셋 코드에 코드에 쓰이지 않는 문자 '{c}'가 있습니다"),
+    ("code_unknown_version", "알 수 없는 프리셋 코드 형식 {n}입니다. 코드가 손상되었습니다"),
+    ("code_truncated", "프리셋 코드가 잘렸습니다. 코드 전체를 복사하세요"),
+    ("client_global", "글로벌"),
+    ("client_cn", "중국"),
+    ("sync_clients_question", "글로벌과 중국 클라이언트의 설정이 다릅니다. 어느 쪽을 다른 쪽에 복사할까요?"),
+    ("sync_keep_both", "그대로 두기"),
+    ("clients_synced", "{client} 클라이언트에 설정을 복사했습니다"),
+    ("clients_sync_failed", "클라이언트 간 설정 복사 실패"),
+    ("help_show_message", "최근 메시지 전체 보기"),
+    ("hint_full_message", "전체 메시지"),
+    ("message_title", "메시지"),
+    ("message_close", "아무 키나 눌러 닫기"),
+    ("help_preferences", "환경설정"),
+    ("prefs_title", "환경설정 (config.toml에 저장)"),
+    ("pref_language", "언어"),
+    ("pref_ask", "시작할 때 묻기"),
+    ("pref_theme", "테마"),
+    ("pref_wrap", "목록 끝에서 처음으로 이동"),
+    ("pref_confirm_saves", "저장 전 변경 사항 확인"),
+    ("pref_vsync_autofix", "FPS 제한 시 VSync 끄기 제안"),
+    ("pref_sync_clients", "글로벌/중국 클라이언트 비교"),
+    ("pref_row_spacing", "행 간격"),
+    ("pref_autosave", "자동 저장"),
+    ("pref_seconds", "{n}초"),
+    ("config_unknown_key", "config.toml의 알 수 없는 키를 무시했습니다: {key}"),
+    ("config_invalid", "config.toml을 읽을 수 없어 config.toml.bak에 복사하고 기본값을 사용합니다"),
+    ("hive_notice", "{read}에서 설정을 읽었습니다. 저장은 {write}에 합니다."),
+    ("choose_language", "언어"),
+    ("help_language", "언어 선택"),
+    ("pref_skip_picker", "언어 선택 화면 건너뛰기"),
+    ("external_change_question", "설정을 불러온 뒤 게임이 다음 설정을 변경했습니다. 지금 저장하면 덮어씁니다."),
+    ("external_merge", "다시 불러와 병합"),
+    ("external_overwrite", "덮어쓰기"),
+    ("external_merged", "게임의 변경 사항을 불러오고 편집한 {n}개를 유지했습니다. S를 눌러 저장하세요."),
+    ("translation_missing", "{file}: 문자열 {n}개가 없어 {lang}(으)로 표시됩니다"),
+    ("translation_unknown", "{file}: 알 수 없는 키를 무시했습니다: {keys}"),
+    ("translation_built_in", "{file}은(는) 기본 제공 언어이므로 무시했습니다"),
+    ("translation_invalid", "{file}을(를) 읽을 수 없습니다"),
+    ("save_creates_key", "게임의 레지스트리 키가 아직 없습니다 \u{2014} 저장하면 새로 생성됩니다."),
+    ("pref_untranslated", "(미번역 {n}개)"),
+    ("pref_category_colors", "카테고리별 행 색상"),
+    ("plural_rule", "none"),
+    ("failure", "{what}: {error}"),
+    ("translation_placeholders", "{file}: 잘못된 자리 표시자가 있어 대체 문자열을 사용합니다: {keys}"),
+    ("may_run_as", "({value}(으)로 동작할 수 있음)"),
+    ("saving", "저장 중\u{2026}"),
+    ("save_in_progress", "이미 저장 중입니다."),
+    ("quit_when_saved", "저장이 끝나면 종료합니다\u{2026}"),
+    ("external_reloaded", "게임이 설정을 변경하여 다시 불러왔습니다."),
+    ("tier_very_low", "매우 낮음"),
+    ("tier_low", "낮음"),
+    ("tier_medium", "중간"),
+    ("tier_high", "높음"),
+    ("tier_very_high", "매우 높음"),
+    ("pref_tier_names", "품질 단계 이름 표시"),
+    ("help_tier_names", "품질 단계를 이름/숫자로 표시"),
+    ("help_reload", "레지스트리에서 다시 불러오기(편집 취소)"),
+    ("confirm_reload", "레지스트리에서 다시 불러오고 저장하지 않은 변경 사항을 버릴까요?"),
+    ("reloaded", "레지스트리에서 설정을 다시 불러왔습니다."),
+    ("code_out_of_range", "프리셋 코드의 {setting} 값 {value}은(는) 게임에서 사용할 수 없는 값입니다"),
+    ("config_invalid_kept", "config.toml을 읽을 수 없어 기본값을 사용하며, 파일은 덮어쓰지 않습니다"),
+];
+
+const JAPANESE: &[(&str, &str)] = &[
+    ("title", " 崩壊：スターレイル グラフィック設定 "),
+    ("saved_bytes", "設定が保存されました（{n}バイト）。"),
+    ("save_failed", "保存失敗"),
+    ("no_registry", "レジストリキーが見つかりません \u{2014} デフォルト値を使用中。保存して作成してください。"),
+    ("on", "オン"),
+    ("off", "オフ"),
+    ("fps", "FPS"),
+    ("vsync", "垂直同期"),
+    ("render_scale", "レンダースケール"),
+    ("resolution_quality", "解像度品質"),
+    ("shadow_quality", "影の品質"),
+    ("light_quality", "ライト品質"),
+    ("character_quality", "キャラクター品質"),
+    ("env_detail", "環境ディテール"),
+    ("reflection_quality", "反射品質"),
+    ("sfx_quality", "エフェクト品質"),
+    ("bloom_quality", "ブルーム品質"),
+    ("anti_aliasing", "アンチエイリアス"),
+    ("self_shadow", "セルフシャドウ"),
+    ("dlss_quality", "DLSS品質"),
+    ("particle_trail", "パーティクルトレイル"),
+    ("group_display", "ディスプレイ"),
+    ("group_quality", "品質"),
+    ("group_advanced", "詳細"),
+    ("group_count", "{n} 項目"),
+    ("config_save_failed", "設定ファイルの保存失敗"),
+    ("safe_mode_badge", "[セーフ] "),
+    ("safe_mode_on", "セーフモード オン \u{2014} 互換性のある値のみ選択できます。"),
+    ("safe_mode_off", "セーフモード オフ。"),
+    ("safe_mode_blocked", "現在の設定と互換性のある他の値がありません。"),
+    ("safe_mode_rejected", "この値は他の設定と競合します（セーフモード）。"),
+    ("recommended_shown", "推奨値を \u{2605} で表示します。"),
+    ("recommended_hidden", "推奨値の表示を非表示にしました。"),
+    ("default_value", "既定"),
+    ("unsaved_changes", "未保存の変更 {n} 件"),
+    ("conflict_dlss_render_scale", "DLSS使用時はレンダースケール無効"),
+    ("conflict_dlss_aa", "DLSSがアンチエイリアスを置き換え"),
+    ("conflict_vsync_fps", "垂直同期がFPS上限を上書き"),
+    ("confirm_disable_vsync", "垂直同期によりこのFPS上限は無視されます。垂直同期をオフにしますか？"),
+    ("prompt_merge", "マージするファイル: "),
+    ("merged", "{n} 件の設定をマージしました。S で保存します。"),
+    ("import_failed", "インポート失敗"),
+    ("preview_title", " JSON プレビュー "),
+    ("desc_title", " 説明 "),
+    ("desc_cost", "負荷"),
+    ("desc_recommended", "推奨値"),
+    ("desc_group", "設定を選択すると説明が表示されます。"),
+    ("cost_low", "低"),
+    ("cost_medium", "中"),
+    ("cost_high", "高"),
+    ("json_edit_title", " JSON 編集 "),
+    ("json_applied", "JSONを適用しました。S で保存します。"),
+    ("json_invalid", "無効なJSON"),
+    ("confirm_discard_json", "JSONの編集内容を破棄しますか？"),
+    ("working", "処理中\u{2026}"),
+    ("too_small", "ターミナルが小さすぎます \u{2014} {w}x{h} 以上が必要です"),
+    ("hint_navigate", "移動"),
+    ("hint_change", "変更"),
+    ("hint_set", "設定"),
+    ("hint_toggle", "切替"),
+    ("hint_fold", "折りたたみ"),
+    ("hint_unfold", "展開"),
+    ("hint_column", "列移動"),
+    ("hint_scroll", "スクロール"),
+    ("hint_info", "説明"),
+    ("hint_preview", "JSON"),
+    ("hint_edit", "編集"),
+    ("hint_save", "保存"),
+    ("hint_quit", "終了"),
+    ("hint_close", "閉じる"),
+    ("hint_confirm", "確定"),
+    ("hint_cancel", "キャンセル"),
+    ("hint_apply", "適用"),
+    ("hint_yes", "はい"),
+    ("hint_no", "いいえ"),
+    ("hint_dismiss", "閉じる"),
+    ("no_unsaved_changes", "未保存の変更なし"),
+    ("no_changes", "保存する変更はありません。"),
+    ("confirm_save", "変更を保存しますか？"),
+    ("quit_unsaved", "保存されていない変更があります。"),
+    ("quit_save", "保存して終了 [S]"),
+    ("quit_discard", "保存しない [D]"),
+    ("quit_cancel", "キャンセル [C]"),
+    ("hint_save_quit", "保存して終了"),
+    ("prompt_export", "エクスポート先 ({format}): "),
+    ("exported", "{path} にエクスポートしました"),
+    ("export_failed", "エクスポート失敗"),
+    ("hint_format", "形式"),
+    ("title_unsaved", "（未保存）"),
+    ("saved_key_created", "設定が保存されました（{n}バイト、レジストリキーを作成）。"),
+    ("read_only_badge", "[読み取り専用]"),
+    ("read_only_refused", "読み取り専用モード：変更できません。"),
+    ("help_title", " キー操作 "),
+    ("help_navigation", "移動"),
+    ("help_editing", "編集"),
+    ("help_files", "ファイル"),
+    ("help_application", "アプリ"),
+    ("help_move_up", "上へ移動"),
+    ("help_move_down", "下へ移動"),
+    ("help_activate", "グループの折りたたみ / トグル切替"),
+    ("help_switch_column", "列の切り替え"),
+    ("help_left_column", "左の列へ移動"),
+    ("help_right_column", "右の列へ移動"),
+    ("help_scroll_up", "JSONプレビューを上へスクロール"),
+    ("help_scroll_down", "JSONプレビューを下へスクロール"),
+    ("help_cycle_next", "次の値"),
+    ("help_cycle_prev", "前の値"),
+    ("help_set_tier", "品質段階を直接設定"),
+    ("help_safe_mode", "セーフモード切替"),
+    ("help_edit_json", "JSONを直接編集"),
+    ("help_save", "レジストリに保存"),
+    ("help_save_quit", "保存して終了"),
+    ("help_merge_import", "設定ファイルをマージ"),
+    ("help_export", "ファイルにエクスポート"),
+    ("help_recommended", "推奨値の表示"),
+    ("help_description", "説明パネルの表示"),
+    ("help_defaults", "デフォルト値の表示"),
+    ("help_preview", "JSONプレビューの表示"),
+    ("help_help", "このヘルプを表示"),
+    ("help_back", "通知を閉じる / パネルを閉じる / 終了"),
+    ("help_quit", "終了"),
+    ("hint_help", "ヘルプ"),
+    ("decimal_separator", "."),
+    ("quality_level_question", "すべての品質設定を次の段階に："),
+    ("quality_level_set", "品質設定 {n} 件を {level} に変更しました。"),
+    ("help_set_all_quality", "すべての品質設定を一括変更"),
+    ("locked_refused", "この設定はロックされています（Fで解除）。"),
+    ("help_lock", "設定のロック / 解除"),
+    ("undid", "元に戻しました：{change}"),
+    ("redid", "やり直しました：{change}"),
+    ("n_changes", "{n} 件の変更"),
+    ("nothing_to_undo", "元に戻す操作はありません。"),
+    ("nothing_to_redo", "やり直す操作はありません。"),
+    ("help_undo", "元に戻す"),
+    ("help_redo", "やり直し"),
+    ("preset_low", "低"),
+    ("preset_medium", "中"),
+    ("preset_high", "高"),
+    ("preset_applied", "プリセット「{preset}」を適用しました（{n} 件の変更）。"),
+    ("preset_suggested", "GPU（{gpu}）からの推奨プリセット：{preset}（おおまかな推定）。Gで適用します。"),
+    ("help_apply_suggested", "GPU推奨プリセットを適用"),
+    ("unknown_gpu", "検出できず"),
+    ("confirm_reset_all", "すべての設定をゲームのデフォルトに戻しますか？"),
+    ("reset_field", "{label} を {value} に戻しました。"),
+    ("reset_all", "{n} 件の設定をデフォルトに戻しました。"),
+    ("help_reset_field", "設定をデフォルトに戻す"),
+    ("help_reset_all", "すべての設定をデフォルトに戻す"),
+    ("reverted_field", "{label} を保存済みの値に戻しました。"),
+    ("reverted_all", "未保存の変更 {n} 件を元に戻しました。"),
+    ("help_revert_field", "設定を保存済みの値に戻す"),
+    ("help_revert_all", "未保存の変更をすべて戻す"),
+    ("copied", "コピーしました：{text}"),
+    ("copy_failed", "コピー失敗"),
+    ("help_copy_value", "設定を \"Key\": value 形式でコピー"),
+    ("copied_bytes", "クリップボードに{n}バイトをコピーしました。"),
+    ("help_copy_json", "設定JSONをコピー"),
+    ("help_copy_markdown", "Markdownの概要をコピー"),
+    ("help_json_keys", "ラベルの横にJSONキーを表示"),
+    ("help_mark", "一括編集する行をマーク"),
+    ("mark_incompatible", "一括編集でマークできるのは1-5の品質設定だけです。"),
+    ("hint_mark", "マーク"),
+    ("hint_change_marked", "マーク項目を変更"),
+    ("hint_clear_marks", "マーク解除"),
+    ("legacy_ignored", "旧バージョンのゲームの設定（{names}）が見つかりましたが、使用しません。"),
+    ("legacy_migrated", "{name} から設定をコピーしました。"),
+    ("legacy_migrate_failed", "旧設定をコピーできませんでした"),
+    ("confirm_migrate_legacy", "旧バージョンのゲームの設定だけが見つかりました。現在のレジストリ名にコピーしますか？"),
+    ("autosave_badge", "[自動保存 {n}秒]"),
+    ("help_cycle_next_coarse", "次の値（大きく）"),
+    ("help_cycle_prev_coarse", "前の値（大きく）"),
+    ("help_cycle_max", "最大値"),
+    ("help_cycle_min", "最小値"),
+    ("hint_coarse", "大きく"),
+    ("hint_min_max", "最小/最大"),
+    ("help_log", "イベントログを表示"),
+    ("log_title", " イベントログ "),
+    ("prompt_set_value", "値を設定（例: \"fps 120\"、\"render scale 1.2\"）:"),
+    ("set_value_usage", "設定名、スペース、値の順に入力してください。"),
+    ("no_such_setting", "「{query}」に一致する設定がありません。"),
+    ("ambiguous_setting", "複数の設定が一致します: {names}"),
+    ("invalid_value", "{setting} に指定できるのは {options} です。"),
+    ("help_set_value", "名前で値を設定"),
+    ("preset_badge", "[{preset}]"),
+    ("group_favorites", "お気に入り"),
+    ("help_favorite", "設定をお気に入りに固定/解除"),
+    ("help_favorites_only", "お気に入りだけを表示"),
+    ("registry_not_utf8", "レジストリ値が正しいUTF-8ではありません（オフセット{n}の不正なバイト）。データが破損しています。デフォルト値を使用します。"),
+    ("registry_invalid", "レジストリ値が正しい設定JSONではありません（{error}）。デフォルト値を使用します。"),
+    ("choose_theme", "カラーテーマ："),
+    ("theme_dark", "ダーク"),
+    ("theme_light", "ライト"),
+    ("theme_solarized", "Solarized"),
+    ("help_theme", "カラーテーマを選ぶ"),
+    ("help_spacing", "行間隔を詰める／広げる"),
+    ("help_copy_code", "共有用プリセットコードをコピー"),
+    ("help_apply_code", "プリセットコードを適用"),
+    ("prompt_code", "プリセットコードを貼り付け："),
+    ("code_copied", "プリセットコードをコピーしました：{code}"),
+    ("code_not_a_code", "プリセットコードではありません。コードは HSR- で始まります"),
+    ("code_damaged", "プリセットコードが壊れています。入力ミスを確認してください"),
+    ("code_newer", "このプリセットコードは新しいバージョンの hsr-settings で作成されています（形式 {n}）。更新してから使ってください"),
+    ("code_applied", "プリセットコードを適用しました：{n} 件の変更"),
+    ("theme_high_contrast", "ハイコントラスト"),
+    ("code_bad_character", "プリセットコードに使われない文字「{c}」が含まれています"),
+    ("code_unknown_version", "不明なプリセットコード形式 {n} です。コードが壊れています"),
+    ("code_truncated", "プリセットコードが途中で切れています。コード全体をコピーしてください"),
+    ("client_global", "グローバル"),
+    ("client_cn", "中国"),
+    ("sync_clients_question", "グローバル版と中国版クライアントの設定が異なります。どちらをもう一方にコピーしますか？"),
+    ("sync_keep_both", "そのままにする"),
+    ("clients_synced", "{client}クライアントに設定をコピーしました"),
+    ("clients_sync_failed", "クライアント間で設定をコピーできませんでした"),
+    ("help_show_message", "最新のメッセージを全文表示"),
+    ("hint_full_message", "全文"),
+    ("message_title", "メッセージ"),
+    ("message_close", "何かキーを押すと閉じます"),
+    ("help_preferences", "環境設定"),
+    ("prefs_title", "環境設定（config.toml に保存）"),
+    ("pref_language", "言語"),
+    ("pref_ask", "起動時に選択"),
+    ("pref_theme", "テーマ"),
+    ("pref_wrap", "リストの端で折り返す"),
+    ("pref_confirm_saves", "保存前に変更を確認"),
+    ("pref_vsync_autofix", "FPS 上限の設定時に VSync オフを提案"),
+    ("pref_sync_clients", "グローバル版と中国版を比較"),
+    ("pref_row_spacing", "行間隔"),
+    ("pref_autosave", "自動保存"),
+    ("pref_seconds", "{n}秒"),
+    ("config_unknown_key", "config.toml の不明なキーを無視しました: {key}"),
+    ("config_invalid", "config.toml を読み込めないため config.toml.bak にコピーし、既定値を使用します"),
+    ("hive_notice", "{read} から設定を読み込みました。保存先は {write} です。"),
+    ("choose_language", "言語"),
+    ("help_language", "言語を選択"),
+    ("pref_skip_picker", "言語選択画面をスキップ"),
+    ("external_change_question", "読み込み後にゲームが次の設定を変更しました。今保存すると上書きされます。"),
+    ("external_merge", "再読み込みして統合"),
+    ("external_overwrite", "上書き"),
+    ("external_merged", "ゲームの変更を読み込み、{n}件の編集を保持しました。Sキーで保存します。"),
+    ("translation_missing", "{file}: {n}件の文字列がないため{lang}で表示します"),
+    ("translation_unknown", "{file}: 不明なキーを無視しました: {keys}"),
+    ("translation_built_in", "{file} は組み込みの言語のため無視しました"),
+    ("translation_invalid", "{file} を読み込めませんでした"),
+    ("save_creates_key", "ゲームのレジストリキーがまだありません \u{2014} 保存すると新しく作成されます。"),
+    ("pref_untranslated", "(未翻訳 {n}件)"),
+    ("pref_category_colors", "カテゴリ別に行を色分け"),
+    ("plural_rule", "none"),
+    ("failure", "{what}: {error}"),
+    ("translation_placeholders", "{file}: プレースホルダーが正しくないため代替の文字列を使用します: {keys}"),
+    ("may_run_as", "({value} で動作する場合あり)"),
+    ("saving", "保存中\u{2026}"),
+    ("save_in_progress", "すでに保存中です。"),
+    ("quit_when_saved", "保存が終わりしだい終了します\u{2026}"),
+    ("external_reloaded", "ゲームが設定を変更したため、再読み込みしました。"),
+    ("tier_very_low", "最低"),
+    ("tier_low", "低"),
+    ("tier_medium", "中"),
+    ("tier_high", "高"),
+    ("tier_very_high", "最高"),
+    ("pref_tier_names", "品質段階を名前で表示"),
+    ("help_tier_names", "品質段階を名前/数字で表示"),
+    ("help_reload", "レジストリから再読み込み（編集を破棄）"),
+    ("confirm_reload", "レジストリから再読み込みし、未保存の変更を破棄しますか？"),
+    ("reloaded", "レジストリから設定を再読み込みしました。"),
+    ("code_out_of_range", "プリセットコードの {setting} の値 {value} はゲームで使用できません"),
+    ("config_invalid_kept", "config.toml を読み込めないため既定値を使用します（ファイルは上書きしません）"),
+];
+
+const RUSSIAN: &[(&str, &str)] = &[
+    ("title", " Настройки графики Star Rail "),
+    ("saved_bytes", "Настройки сохранены ({n} байт)."),
+    ("save_failed", "Не удалось сохранить"),
+    ("no_registry", "Ключ реестра не найден \u{2014} используются значения по умолчанию. Сохраните, чтобы создать его."),
+    ("on", "Вкл"),
+    ("off", "Выкл"),
+    ("fps", "FPS"),
+    ("vsync", "Верт. синхронизация"),
+    ("render_scale", "Масштаб рендеринга"),
+    ("resolution_quality", "Качество разрешения"),
+    ("shadow_quality", "Качество теней"),
+    ("light_quality", "Качество освещения"),
+    ("character_quality", "Качество персонажей"),
+    ("env_detail", "Детализация окружения"),
+    ("reflection_quality", "Качество отражений"),
+    ("sfx_quality", "Качество эффектов"),
+    ("bloom_quality", "Качество свечения"),
+    ("anti_aliasing", "Сглаживание"),
+    ("self_shadow", "Собственные тени"),
+    ("dlss_quality", "Качество DLSS"),
+    ("particle_trail", "Шлейфы частиц"),
+    ("group_display", "Экран"),
+    ("group_quality", "Качество"),
+    ("group_advanced", "Дополнительно"),
+    ("group_count", "{n} {n|настройка|настройки|настроек}"),
+    ("config_save_failed", "Не удалось сохранить конфигурацию"),
+    ("safe_mode_badge", "[Безоп.] "),
+    ("safe_mode_on", "Безопасный режим включён \u{2014} можно выбрать только совместимые значения."),
+    ("safe_mode_off", "Безопасный режим выключен."),
+    ("safe_mode_blocked", "Нет других значений, совместимых с текущими настройками."),
+    ("safe_mode_rejected", "Это значение конфликтует с другими настройками (безопасный режим)."),
+    ("recommended_shown", "Рекомендуемые значения отмечены \u{2605}."),
+    ("recommended_hidden", "Отметки рекомендаций скрыты."),
+    ("default_value", "по умолчанию"),
+    ("unsaved_changes", "{n} {n|несохранённое изменение|несохранённых изменения|несохранённых изменений}"),
+    ("conflict_dlss_render_scale", "DLSS игнорирует масштаб рендеринга"),
+    ("conflict_dlss_aa", "DLSS заменяет сглаживание"),
+    ("conflict_vsync_fps", "Верт. синхронизация перекрывает лимит FPS"),
+    ("confirm_disable_vsync", "Верт. синхронизация проигнорирует этот лимит FPS. Выключить её?"),
+    ("prompt_merge", "Объединить с файлом: "),
+    ("merged", "Объединено: {n} {n|изменённая настройка|изменённые настройки|изменённых настроек}. Нажмите S, чтобы сохранить."),
+    ("import_failed", "Не удалось импортировать"),
+    ("preview_title", " Просмотр JSON "),
+    ("desc_title", " Описание "),
+    ("desc_cost", "Нагрузка"),
+    ("desc_recommended", "Рекомендуется"),
+    ("desc_group", "Выберите настройку, чтобы узнать, что она делает."),
+    ("cost_low", "Низкая"),
+    ("cost_medium", "Средняя"),
+    ("cost_high", "Высокая"),
+    ("json_edit_title", " Правка JSON "),
+    ("json_applied", "JSON применён. Нажмите S, чтобы сохранить."),
+    ("json_invalid", "Некорректный JSON"),
+    ("confirm_discard_json", "Отменить правки JSON?"),
+    ("working", "Выполняется\u{2026}"),
+    ("too_small", "Терминал слишком мал \u{2014} нужно не меньше {w}x{h}"),
+    ("hint_navigate", "Переход"),
+    ("hint_change", "Изменить"),
+    ("hint_set", "Задать"),
+    ("hint_toggle", "Переключить"),
+    ("hint_fold", "Свернуть"),
+    ("hint_unfold", "Развернуть"),
+    ("hint_column", "Столбец"),
+    ("hint_scroll", "Прокрутка"),
+    ("hint_info", "Описание"),
+    ("hint_preview", "JSON"),
+    ("hint_edit", "Правка"),
+    ("hint_save", "Сохранить"),
+    ("hint_quit", "Выход"),
+    ("hint_close", "Закрыть"),
+    ("hint_confirm", "Подтвердить"),
+    ("hint_cancel", "Отмена"),
+    ("hint_apply", "Применить"),
+    ("hint_yes", "Да"),
+    ("hint_no", "Нет"),
+    ("hint_dismiss", "Скрыть"),
+    ("no_unsaved_changes", "Нет несохранённых изменений"),
+    ("no_changes", "Нечего сохранять."),
+    ("confirm_save", "Сохранить эти изменения?"),
+    ("quit_unsaved", "Есть несохранённые изменения."),
+    ("quit_save", "Сохранить и выйти [S]"),
+    ("quit_discard", "Не сохранять [D]"),
+    ("quit_cancel", "Отмена [C]"),
+    ("hint_save_quit", "Сохранить и выйти"),
+    ("prompt_export", "Экспорт ({format}) в: "),
+    ("exported", "Экспортировано в {path}"),
+    ("export_failed", "Не удалось экспортировать"),
+    ("hint_format", "Формат"),
+    ("title_unsaved", "(не сохранено)"),
+    ("saved_key_created", "Настройки сохранены ({n} байт, ключ реестра создан)."),
+    ("read_only_badge", "[только чтение]"),
+    ("read_only_refused", "Режим только для чтения: изменения отключены."),
+    ("help_title", " Горячие клавиши "),
+    ("help_navigation", "Навигация"),
+    ("help_editing", "Правка"),
+    ("help_files", "Файлы"),
+    ("help_application", "Приложение"),
+    ("help_move_up", "Вверх"),
+    ("help_move_down", "Вниз"),
+    ("help_activate", "Свернуть группу / переключить"),
+    ("help_switch_column", "Сменить столбец"),
+    ("help_left_column", "К левому столбцу"),
+    ("help_right_column", "К правому столбцу"),
+    ("help_scroll_up", "Прокрутить JSON вверх"),
+    ("help_scroll_down", "Прокрутить JSON вниз"),
+    ("help_cycle_next", "Следующее значение"),
+    ("help_cycle_prev", "Предыдущее значение"),
+    ("help_set_tier", "Задать уровень качества напрямую"),
+    ("help_safe_mode", "Безопасный режим"),
+    ("help_edit_json", "Править исходный JSON"),
+    ("help_save", "Сохранить в реестр"),
+    ("help_save_quit", "Сохранить и выйти"),
+    ("help_merge_import", "Объединить с файлом настроек"),
+    ("help_export", "Экспортировать в файл"),
+    ("help_recommended", "Показать отметки рекомендаций"),
+    ("help_description", "Показать панель описания"),
+    ("help_defaults", "Показать значения по умолчанию"),
+    ("help_preview", "Показать просмотр JSON"),
+    ("help_help", "Показать эту справку"),
+    ("help_back", "Скрыть / закрыть панель / выйти"),
+    ("help_quit", "Выход"),
+    ("hint_help", "Справка"),
+    ("decimal_separator", ","),
+    ("quality_level_question", "Установить все настройки качества на:"),
+    ("quality_level_set", "Уровень «{level}» установлен для {n} {n|настройки|настроек|настроек} качества."),
+    ("help_set_all_quality", "Задать все настройки качества сразу"),
+    ("locked_refused", "Эта настройка заблокирована (F — разблокировать)."),
+    ("help_lock", "Заблокировать / разблокировать настройку"),
+    ("undid", "Отменено: {change}."),
+    ("redid", "Повторено: {change}."),
+    ("n_changes", "изменений: {n}"),
+    ("nothing_to_undo", "Нечего отменять."),
+    ("nothing_to_redo", "Нечего повторять."),
+    ("help_undo", "Отменить"),
+    ("help_redo", "Повторить"),
+    ("preset_low", "Низкий"),
+    ("preset_medium", "Средний"),
+    ("preset_high", "Высокий"),
+    ("preset_applied", "Применён пресет «{preset}» ({n} {n|изменение|изменения|изменений})."),
+    ("preset_suggested", "Для вашей видеокарты ({gpu}) подходит пресет «{preset}» (грубая оценка). Нажмите G, чтобы применить."),
+    ("help_apply_suggested", "Применить пресет для вашей видеокарты"),
+    ("unknown_gpu", "не определена"),
+    ("confirm_reset_all", "Сбросить все настройки к значениям игры по умолчанию?"),
+    ("reset_field", "{label}: сброшено на {value}."),
+    ("reset_all", "Сброшено к значениям по умолчанию: {n} {n|настройка|настройки|настроек}."),
+    ("help_reset_field", "Сбросить настройку по умолчанию"),
+    ("help_reset_all", "Сбросить все настройки по умолчанию"),
+    ("reverted_field", "{label}: восстановлено сохранённое значение."),
+    ("reverted_all", "Отменено: {n} {n|несохранённое изменение|несохранённых изменения|несохранённых изменений}."),
+    ("help_revert_field", "Вернуть сохранённое значение"),
+    ("help_revert_all", "Отменить все несохранённые изменения"),
+    ("copied", "Скопировано: {text}"),
+    ("copy_failed", "Не удалось скопировать"),
+    ("help_copy_value", "Скопировать как \"Key\": value"),
+    ("copied_bytes", "Скопировано в буфер обмена: {n} байт."),
+    ("help_copy_json", "Скопировать JSON настроек"),
+    ("help_copy_markdown", "Скопировать сводку в Markdown"),
+    ("help_json_keys", "Показать ключи JSON рядом с названиями"),
+    ("help_mark", "Отметить строку для группового изменения"),
+    ("mark_incompatible", "Для группового изменения можно отметить только настройки качества 1-5."),
+    ("hint_mark", "Отметить"),
+    ("hint_change_marked", "Изменить отмеченные"),
+    ("hint_clear_marks", "Снять отметки"),
+    ("legacy_ignored", "Найдены настройки старой версии игры ({names}); они игнорируются."),
+    ("legacy_migrated", "Настройки скопированы из {name}."),
+    ("legacy_migrate_failed", "Не удалось скопировать старые настройки"),
+    ("confirm_migrate_legacy", "Найдены только настройки старой версии игры. Скопировать их под текущее имя в реестре?"),
+    ("autosave_badge", "[автосохранение {n} с]"),
+    ("help_cycle_next_coarse", "Следующее значение, крупный шаг"),
+    ("help_cycle_prev_coarse", "Предыдущее значение, крупный шаг"),
+    ("help_cycle_max", "Наибольшее значение"),
+    ("help_cycle_min", "Наименьшее значение"),
+    ("hint_coarse", "Крупный шаг"),
+    ("hint_min_max", "Мин/Макс"),
+    ("help_log", "Показать журнал событий"),
+    ("log_title", " Журнал событий "),
+    ("prompt_set_value", "Задайте значение, например \"fps 120\" или \"render scale 1.2\":"),
+    ("set_value_usage", "Введите название настройки, пробел и значение."),
+    ("no_such_setting", "Нет настройки, подходящей под \"{query}\"."),
+    ("ambiguous_setting", "Подходит несколько настроек: {names}"),
+    ("invalid_value", "{setting} принимает {options}."),
+    ("help_set_value", "Задать значение по названию"),
+    ("preset_badge", "[{preset}]"),
+    ("group_favorites", "Избранное"),
+    ("help_favorite", "Добавить в Избранное или убрать из него"),
+    ("help_favorites_only", "Показывать только Избранное"),
+    ("registry_not_utf8", "Значение в реестре не является корректным UTF-8 (ошибочный байт по смещению {n}); данные повреждены. Используются значения по умолчанию."),
+    ("registry_invalid", "Значение в реестре не является корректным JSON настроек ({error}). Используются значения по умолчанию."),
+    ("choose_theme", "Цветовая тема:"),
+    ("theme_dark", "Тёмная"),
+    ("theme_light", "Светлая"),
+    ("theme_solarized", "Solarized"),
+    ("help_theme", "Выбрать цветовую тему"),
+    ("help_spacing", "Компактные или свободные строки"),
+    ("help_copy_code", "Скопировать код пресета"),
+    ("help_apply_code", "Применить код пресета"),
+    ("prompt_code", "Вставьте код пресета:"),
+    ("code_copied", "Код пресета скопирован: {code}"),
+    ("code_not_a_code", "Это не код пресета; коды начинаются с HSR-"),
+    ("code_damaged", "Код пресета повреждён; проверьте, нет ли опечаток"),
+    ("code_newer", "Этот код пресета из более новой версии hsr-settings (формат {n}); обновитесь, чтобы применить его"),
+    ("code_applied", "Код пресета применён: {n} {n|изменение|изменения|изменений}"),
+    ("theme_high_contrast", "Высокий контраст"),
+    ("code_bad_character", "Код пресета содержит '{c}', такого символа в кодах не бывает"),
+    ("code_unknown_version", "Неизвестный формат кода пресета {n}; код повреждён"),
+    ("code_truncated", "Код пресета неполный; скопируйте его целиком"),
+    ("client_global", "Global"),
+    ("client_cn", "CN"),
+    ("sync_clients_question", "Настройки клиентов Global и CN различаются. Какие скопировать поверх других?"),
+    ("sync_keep_both", "Оставить оба"),
+    ("clients_synced", "Настройки скопированы в клиент {client}"),
+    ("clients_sync_failed", "Не удалось скопировать настройки между клиентами"),
+    ("help_show_message", "Показать последнее сообщение полностью"),
+    ("hint_full_message", "Сообщение целиком"),
+    ("message_title", "Сообщение"),
+    ("message_close", "Нажмите любую клавишу, чтобы закрыть"),
+    ("help_preferences", "Параметры"),
+    ("prefs_title", "Параметры (сохраняются в config.toml)"),
+    ("pref_language", "Язык"),
+    ("pref_ask", "Спрашивать при запуске"),
+    ("pref_theme", "Тема"),
+    ("pref_wrap", "Циклическая навигация"),
+    ("pref_confirm_saves", "Просматривать изменения перед сохранением"),
+    ("pref_vsync_autofix", "Предлагать выключать верт. синхронизацию при лимите FPS"),
+    ("pref_sync_clients", "Сравнивать клиенты Global и CN"),
+    ("pref_row_spacing", "Интервал строк"),
+    ("pref_autosave", "Автосохранение"),
+    ("pref_seconds", "{n} с"),
+    ("config_unknown_key", "Неизвестный ключ в config.toml пропущен: {key}"),
+    ("config_invalid", "Не удалось прочитать config.toml, он скопирован в config.toml.bak; используются значения по умолчанию"),
+    ("hive_notice", "Настройки прочитаны из {read}; сохранение идёт в {write}."),
+    ("choose_language", "Язык"),
+    ("help_language", "Выбрать язык"),
+    ("pref_skip_picker", "Не показывать выбор языка"),
+    ("external_change_question", "После загрузки игра изменила эти настройки. Сохранение сейчас перезапишет их."),
+    ("external_merge", "Перечитать и объединить"),
+    ("external_overwrite", "Перезаписать"),
+    ("external_merged", "Изменения игры загружены, ваши правки сохранены ({n} {n|изменение|изменения|изменений}). Нажмите S, чтобы сохранить."),
+    ("translation_missing", "{file}: не хватает {n} {n|строки|строк|строк}, показано на языке {lang}"),
+    ("translation_unknown", "{file}: неизвестные ключи пропущены: {keys}"),
+    ("translation_built_in", "{file} пропущен: этот язык встроенный"),
+    ("translation_invalid", "Не удалось прочитать {file}"),
+    ("save_creates_key", "Ключа реестра игры ещё нет \u{2014} он будет создан при сохранении."),
+    ("pref_untranslated", "(не переведено: {n})"),
+    ("pref_category_colors", "Цвет строк по категориям"),
+    ("plural_rule", "slavic"),
+    ("failure", "{what}: {error}"),
+    ("translation_placeholders", "{file}: неверные подстановки, оставлен запасной текст: {keys}"),
+    ("may_run_as", "(может работать как {value})"),
+    ("saving", "Сохранение\u{2026}"),
+    ("save_in_progress", "Сохранение уже выполняется."),
+    ("quit_when_saved", "Выход после завершения сохранения\u{2026}"),
+    ("external_reloaded", "Игра изменила настройки; они перечитаны."),
+    ("tier_very_low", "Очень низкое"),
+    ("tier_low", "Низкое"),
+    ("tier_medium", "Среднее"),
+    ("tier_high", "Высокое"),
+    ("tier_very_high", "Очень высокое"),
+    ("pref_tier_names", "Названия уровней качества"),
+    ("help_tier_names", "Уровни качества названиями или числами"),
+    ("help_reload", "Перечитать из реестра, отменив правки"),
+    ("confirm_reload", "Перечитать из реестра и отменить несохранённые изменения?"),
+    ("reloaded", "Настройки перечитаны из реестра."),
+    ("code_out_of_range", "Код пресета задаёт для «{setting}» значение {value}, которое игра не поддерживает"),
+    ("config_invalid_kept", "Не удалось прочитать config.toml; используются значения по умолчанию, файл не будет перезаписан"),
+];
+
+const SPANISH: &[(&str, &str)] = &[
+    ("title", " Ajustes gráficos de Star Rail "),
+    ("saved_bytes", "Ajustes guardados ({n} bytes)."),
+    ("save_failed", "Error al guardar"),
+    ("no_registry", "No se encontró la clave del registro \u{2014} se usan los valores predeterminados. Guarda para crearla."),
+    ("on", "Sí"),
+    ("off", "No"),
+    ("fps", "FPS"),
+    ("vsync", "Sincronización vertical"),
+    ("render_scale", "Escala de renderizado"),
+    ("resolution_quality", "Calidad de resolución"),
+    ("shadow_quality", "Calidad de sombras"),
+    ("light_quality", "Calidad de iluminación"),
+    ("character_quality", "Calidad de personajes"),
+    ("env_detail", "Detalle del entorno"),
+    ("reflection_quality", "Calidad de reflejos"),
+    ("sfx_quality", "Calidad de efectos"),
+    ("bloom_quality", "Calidad del resplandor"),
+    ("anti_aliasing", "Antialiasing"),
+    ("self_shadow", "Autosombreado"),
+    ("dlss_quality", "Calidad de DLSS"),
+    ("particle_trail", "Estela de partículas"),
+    ("group_display", "Pantalla"),
+    ("group_quality", "Calidad"),
+    ("group_advanced", "Avanzado"),
+    ("group_count", "{n} {n|ajuste|ajustes}"),
+    ("config_save_failed", "Error al guardar la configuración"),
+    ("safe_mode_badge", "[Seguro] "),
+    ("safe_mode_on", "Modo seguro activado \u{2014} solo se pueden elegir valores compatibles."),
+    ("safe_mode_off", "Modo seguro desactivado."),
+    ("safe_mode_blocked", "Ningún otro valor es compatible con los ajustes actuales."),
+    ("safe_mode_rejected", "Ese valor entra en conflicto con otros ajustes (modo seguro)."),
+    ("recommended_shown", "Valores recomendados marcados con \u{2605}."),
+    ("recommended_hidden", "Marcas de recomendación ocultas."),
+    ("default_value", "predeterminado"),
+    ("unsaved_changes", "{n} {n|cambio|cambios} sin guardar"),
+    ("conflict_dlss_render_scale", "DLSS ignora la escala de renderizado"),
+    ("conflict_dlss_aa", "DLSS sustituye al antialiasing"),
+    ("conflict_vsync_fps", "La sincronización vertical anula el límite de FPS"),
+    ("confirm_disable_vsync", "La sincronización vertical ignorará este límite de FPS. ¿Desactivarla?"),
+    ("prompt_merge", "Combinar importación desde: "),
+    ("merged", "Se {n|combinó|combinaron} {n} {n|ajuste modificado|ajustes modificados}. Pulsa S para guardar."),
+    ("import_failed", "Error al importar"),
+    ("preview_title", " Vista previa JSON "),
+    ("desc_title", " Acerca de "),
+    ("desc_cost", "Coste de rendimiento"),
+    ("desc_recommended", "Recomendado"),
+    ("desc_group", "Selecciona un ajuste para ver qué hace."),
+    ("cost_low", "Bajo"),
+    ("cost_medium", "Medio"),
+    ("cost_high", "Alto"),
+    ("json_edit_title", " Editar JSON "),
+    ("json_applied", "JSON aplicado. Pulsa S para guardar."),
+    ("json_invalid", "JSON no válido"),
+    ("confirm_discard_json", "¿Descartar los cambios en el JSON?"),
+    ("working", "Procesando\u{2026}"),
+    ("too_small", "Terminal demasiado pequeña \u{2014} se necesita al menos {w}x{h}"),
+    ("hint_navigate", "Navegar"),
+    ("hint_change", "Cambiar"),
+    ("hint_set", "Fijar"),
+    ("hint_toggle", "Alternar"),
+    ("hint_fold", "Contraer"),
+    ("hint_unfold", "Expandir"),
+    ("hint_column", "Columna"),
+    ("hint_scroll", "Desplazar"),
+    ("hint_info", "Info"),
+    ("hint_preview", "JSON"),
+    ("hint_edit", "Editar"),
+    ("hint_save", "Guardar"),
+    ("hint_quit", "Salir"),
+    ("hint_close", "Cerrar"),
+    ("hint_confirm", "Confirmar"),
+    ("hint_cancel", "Cancelar"),
+    ("hint_apply", "Aplicar"),
+    ("hint_yes", "Sí"),
+    ("hint_no", "No"),
+    ("hint_dismiss", "Descartar"),
+    ("no_unsaved_changes", "No hay cambios sin guardar"),
+    ("no_changes", "No hay cambios que guardar."),
+    ("confirm_save", "¿Guardar estos cambios?"),
+    ("quit_unsaved", "Tienes cambios sin guardar."),
+    ("quit_save", "Guardar y salir [S]"),
+    ("quit_discard", "Descartar [D]"),
+    ("quit_cancel", "Cancelar [C]"),
+    ("hint_save_quit", "Guardar y salir"),
+    ("prompt_export", "Exportar ({format}) a: "),
+    ("exported", "Exportado a {path}"),
+    ("export_failed", "Error al exportar"),
+    ("hint_format", "Formato"),
+    ("title_unsaved", "(sin guardar)"),
+    ("saved_key_created", "Ajustes guardados ({n} bytes, clave del registro creada)."),
+    ("read_only_badge", "[solo lectura]"),
+    ("read_only_refused", "Modo de solo lectura: los cambios están desactivados."),
+    ("help_title", " Atajos de teclado "),
+    ("help_navigation", "Navegación"),
+    ("help_editing", "Edición"),
+    ("help_files", "Archivos"),
+    ("help_application", "Aplicación"),
+    ("help_move_up", "Subir"),
+    ("help_move_down", "Bajar"),
+    ("help_activate", "Contraer un grupo / alternar"),
+    ("help_switch_column", "Cambiar de columna"),
+    ("help_left_column", "Ir a la columna izquierda"),
+    ("help_right_column", "Ir a la columna derecha"),
+    ("help_scroll_up", "Desplazar la vista JSON hacia arriba"),
+    ("help_scroll_down", "Desplazar la vista JSON hacia abajo"),
+    ("help_cycle_next", "Valor siguiente"),
+    ("help_cycle_prev", "Valor anterior"),
+    ("help_set_tier", "Fijar un nivel de calidad directamente"),
+    ("help_safe_mode", "Activar o desactivar el modo seguro"),
+    ("help_edit_json", "Editar el JSON sin procesar"),
+    ("help_save", "Guardar en el registro"),
+    ("help_save_quit", "Guardar y salir"),
+    ("help_merge_import", "Combinar un archivo de ajustes"),
+    ("help_export", "Exportar a un archivo"),
+    ("help_recommended", "Mostrar las marcas de recomendación"),
+    ("help_description", "Mostrar el panel de descripción"),
+    ("help_defaults", "Mostrar los valores predeterminados"),
+    ("help_preview", "Mostrar la vista previa JSON"),
+    ("help_help", "Mostrar esta ayuda"),
+    ("help_back", "Descartar / cerrar panel / salir"),
+    ("help_quit", "Salir"),
+    ("hint_help", "Ayuda"),
+    ("decimal_separator", ","),
+    ("quality_level_question", "Fijar todos los ajustes de calidad en:"),
+    ("quality_level_set", "{n} {n|ajuste|ajustes} de calidad {n|fijado|fijados} en {level}."),
+    ("help_set_all_quality", "Fijar todos los ajustes de calidad a la vez"),
+    ("locked_refused", "Este ajuste está bloqueado (F para desbloquear)."),
+    ("help_lock", "Bloquear / desbloquear el ajuste"),
+    ("undid", "Deshecho: {change}."),
+    ("redid", "Rehecho: {change}."),
+    ("n_changes", "{n} cambios"),
+    ("nothing_to_undo", "Nada que deshacer."),
+    ("nothing_to_redo", "Nada que rehacer."),
+    ("help_undo", "Deshacer"),
+    ("help_redo", "Rehacer"),
+    ("preset_low", "Bajo"),
+    ("preset_medium", "Medio"),
+    ("preset_high", "Alto"),
+    ("preset_applied", "Preajuste {preset} aplicado ({n} {n|cambio|cambios})."),
+    ("preset_suggested", "Tu GPU ({gpu}) sugiere el preajuste {preset} (una estimación aproximada). Pulsa G para aplicarlo."),
+    ("help_apply_suggested", "Aplicar el preajuste sugerido para tu GPU"),
+    ("unknown_gpu", "no detectada"),
+    ("confirm_reset_all", "¿Restablecer todos los ajustes a los valores predeterminados del juego?"),
+    ("reset_field", "{label} restablecido a {value}."),
+    ("reset_all", "{n} {n|ajuste restablecido a su valor predeterminado|ajustes restablecidos a sus valores predeterminados}."),
+    ("help_reset_field", "Restablecer el ajuste a su valor predeterminado"),
+    ("help_reset_all", "Restablecer todos los ajustes"),
+    ("reverted_field", "{label} vuelve al valor guardado."),
+    ("reverted_all", "Se {n|revirtió|revirtieron} {n} {n|cambio pendiente|cambios pendientes}."),
+    ("help_revert_field", "Volver al valor guardado"),
+    ("help_revert_all", "Revertir todos los cambios sin guardar"),
+    ("copied", "Copiado {text}"),
+    ("copy_failed", "Error al copiar"),
+    ("help_copy_value", "Copiar el ajuste como \"Key\": value"),
+    ("copied_bytes", "{n} bytes copiados al portapapeles."),
+    ("help_copy_json", "Copiar el JSON de ajustes"),
+    ("help_copy_markdown", "Copiar un resumen en Markdown"),
+    ("help_json_keys", "Mostrar las claves JSON junto a las etiquetas"),
+    ("help_mark", "Marcar la fila para edición en lote"),
+    ("mark_incompatible", "Solo los ajustes de calidad 1-5 se pueden marcar para edición en lote."),
+    ("hint_mark", "Marcar"),
+    ("hint_change_marked", "Cambiar marcados"),
+    ("hint_clear_marks", "Quitar marcas"),
+    ("legacy_ignored", "Se encontraron ajustes de una versión anterior del juego ({names}); se ignoran."),
+    ("legacy_migrated", "Ajustes copiados de {name}."),
+    ("legacy_migrate_failed", "No se pudieron copiar los ajustes anteriores"),
+    ("confirm_migrate_legacy", "Solo se encontraron ajustes de una versión anterior del juego. ¿Copiarlos al nombre actual del registro?"),
+    ("autosave_badge", "[autoguardado {n} s]"),
+    ("help_cycle_next_coarse", "Valor siguiente, en pasos grandes"),
+    ("help_cycle_prev_coarse", "Valor anterior, en pasos grandes"),
+    ("help_cycle_max", "Valor más alto"),
+    ("help_cycle_min", "Valor más bajo"),
+    ("hint_coarse", "Paso grande"),
+    ("hint_min_max", "Mín/Máx"),
+    ("help_log", "Mostrar el registro de eventos"),
+    ("log_title", " Registro de eventos "),
+    ("prompt_set_value", "Escribe un valor, p. ej. \"fps 120\" o \"render scale 1.2\":"),
+    ("set_value_usage", "Escribe el nombre de un ajuste, un espacio y un valor."),
+    ("no_such_setting", "Ningún ajuste coincide con \"{query}\"."),
+    ("ambiguous_setting", "Coinciden varios ajustes: {names}"),
+    ("invalid_value", "{setting} admite {options}."),
+    ("help_set_value", "Fijar un valor por nombre"),
+    ("preset_badge", "[{preset}]"),
+    ("group_favorites", "Favoritos"),
+    ("help_favorite", "Fijar o quitar el ajuste de Favoritos"),
+    ("help_favorites_only", "Mostrar solo Favoritos"),
+    ("registry_not_utf8", "El valor del registro no es UTF-8 válido (byte erróneo en la posición {n}); los datos están dañados. Se usan los valores predeterminados."),
+    ("registry_invalid", "El valor del registro no es un JSON de ajustes válido ({error}). Se usan los valores predeterminados."),
+    ("choose_theme", "Tema de color:"),
+    ("theme_dark", "Oscuro"),
+    ("theme_light", "Claro"),
+    ("theme_solarized", "Solarized"),
+    ("help_theme", "Elegir el tema de color"),
+    ("help_spacing", "Filas compactas o espaciadas"),
+    ("help_copy_code", "Copiar un código de preajuste para compartir"),
+    ("help_apply_code", "Aplicar un código de preajuste"),
+    ("prompt_code", "Pega un código de preajuste:"),
+    ("code_copied", "Código de preajuste copiado: {code}"),
+    ("code_not_a_code", "No es un código de preajuste; los códigos empiezan por HSR-"),
+    ("code_damaged", "El código de preajuste está dañado; revisa si hay erratas"),
+    ("code_newer", "Este código de preajuste es de una versión más reciente de hsr-settings (formato {n}); actualiza para usarlo"),
+    ("code_applied", "Código de preajuste aplicado: {n} {n|cambio|cambios}"),
+    ("theme_high_contrast", "Alto contraste"),
+    ("code_bad_character", "El código de preajuste contiene '{c}', que nunca aparece en un código"),
+    ("code_unknown_version", "Formato de código de preajuste desconocido {n}; el código está dañado"),
+    ("code_truncated", "El código de preajuste está incompleto; copia el código entero"),
+    ("client_global", "Global"),
+    ("client_cn", "CN"),
+    ("sync_clients_question", "Los ajustes de los clientes Global y CN son distintos. ¿Cuál se copia sobre el otro?"),
+    ("sync_keep_both", "Mantener ambos"),
+    ("clients_synced", "Ajustes copiados sobre el cliente {client}"),
+    ("clients_sync_failed", "No se pudieron copiar los ajustes entre clientes"),
+    ("help_show_message", "Mostrar el último mensaje completo"),
+    ("hint_full_message", "Mensaje completo"),
+    ("message_title", "Mensaje"),
+    ("message_close", "Pulsa cualquier tecla para cerrar"),
+    ("help_preferences", "Preferencias"),
+    ("prefs_title", "Preferencias (se guardan en config.toml)"),
+    ("pref_language", "Idioma"),
+    ("pref_ask", "Preguntar al iniciar"),
+    ("pref_theme", "Tema"),
+    ("pref_wrap", "Navegación circular"),
+    ("pref_confirm_saves", "Revisar los cambios antes de guardar"),
+    ("pref_vsync_autofix", "Ofrecer desactivar la sincronización vertical con límite de FPS"),
+    ("pref_sync_clients", "Comparar los clientes Global y CN"),
+    ("pref_row_spacing", "Espaciado de filas"),
+    ("pref_autosave", "Autoguardado"),
+    ("pref_seconds", "{n} s"),
+    ("config_unknown_key", "Clave desconocida en config.toml, ignorada: {key}"),
+    ("config_invalid", "No se pudo leer config.toml y se copió a config.toml.bak; se usan los valores predeterminados"),
+    ("hive_notice", "Ajustes leídos de {read}; se guardan en {write}."),
+    ("choose_language", "Idioma"),
+    ("help_language", "Elegir el idioma"),
+    ("pref_skip_picker", "Omitir el selector de idioma"),
+    ("external_change_question", "El juego cambió estos ajustes después de cargarlos. Guardar ahora los sobrescribiría."),
+    ("external_merge", "Recargar y combinar"),
+    ("external_overwrite", "Sobrescribir"),
+    ("external_merged", "Se cargaron los cambios del juego y se {n|conservó|conservaron} {n} {n|edición tuya|ediciones tuyas}. Pulsa S para guardar."),
+    ("translation_missing", "{file}: {n|falta|faltan} {n} {n|texto|textos}, se {n|muestra|muestran} en {lang}"),
+    ("translation_unknown", "{file}: claves desconocidas ignoradas: {keys}"),
+    ("translation_built_in", "{file} se ignora; ese idioma ya está incluido"),
+    ("translation_invalid", "No se pudo leer {file}"),
+    ("save_creates_key", "La clave del registro del juego aún no existe \u{2014} se creará al guardar."),
+    ("pref_untranslated", "({n} sin traducir)"),
+    ("pref_category_colors", "Colorear filas por categoría"),
+    ("plural_rule", "one"),
+    ("failure", "{what}: {error}"),
+    ("translation_placeholders", "{file}: marcadores incorrectos, se mantiene el texto de respaldo: {keys}"),
+    ("may_run_as", "(puede funcionar como {value})"),
+    ("saving", "Guardando\u{2026}"),
+    ("save_in_progress", "Ya hay un guardado en curso."),
+    ("quit_when_saved", "Se saldrá cuando termine el guardado\u{2026}"),
+    ("external_reloaded", "El juego cambió los ajustes; se volvieron a cargar."),
+    ("tier_very_low", "Muy bajo"),
+    ("tier_low", "Bajo"),
+    ("tier_medium", "Medio"),
+    ("tier_high", "Alto"),
+    ("tier_very_high", "Muy alto"),
+    ("pref_tier_names", "Nombres de niveles de calidad"),
+    ("help_tier_names", "Niveles de calidad como nombres o números"),
+    ("help_reload", "Recargar del registro, descartando cambios"),
+    ("confirm_reload", "¿Recargar del registro y descartar los cambios sin guardar?"),
+    ("reloaded", "Se recargaron los ajustes del registro."),
+    ("code_out_of_range", "El código de preajuste pone {setting} en {value}, que el juego no admite"),
+    ("config_invalid_kept", "No se pudo leer config.toml; se usan los valores predeterminados sin sobrescribirlo"),
+];
+
+const PORTUGUESE_BR: &[(&str, &str)] = &[
+    ("title", " Configurações gráficas de Star Rail "),
+    ("saved_bytes", "Configurações salvas ({n} bytes)."),
+    ("save_failed", "Falha ao salvar"),
+    ("no_registry", "Chave do registro não encontrada \u{2014} usando os valores padrão. Salve para criá-la."),
+    ("on", "Sim"),
+    ("off", "Não"),
+    ("fps", "FPS"),
+    ("vsync", "Sincronização vertical"),
+    ("render_scale", "Escala de renderização"),
+    ("resolution_quality", "Qualidade da resolução"),
+    ("shadow_quality", "Qualidade das sombras"),
+    ("light_quality", "Qualidade da iluminação"),
+    ("character_quality", "Qualidade dos personagens"),
+    ("env_detail", "Detalhes do ambiente"),
+    ("reflection_quality", "Qualidade dos reflexos"),
+    ("sfx_quality", "Qualidade dos efeitos"),
+    ("bloom_quality", "Qualidade do brilho"),
+    ("anti_aliasing", "Anti-aliasing"),
+    ("self_shadow", "Autossombreamento"),
+    ("dlss_quality", "Qualidade do DLSS"),
+    ("particle_trail", "Rastro de partículas"),
+    ("group_display", "Tela"),
+    ("group_quality", "Qualidade"),
+    ("group_advanced", "Avançado"),
+    ("group_count", "{n} {n|configuração|configurações}"),
+    ("config_save_failed", "Falha ao salvar a configuração"),
+    ("safe_mode_badge", "[Seguro] "),
+    ("safe_mode_on", "Modo seguro ativado \u{2014} só valores compatíveis podem ser escolhidos."),
+    ("safe_mode_off", "Modo seguro desativado."),
+    ("safe_mode_blocked", "Nenhum outro valor é compatível com as configurações atuais."),
+    ("safe_mode_rejected", "Esse valor conflita com outras configurações (modo seguro)."),
+    ("recommended_shown", "Valores recomendados marcados com \u{2605}."),
+    ("recommended_hidden", "Marcas de recomendação ocultas."),
+    ("default_value", "padrão"),
+    ("unsaved_changes", "{n} {n|alteração não salva|alterações não salvas}"),
+    ("conflict_dlss_render_scale", "O DLSS ignora a escala de renderização"),
+    ("conflict_dlss_aa", "O DLSS substitui o anti-aliasing"),
+    ("conflict_vsync_fps", "A sincronização vertical anula o limite de FPS"),
+    ("confirm_disable_vsync", "A sincronização vertical vai ignorar este limite de FPS. Desativá-la?"),
+    ("prompt_merge", "Mesclar importação de: "),
+    ("merged", "{n} {n|configuração alterada mesclada|configurações alteradas mescladas}. Pressione S para salvar."),
+    ("import_failed", "Falha na importação"),
+    ("preview_title", " Prévia do JSON "),
+    ("desc_title", " Sobre "),
+    ("desc_cost", "Custo de desempenho"),
+    ("desc_recommended", "Recomendado"),
+    ("desc_group", "Selecione uma configuração para ver o que ela faz."),
+    ("cost_low", "Baixo"),
+    ("cost_medium", "Médio"),
+    ("cost_high", "Alto"),
+    ("json_edit_title", " Editar JSON "),
+    ("json_applied", "JSON aplicado. Pressione S para salvar."),
+    ("json_invalid", "JSON inválido"),
+    ("confirm_discard_json", "Descartar as edições no JSON?"),
+    ("working", "Processando\u{2026}"),
+    ("too_small", "Terminal pequeno demais \u{2014} é preciso pelo menos {w}x{h}"),
+    ("hint_navigate", "Navegar"),
+    ("hint_change", "Alterar"),
+    ("hint_set", "Definir"),
+    ("hint_toggle", "Alternar"),
+    ("hint_fold", "Recolher"),
+    ("hint_unfold", "Expandir"),
+    ("hint_column", "Coluna"),
+    ("hint_scroll", "Rolar"),
+    ("hint_info", "Info"),
+    ("hint_preview", "JSON"),
+    ("hint_edit", "Editar"),
+    ("hint_save", "Salvar"),
+    ("hint_quit", "Sair"),
+    ("hint_close", "Fechar"),
+    ("hint_confirm", "Confirmar"),
+    ("hint_cancel", "Cancelar"),
+    ("hint_apply", "Aplicar"),
+    ("hint_yes", "Sim"),
+    ("hint_no", "Não"),
+    ("hint_dismiss", "Dispensar"),
+    ("no_unsaved_changes", "Nenhuma alteração não salva"),
+    ("no_changes", "Nenhuma alteração para salvar."),
+    ("confirm_save", "Salvar estas alterações?"),
+    ("quit_unsaved", "Há alterações não salvas."),
+    ("quit_save", "Salvar e sair [S]"),
+    ("quit_discard", "Descartar [D]"),
+    ("quit_cancel", "Cancelar [C]"),
+    ("hint_save_quit", "Salvar e sair"),
+    ("prompt_export", "Exportar ({format}) para: "),
+    ("exported", "Exportado para {path}"),
+    ("export_failed", "Falha na exportação"),
+    ("hint_format", "Formato"),
+    ("title_unsaved", "(não salvo)"),
+    ("saved_key_created", "Configurações salvas ({n} bytes, chave do registro criada)."),
+    ("read_only_badge", "[somente leitura]"),
+    ("read_only_refused", "Modo somente leitura: alterações desativadas."),
+    ("help_title", " Atalhos de teclado "),
+    ("help_navigation", "Navegação"),
+    ("help_editing", "Edição"),
+    ("help_files", "Arquivos"),
+    ("help_application", "Aplicativo"),
+    ("help_move_up", "Subir"),
+    ("help_move_down", "Descer"),
+    ("help_activate", "Recolher um grupo / alternar"),
+    ("help_switch_column", "Trocar de coluna"),
+    ("help_left_column", "Ir para a coluna da esquerda"),
+    ("help_right_column", "Ir para a coluna da direita"),
+    ("help_scroll_up", "Rolar a prévia do JSON para cima"),
+    ("help_scroll_down", "Rolar a prévia do JSON para baixo"),
+    ("help_cycle_next", "Próximo valor"),
+    ("help_cycle_prev", "Valor anterior"),
+    ("help_set_tier", "Definir um nível de qualidade diretamente"),
+    ("help_safe_mode", "Ativar ou desativar o modo seguro"),
+    ("help_edit_json", "Editar o JSON bruto"),
+    ("help_save", "Salvar no registro"),
+    ("help_save_quit", "Salvar e sair"),
+    ("help_merge_import", "Mesclar um arquivo de configurações"),
+    ("help_export", "Exportar para um arquivo"),
+    ("help_recommended", "Mostrar as marcas de recomendação"),
+    ("help_description", "Mostrar o painel de descrição"),
+    ("help_defaults", "Mostrar os valores padrão"),
+    ("help_preview", "Mostrar a prévia do JSON"),
+    ("help_help", "Mostrar esta ajuda"),
+    ("help_back", "Dispensar / fechar painel / sair"),
+    ("help_quit", "Sair"),
+    ("hint_help", "Ajuda"),
+    ("decimal_separator", ","),
+    ("quality_level_question", "Definir todas as configurações de qualidade como:"),
+    ("quality_level_set", "{n} {n|configuração|configurações} de qualidade {n|definida|definidas} como {level}."),
+    ("help_set_all_quality", "Definir todas as configurações de qualidade de uma vez"),
+    ("locked_refused", "Esta configuração está bloqueada (F para desbloquear)."),
+    ("help_lock", "Bloquear / desbloquear a configuração"),
+    ("undid", "Desfeito: {change}."),
+    ("redid", "Refeito: {change}."),
+    ("n_changes", "{n} alterações"),
+    ("nothing_to_undo", "Nada para desfazer."),
+    ("nothing_to_redo", "Nada para refazer."),
+    ("help_undo", "Desfazer"),
+    ("help_redo", "Refazer"),
+    ("preset_low", "Baixo"),
+    ("preset_medium", "Médio"),
+    ("preset_high", "Alto"),
+    ("preset_applied", "Predefinição {preset} aplicada ({n} {n|alteração|alterações})."),
+    ("preset_suggested", "Sua GPU ({gpu}) sugere a predefinição {preset} (uma estimativa aproximada). Pressione G para aplicá-la."),
+    ("help_apply_suggested", "Aplicar a predefinição sugerida para sua GPU"),
+    ("unknown_gpu", "não detectada"),
+    ("confirm_reset_all", "Restaurar todas as configurações para o padrão do jogo?"),
+    ("reset_field", "{label} restaurado para {value}."),
+    ("reset_all", "{n} {n|configuração restaurada|configurações restauradas} para o padrão."),
+    ("help_reset_field", "Restaurar a configuração para o padrão"),
+    ("help_reset_all", "Restaurar todas as configurações para o padrão"),
+    ("reverted_field", "{label} voltou ao valor salvo."),
+    ("reverted_all", "{n} {n|alteração pendente revertida|alterações pendentes revertidas}."),
+    ("help_revert_field", "Voltar ao valor salvo"),
+    ("help_revert_all", "Reverter todas as alterações não salvas"),
+    ("copied", "Copiado {text}"),
+    ("copy_failed", "Falha ao copiar"),
+    ("help_copy_value", "Copiar a configuração como \"Key\": value"),
+    ("copied_bytes", "{n} bytes copiados para a área de transferência."),
+    ("help_copy_json", "Copiar o JSON das configurações"),
+    ("help_copy_markdown", "Copiar um resumo em Markdown"),
+    ("help_json_keys", "Mostrar as chaves JSON ao lado dos rótulos"),
+    ("help_mark", "Marcar a linha para edição em lote"),
+    ("mark_incompatible", "Só configurações de qualidade 1-5 podem ser marcadas para edição em lote."),
+    ("hint_mark", "Marcar"),
+    ("hint_change_marked", "Alterar marcadas"),
+    ("hint_clear_marks", "Limpar marcas"),
+    ("legacy_ignored", "Foram encontradas configurações de uma versão antiga do jogo ({names}); elas são ignoradas."),
+    ("legacy_migrated", "Configurações copiadas de {name}."),
+    ("legacy_migrate_failed", "Não foi possível copiar as configurações antigas"),
+    ("confirm_migrate_legacy", "Só foram encontradas configurações de uma versão antiga do jogo. Copiá-las para o nome atual no registro?"),
+    ("autosave_badge", "[salvamento automático {n} s]"),
+    ("help_cycle_next_coarse", "Próximo valor, em passos grandes"),
+    ("help_cycle_prev_coarse", "Valor anterior, em passos grandes"),
+    ("help_cycle_max", "Valor mais alto"),
+    ("help_cycle_min", "Valor mais baixo"),
+    ("hint_coarse", "Passo grande"),
+    ("hint_min_max", "Mín/Máx"),
+    ("help_log", "Mostrar o registro de eventos"),
+    ("log_title", " Registro de eventos "),
+    ("prompt_set_value", "Digite um valor, p. ex. \"fps 120\" ou \"render scale 1.2\":"),
+    ("set_value_usage", "Digite o nome de uma configuração, um espaço e um valor."),
+    ("no_such_setting", "Nenhuma configuração corresponde a \"{query}\"."),
+    ("ambiguous_setting", "Várias configurações correspondem: {names}"),
+    ("invalid_value", "{setting} aceita {options}."),
+    ("help_set_value", "Definir um valor pelo nome"),
+    ("preset_badge", "[{preset}]"),
+    ("group_favorites", "Favoritos"),
+    ("help_favorite", "Fixar ou desafixar a configuração nos Favoritos"),
+    ("help_favorites_only", "Mostrar só os Favoritos"),
+    ("registry_not_utf8", "O valor do registro não é UTF-8 válido (byte inválido na posição {n}); os dados estão corrompidos. Usando os valores padrão."),
+    ("registry_invalid", "O valor do registro não é um JSON de configurações válido ({error}). Usando os valores padrão."),
+    ("choose_theme", "Tema de cores:"),
+    ("theme_dark", "Escuro"),
+    ("theme_light", "Claro"),
+    ("theme_solarized", "Solarized"),
+    ("help_theme", "Escolher o tema de cores"),
+    ("help_spacing", "Linhas compactas ou espaçadas"),
+    ("help_copy_code", "Copiar um código de predefinição para compartilhar"),
+    ("help_apply_code", "Aplicar um código de predefinição"),
+    ("prompt_code", "Cole um código de predefinição:"),
+    ("code_copied", "Código de predefinição copiado: {code}"),
+    ("code_not_a_code", "Não é um código de predefinição; os códigos começam com HSR-"),
+    ("code_damaged", "O código de predefinição está danificado; verifique se há erros de digitação"),
+    ("code_newer", "Este código de predefinição é de uma versão mais nova do hsr-settings (formato {n}); atualize para usá-lo"),
+    ("code_applied", "Código de predefinição aplicado: {n} {n|alteração|alterações}"),
+    ("theme_high_contrast", "Alto contraste"),
+    ("code_bad_character", "O código de predefinição contém '{c}', que nunca aparece em um código"),
+    ("code_unknown_version", "Formato de código de predefinição desconhecido {n}; o código está danificado"),
+    ("code_truncated", "O código de predefinição está incompleto; copie o código inteiro"),
+    ("client_global", "Global"),
+    ("client_cn", "CN"),
+    ("sync_clients_question", "As configurações dos clientes Global e CN são diferentes. Qual copiar sobre o outro?"),
+    ("sync_keep_both", "Manter ambos"),
+    ("clients_synced", "Configurações copiadas sobre o cliente {client}"),
+    ("clients_sync_failed", "Não foi possível copiar as configurações entre clientes"),
+    ("help_show_message", "Mostrar a última mensagem completa"),
+    ("hint_full_message", "Mensagem completa"),
+    ("message_title", "Mensagem"),
+    ("message_close", "Pressione qualquer tecla para fechar"),
+    ("help_preferences", "Preferências"),
+    ("prefs_title", "Preferências (salvas em config.toml)"),
+    ("pref_language", "Idioma"),
+    ("pref_ask", "Perguntar ao iniciar"),
+    ("pref_theme", "Tema"),
+    ("pref_wrap", "Navegação circular"),
+    ("pref_confirm_saves", "Revisar as alterações antes de salvar"),
+    ("pref_vsync_autofix", "Oferecer desativar a sincronização vertical com limite de FPS"),
+    ("pref_sync_clients", "Comparar os clientes Global e CN"),
+    ("pref_row_spacing", "Espaçamento das linhas"),
+    ("pref_autosave", "Salvamento automático"),
+    ("pref_seconds", "{n} s"),
+    ("config_unknown_key", "Chave desconhecida em config.toml, ignorada: {key}"),
+    ("config_invalid", "Não foi possível ler config.toml e ele foi copiado para config.toml.bak; usando os valores padrão"),
+    ("hive_notice", "Configurações lidas de {read}; salvas em {write}."),
+    ("choose_language", "Idioma"),
+    ("help_language", "Escolher o idioma"),
+    ("pref_skip_picker", "Pular o seletor de idioma"),
+    ("external_change_question", "O jogo alterou estas configurações depois que foram carregadas. Salvar agora as sobrescreveria."),
+    ("external_merge", "Recarregar e mesclar"),
+    ("external_overwrite", "Sobrescrever"),
+    ("external_merged", "As alterações do jogo foram carregadas e {n} {n|edição sua foi mantida|edições suas foram mantidas}. Pressione S para salvar."),
+    ("translation_missing", "{file}: {n|falta|faltam} {n} {n|texto|textos}, {n|exibido|exibidos} em {lang}"),
+    ("translation_unknown", "{file}: chaves desconhecidas ignoradas: {keys}"),
+    ("translation_built_in", "{file} foi ignorado; esse idioma já está incluído"),
+    ("translation_invalid", "Não foi possível ler {file}"),
+    ("save_creates_key", "A chave do registro do jogo ainda não existe \u{2014} ela será criada ao salvar."),
+    ("pref_untranslated", "({n} sem tradução)"),
+    ("pref_category_colors", "Colorir linhas por categoria"),
+    ("plural_rule", "zero_one"),
+    ("failure", "{what}: {error}"),
+    ("translation_placeholders", "{file}: marcadores incorretos, mantido o texto alternativo: {keys}"),
+    ("may_run_as", "(pode funcionar como {value})"),
+    ("saving", "Salvando\u{2026}"),
+    ("save_in_progress", "Já há um salvamento em andamento."),
+    ("quit_when_saved", "Saindo assim que o salvamento terminar\u{2026}"),
+    ("external_reloaded", "O jogo alterou as configurações; elas foram recarregadas."),
+    ("tier_very_low", "Muito baixo"),
+    ("tier_low", "Baixo"),
+    ("tier_medium", "Médio"),
+    ("tier_high", "Alto"),
+    ("tier_very_high", "Muito alto"),
+    ("pref_tier_names", "Nomes dos níveis de qualidade"),
+    ("help_tier_names", "Níveis de qualidade como nomes ou números"),
+    ("help_reload", "Recarregar do registro, descartando edições"),
+    ("confirm_reload", "Recarregar do registro e descartar as alterações não salvas?"),
+    ("reloaded", "As configurações foram recarregadas do registro."),
+    ("code_out_of_range", "O código de predefinição define {setting} como {value}, que o jogo não aceita"),
+    ("config_invalid_kept", "Não foi possível ler config.toml; usando os valores padrão sem sobrescrevê-lo"),
+];
+
 // ---------------------------------------------------------------------------
 // Settings model
 // ---------------------------------------------------------------------------
 
+/// Field order is the key order the game itself writes (pinned by a test
+/// against `tests/data/game_settings.json`, a value captured from the game);
+/// serde serializes in declaration order, so don't reorder fields.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 struct GraphicsSettings {
@@ -156,6 +2160,9 @@ struct GraphicsSettings {
     enable_self_shadow: i64,
     dlss_quality: i64,
     particle_trail_smoothness: i64,
+    /// Keys this tool doesn't model, carried through unchanged on save.
+    #[serde(flatten)]
+    extra: serde_json::Map<String, serde_json::Value>,
 }
 
 impl Default for GraphicsSettings {
@@ -178,6 +2185,7 @@ impl Default for GraphicsSettings {
             enable_self_shadow: 1,
             dlss_quality: 0,
             particle_trail_smoothness: 3,
+            extra: serde_json::Map::new(),
         }
     }
 }
@@ -186,520 +2194,6063 @@ impl Default for GraphicsSettings {
 // Registry I/O
 // ---------------------------------------------------------------------------
 
-fn read_settings() -> (GraphicsSettings, bool) {
-    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
-    let Ok(key) = hkcu.open_subkey(REG_PATH) else {
-        return (GraphicsSettings::default(), false);
-    };
-    let Ok(val) = key.get_raw_value(REG_VALUE) else {
-        return (GraphicsSettings::default(), false);
-    };
-    let json = String::from_utf8_lossy(&val.bytes)
-        .trim_end_matches('\0')
-        .to_string();
-    match serde_json::from_str::<GraphicsSettings>(&json) {
-        Ok(s) => (s, true),
-        Err(_) => (GraphicsSettings::default(), false),
-    }
+/// A successfully parsed registry value.
+struct Stored {
+    settings: GraphicsSettings,
+    /// Whether the blob ended in a NUL byte; saves mirror it by default.
+    nul_terminated: bool,
+    /// Where the value was found.
+    hive: Hive,
 }
 
-fn write_settings(settings: &GraphicsSettings) -> io::Result<()> {
-    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
-    let (key, _) = hkcu.create_subkey(REG_PATH)?;
-    let mut json = serde_json::to_string(settings)?;
-    json.push('\0');
-    key.set_raw_value(
-        REG_VALUE,
-        &RegValue {
-            vtype: REG_BINARY,
-            bytes: json.into_bytes(),
-        },
-    )?;
-    Ok(())
+/// What reading the registry value found. Encoding and schema problems are
+/// kept apart so a report says whether the blob itself is damaged.
+enum ReadResult {
+    Found(Box<Stored>),
+    /// The key or value doesn't exist (the game was never launched).
+    Missing,
+    /// The blob isn't UTF-8 text; holds the offset of the first bad byte.
+    NotUtf8(usize),
+    /// Valid text that doesn't parse as settings JSON.
+    Invalid(String),
 }
 
-// ---------------------------------------------------------------------------
-// Setting field identifiers (no fragile index mapping)
-// ---------------------------------------------------------------------------
+impl ReadResult {
+    fn stored(self) -> Option<Stored> {
+        match self {
+            ReadResult::Found(stored) => Some(*stored),
+            _ => None,
+        }
+    }
 
-#[derive(Clone, Copy)]
-enum Field {
-    Fps,
-    VSync,
-    RenderScale,
-    ResolutionQuality,
-    ShadowQuality,
-    LightQuality,
-    CharacterQuality,
-    EnvDetailQuality,
-    ReflectionQuality,
-    SfxQuality,
-    BloomQuality,
-    AaMode,
-    SelfShadow,
-    DlssQuality,
-    ParticleTrail,
+    /// Why the defaults are in use, when nothing usable was read.
+    fn problem(&self, t: &L10n) -> Option<String> {
+        match self {
+            ReadResult::Found(_) => None,
+            ReadResult::Missing => Some(t.no_registry.to_string()),
+            ReadResult::NotUtf8(at) => Some(t.fill(&t.registry_not_utf8, &[("n", at)])),
+            ReadResult::Invalid(e) => Some(t.fill(&t.registry_invalid, &[("error", e)])),
+        }
+    }
 }
 
-// ---------------------------------------------------------------------------
-// TUI setting descriptors
-// ---------------------------------------------------------------------------
+fn read_settings() -> ReadResult {
+    read_settings_from(Client::Global)
+}
 
-#[derive(Clone)]
-enum SettingKind {
-    SelectI64(Vec<(&'static str, i64)>),
-    SelectF64(Vec<(&'static str, f64)>),
-    Toggle,
+/// Reads from HKCU, or from HKLM when HKCU has no key for the client.
+fn read_settings_from(client: Client) -> ReadResult {
+    let Some((hive, key)) = Hive::ALL
+        .into_iter()
+        .find_map(|hive| hive.root().open_subkey(client.reg_path()).ok().map(|key| (hive, key)))
+    else {
+        return ReadResult::Missing;
+    };
+    let Ok(val) = key.get_raw_value(REG_VALUE) else {
+        return ReadResult::Missing;
+    };
+    parse_value(&val.bytes, hive)
 }
 
-#[derive(Clone)]
-struct SettingDef {
-    field: Field,
-    kind: SettingKind,
+/// Reads the client's value from `hive` alone, as a save to it would
+/// replace it.
+fn read_settings_in(hive: Hive, client: Client) -> ReadResult {
+    let Ok(val) = hive.root().open_subkey(client.reg_path()).and_then(|key| key.get_raw_value(REG_VALUE)) else {
+        return ReadResult::Missing;
+    };
+    parse_value(&val.bytes, hive)
 }
 
-impl SettingDef {
-    fn label(&self, t: &L10n) -> &'static str {
-        match self.field {
-            Field::Fps               => t.fps,
-            Field::VSync             => t.vsync,
-            Field::RenderScale       => t.render_scale,
-            Field::ResolutionQuality => t.resolution_quality,
-            Field::ShadowQuality     => t.shadow_quality,
-            Field::LightQuality      => t.light_quality,
-            Field::CharacterQuality  => t.character_quality,
-            Field::EnvDetailQuality  => t.env_detail,
-            Field::ReflectionQuality => t.reflection_quality,
-            Field::SfxQuality        => t.sfx_quality,
-            Field::BloomQuality      => t.bloom_quality,
-            Field::AaMode            => t.anti_aliasing,
-            Field::SelfShadow        => t.self_shadow,
-            Field::DlssQuality       => t.dlss_quality,
-            Field::ParticleTrail     => t.particle_trail,
-        }
-    }
-}
-
-fn setting_defs() -> Vec<SettingDef> {
-    let quality: Vec<(&str, i64)> = (1..=5).map(|i| (leak_str(i.to_string()), i)).collect();
+/// Parses a settings blob read from `hive`.
+fn parse_value(bytes: &[u8], hive: Hive) -> ReadResult {
+    let nul_terminated = bytes.last() == Some(&0);
+    let json = match std::str::from_utf8(bytes) {
+        Ok(text) => text.trim_end_matches('\0'),
+        Err(e) => return ReadResult::NotUtf8(e.valid_up_to()),
+    };
+    match serde_json::from_str::<GraphicsSettings>(json) {
+        Ok(settings) => ReadResult::Found(Box::new(Stored { settings, nul_terminated, hive })),
+        Err(e) => ReadResult::Invalid(e.to_string()),
+    }
+}
 
-    vec![
-        SettingDef { field: Field::Fps,               kind: SettingKind::SelectI64(vec![("30", 30), ("60", 60), ("120", 120)]) },
-        SettingDef { field: Field::VSync,             kind: SettingKind::Toggle },
-        SettingDef { field: Field::RenderScale,       kind: SettingKind::SelectF64(
-            (6..=20).step_by(2).map(|v| { let f = v as f64 / 10.0; (leak_str(format!("{f:.1}")), f) }).collect(),
-        )},
-        SettingDef { field: Field::ResolutionQuality, kind: SettingKind::SelectI64(quality.clone()) },
-        SettingDef { field: Field::ShadowQuality,     kind: SettingKind::SelectI64(quality.clone()) },
-        SettingDef { field: Field::LightQuality,      kind: SettingKind::SelectI64(quality.clone()) },
-        SettingDef { field: Field::CharacterQuality,  kind: SettingKind::SelectI64(quality.clone()) },
-        SettingDef { field: Field::EnvDetailQuality,  kind: SettingKind::SelectI64(quality.clone()) },
-        SettingDef { field: Field::ReflectionQuality, kind: SettingKind::SelectI64(quality.clone()) },
-        SettingDef { field: Field::SfxQuality,        kind: SettingKind::SelectI64(quality.clone()) },
-        SettingDef { field: Field::BloomQuality,      kind: SettingKind::SelectI64(quality.clone()) },
-        SettingDef { field: Field::AaMode,            kind: SettingKind::SelectI64(vec![("Off", 0), ("On", 1)]) },
-        SettingDef { field: Field::SelfShadow,        kind: SettingKind::SelectI64(vec![("Off", 0), ("On", 1)]) },
-        SettingDef { field: Field::DlssQuality,       kind: SettingKind::SelectI64(
-            std::iter::once(("Off", 0i64)).chain((1..=5).map(|i| (leak_str(i.to_string()), i))).collect(),
-        )},
-        SettingDef { field: Field::ParticleTrail,     kind: SettingKind::SelectI64(quality) },
-    ]
+/// Settings values under the game's key other than `REG_VALUE`, left behind
+/// by an earlier game version. Windows keeps no per-value timestamps, so
+/// there is no telling which one was written last; `REG_VALUE` always wins.
+fn legacy_values() -> Vec<String> {
+    let Ok(key) = RegKey::predef(HKEY_CURRENT_USER).open_subkey(REG_PATH) else {
+        return Vec::new();
+    };
+    key.enum_values()
+        .filter_map(Result::ok)
+        .map(|(name, _)| name)
+        .filter(|name| name.starts_with(REG_VALUE_PREFIX) && name != REG_VALUE)
+        .collect()
 }
 
-fn leak_str(s: String) -> &'static str {
-    Box::leak(s.into_boxed_str())
+/// Registry root holding the game's key. The game writes under HKCU; some
+/// multi-user and service-account installs have it under HKLM instead.
+#[derive(Clone, Copy, PartialEq)]
+enum Hive {
+    CurrentUser,
+    LocalMachine,
 }
 
-// ---------------------------------------------------------------------------
-// Field accessors
-// ---------------------------------------------------------------------------
+impl Hive {
+    /// In the order reads try them.
+    const ALL: [Hive; 2] = [Hive::CurrentUser, Hive::LocalMachine];
 
-fn get_i64(s: &GraphicsSettings, f: Field) -> i64 {
-    match f {
-        Field::Fps               => s.fps,
-        Field::ResolutionQuality => s.resolution_quality,
-        Field::ShadowQuality     => s.shadow_quality,
-        Field::LightQuality      => s.light_quality,
-        Field::CharacterQuality  => s.character_quality,
-        Field::EnvDetailQuality  => s.env_detail_quality,
-        Field::ReflectionQuality => s.reflection_quality,
-        Field::SfxQuality        => s.sfx_quality,
-        Field::BloomQuality      => s.bloom_quality,
-        Field::AaMode            => s.aa_mode,
-        Field::SelfShadow        => s.enable_self_shadow,
-        Field::DlssQuality       => s.dlss_quality,
-        Field::ParticleTrail     => s.particle_trail_smoothness,
-        _ => 0,
+    /// Parses a `--hive` argument.
+    fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "hkcu" | "hkey_current_user" => Some(Hive::CurrentUser),
+            "hklm" | "hkey_local_machine" => Some(Hive::LocalMachine),
+            _ => None,
+        }
     }
-}
 
-fn set_i64(s: &mut GraphicsSettings, f: Field, v: i64) {
-    match f {
-        Field::Fps               => s.fps = v,
-        Field::ResolutionQuality => s.resolution_quality = v,
-        Field::ShadowQuality     => s.shadow_quality = v,
-        Field::LightQuality      => s.light_quality = v,
-        Field::CharacterQuality  => s.character_quality = v,
-        Field::EnvDetailQuality  => s.env_detail_quality = v,
-        Field::ReflectionQuality => s.reflection_quality = v,
-        Field::SfxQuality        => s.sfx_quality = v,
-        Field::BloomQuality      => s.bloom_quality = v,
-        Field::AaMode            => s.aa_mode = v,
-        Field::SelfShadow        => s.enable_self_shadow = v,
-        Field::DlssQuality       => s.dlss_quality = v,
-        Field::ParticleTrail     => s.particle_trail_smoothness = v,
-        _ => {}
+    fn root(self) -> RegKey {
+        match self {
+            Hive::CurrentUser  => RegKey::predef(HKEY_CURRENT_USER),
+            Hive::LocalMachine => RegKey::predef(HKEY_LOCAL_MACHINE),
+        }
     }
-}
 
-fn get_f64(s: &GraphicsSettings, f: Field) -> f64 {
-    match f {
-        Field::RenderScale => s.render_scale,
-        _ => 0.0,
+    fn name(self) -> &'static str {
+        match self {
+            Hive::CurrentUser  => "HKEY_CURRENT_USER",
+            Hive::LocalMachine => "HKEY_LOCAL_MACHINE",
+        }
     }
-}
 
-fn set_f64(s: &mut GraphicsSettings, f: Field, v: f64) {
-    match f {
-        Field::RenderScale => s.render_scale = v,
-        _ => {}
+    fn short_name(self) -> &'static str {
+        match self {
+            Hive::CurrentUser  => "HKCU",
+            Hive::LocalMachine => "HKLM",
+        }
     }
 }
 
-fn get_bool(s: &GraphicsSettings, f: Field) -> bool {
-    match f {
-        Field::VSync => s.enable_vsync,
-        _ => false,
-    }
+/// Game clients whose settings `sync_clients` keeps in step. Everything
+/// else reads and writes the Global client's key.
+#[derive(Clone, Copy, PartialEq)]
+enum Client {
+    Global,
+    Cn,
 }
 
-fn set_bool(s: &mut GraphicsSettings, f: Field, v: bool) {
-    match f {
-        Field::VSync => s.enable_vsync = v,
-        _ => {}
+impl Client {
+    fn reg_path(self) -> &'static str {
+        match self {
+            Client::Global => REG_PATH,
+            Client::Cn     => CN_REG_PATH,
+        }
+    }
+
+    fn label(self, t: &L10n) -> &str {
+        match self {
+            Client::Global => &t.client_global,
+            Client::Cn     => &t.client_cn,
+        }
     }
 }
 
-// ---------------------------------------------------------------------------
-// App state
-// ---------------------------------------------------------------------------
+/// Copies one client's blob over the other's byte for byte.
+fn copy_client(from: Client, to: Client) -> io::Result<()> {
+    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+    let blob = hkcu.open_subkey(from.reg_path())?.get_raw_value(REG_VALUE)?;
+    let (key, _) = hkcu.create_subkey(to.reg_path())?;
+    key.set_raw_value(REG_VALUE, &blob)
+}
 
-struct App {
-    settings: GraphicsSettings,
-    defs: Vec<SettingDef>,
-    cursor: usize,
-    status: String,
-    lang: Lang,
+/// Copies a legacy value's blob to `REG_VALUE` byte for byte.
+fn migrate_legacy(name: &str) -> io::Result<()> {
+    let key = RegKey::predef(HKEY_CURRENT_USER).open_subkey_with_flags(REG_PATH, KEY_READ | KEY_WRITE)?;
+    let blob = key.get_raw_value(name)?;
+    key.set_raw_value(REG_VALUE, &blob)
 }
 
-impl App {
-    fn new(lang: Lang) -> Self {
-        let (settings, existed) = read_settings();
-        let t = l10n(lang);
-        let status = if existed {
-            String::new()
-        } else {
-            t.no_registry.into()
-        };
-        Self {
-            settings,
-            defs: setting_defs(),
-            cursor: 0,
-            status,
-            lang,
-        }
+/// Serializes settings exactly as they are stored; `pretty` only changes whitespace.
+fn settings_json(settings: &GraphicsSettings, pretty: bool) -> serde_json::Result<String> {
+    let json = if pretty {
+        serde_json::to_string_pretty(settings)?
+    } else {
+        serde_json::to_string(settings)?
+    };
+    Ok(json)
+}
+
+/// What a verified write did to the registry.
+struct SaveOutcome {
+    /// The game's key didn't exist yet (the game was never launched).
+    key_created: bool,
+    bytes_written: usize,
+}
+
+impl SaveOutcome {
+    fn message(&self, t: &L10n) -> String {
+        let text = if self.key_created { &t.saved_key_created } else { &t.saved_bytes };
+        t.fill(text, &[("n", &self.bytes_written)])
     }
+}
 
-    fn t(&self) -> &'static L10n {
-        l10n(self.lang)
+/// The REG_BINARY data a save writes: compact JSON in the game's key
+/// order, NUL-terminated like the game's own writes unless `append_nul`
+/// is off. Scripts from `--emit-script` embed the same bytes.
+fn registry_bytes(settings: &GraphicsSettings, append_nul: bool) -> serde_json::Result<Vec<u8>> {
+    let mut bytes = settings_json(settings, false)?.into_bytes();
+    if append_nul {
+        bytes.push(0);
+    }
+    Ok(bytes)
+}
+
+/// Writes the settings blob under `hive`; the game's own writes end in a
+/// NUL, which `append_nul` controls for troubleshooting. The value is read
+/// back through a freshly opened key to confirm it landed with the written
+/// length.
+fn write_settings(settings: &GraphicsSettings, append_nul: bool, hive: Hive) -> io::Result<SaveOutcome> {
+    let root = hive.root();
+    let (key, disposition) = root.create_subkey(REG_PATH)?;
+    let bytes = registry_bytes(settings, append_nul)?;
+    let bytes_written = bytes.len();
+    key.set_raw_value(REG_VALUE, &RegValue { vtype: REG_BINARY, bytes })?;
+
+    let check = root.open_subkey(REG_PATH)?.get_raw_value(REG_VALUE)?;
+    if check.bytes.len() != bytes_written {
+        return Err(io::Error::other(format!(
+            "read back {} bytes after writing {bytes_written}",
+            check.bytes.len()
+        )));
     }
+    Ok(SaveOutcome {
+        key_created: matches!(disposition, RegDisposition::REG_CREATED_NEW_KEY),
+        bytes_written,
+    })
+}
 
-    fn cycle(&mut self, delta: isize) {
-        let def = &self.defs[self.cursor];
-        let field = def.field;
-        match &def.kind {
-            SettingKind::SelectI64(opts) => {
-                let cur = get_i64(&self.settings, field);
-                let pos = opts.iter().position(|(_, v)| *v == cur).unwrap_or(0);
-                let next = (pos as isize + delta).rem_euclid(opts.len() as isize) as usize;
-                set_i64(&mut self.settings, field, opts[next].1);
-            }
-            SettingKind::SelectF64(opts) => {
-                let cur = get_f64(&self.settings, field);
-                let pos = opts
-                    .iter()
-                    .position(|(_, v)| (*v - cur).abs() < 0.001)
-                    .unwrap_or(0);
-                let next = (pos as isize + delta).rem_euclid(opts.len() as isize) as usize;
-                set_f64(&mut self.settings, field, opts[next].1);
-            }
-            SettingKind::Toggle => {
-                let cur = get_bool(&self.settings, field);
-                set_bool(&mut self.settings, field, !cur);
-            }
+/// Where the app reads and writes the settings value. Tests keep it in
+/// memory so saves never reach the registry.
+#[derive(Clone)]
+enum Store {
+    Registry,
+    /// One value per hive, in `Hive::ALL` order.
+    #[cfg(test)]
+    Memory(Values),
+}
+
+#[cfg(test)]
+type Values = std::sync::Arc<std::sync::Mutex<[Option<Vec<u8>>; 2]>>;
+
+impl Store {
+    fn read(&self) -> ReadResult {
+        match self {
+            Store::Registry => read_settings(),
+            #[cfg(test)]
+            Store::Memory(_) => Hive::ALL
+                .into_iter()
+                .map(|hive| self.read_hive(hive))
+                .find(|result| !matches!(result, ReadResult::Missing))
+                .unwrap_or(ReadResult::Missing),
         }
     }
 
-    fn save(&mut self) {
-        let t = self.t();
-        match write_settings(&self.settings) {
-            Ok(()) => self.status = t.saved.into(),
-            Err(e) => self.status = format!("{}: {e}", t.save_failed),
+    /// Reads `hive` only, whether or not the other one has a value.
+    fn read_hive(&self, hive: Hive) -> ReadResult {
+        match self {
+            Store::Registry => read_settings_in(hive, Client::Global),
+            #[cfg(test)]
+            Store::Memory(values) => match &values.lock().unwrap()[hive as usize] {
+                Some(bytes) => parse_value(bytes, hive),
+                None => ReadResult::Missing,
+            },
         }
     }
 
-    fn value_display(&self, idx: usize) -> String {
-        let def = &self.defs[idx];
-        let t = self.t();
-        match &def.kind {
-            SettingKind::SelectI64(opts) => {
-                let cur = get_i64(&self.settings, def.field);
-                opts.iter()
-                    .find(|(_, v)| *v == cur)
-                    .map(|(l, _)| l.to_string())
-                    .unwrap_or_else(|| cur.to_string())
-            }
-            SettingKind::SelectF64(opts) => {
-                let cur = get_f64(&self.settings, def.field);
-                opts.iter()
-                    .find(|(_, v)| (*v - cur).abs() < 0.001)
-                    .map(|(l, _)| l.to_string())
-                    .unwrap_or_else(|| format!("{cur:.1}"))
-            }
-            SettingKind::Toggle => {
-                if get_bool(&self.settings, def.field) {
-                    t.on.into()
-                } else {
-                    t.off.into()
-                }
+    fn write(&self, settings: &GraphicsSettings, append_nul: bool, hive: Hive) -> io::Result<SaveOutcome> {
+        match self {
+            Store::Registry => write_settings(settings, append_nul, hive),
+            #[cfg(test)]
+            Store::Memory(values) => {
+                let bytes = registry_bytes(settings, append_nul)?;
+                let bytes_written = bytes.len();
+                let key_created = values.lock().unwrap()[hive as usize].replace(bytes).is_none();
+                Ok(SaveOutcome { key_created, bytes_written })
             }
         }
     }
 }
 
 // ---------------------------------------------------------------------------
-// Language picker
+// JSON import
 // ---------------------------------------------------------------------------
 
-fn draw_lang_picker(frame: &mut Frame, cursor: usize) {
-    let area = frame.area();
-    let [_, center, _] = Layout::vertical([
-        Constraint::Fill(1),
-        Constraint::Length(9),
-        Constraint::Fill(1),
-    ])
-    .areas(area);
+/// Applies only the keys present in `overlay` on top of `base`. Keys whose
+/// value doesn't fit the field's type are skipped rather than failing the merge.
+fn merge_settings(base: GraphicsSettings, overlay: serde_json::Value) -> GraphicsSettings {
+    let serde_json::Value::Object(overlay) = overlay else {
+        return base;
+    };
+    let mut merged = base;
+    for (key, value) in overlay {
+        let Ok(serde_json::Value::Object(mut obj)) = serde_json::to_value(&merged) else {
+            break;
+        };
+        obj.insert(key, value);
+        if let Ok(next) = serde_json::from_value(serde_json::Value::Object(obj)) {
+            merged = next;
+        }
+    }
+    merged
+}
+
+/// Reads a settings file as a JSON value. Regedit exports are decoded from
+/// their settings value; other files that don't start with `{` are parsed as
+/// the sharable text format.
+fn read_json_value(path: &str) -> io::Result<serde_json::Value> {
+    let bytes = std::fs::read(path)?;
+    let invalid = |e: String| io::Error::new(io::ErrorKind::InvalidData, format!("{path}: {e}"));
+    let text = script::decode_text(&bytes).map_err(invalid)?;
+    let text = text.trim_end_matches('\0');
+    let value = if script::is_reg_file(text) {
+        from_reg_file(text)
+    } else if text.trim_start().starts_with('{') {
+        serde_json::from_str(text).map_err(|e| e.to_string())
+    } else {
+        from_sharable_text(text)
+    };
+    value.map_err(invalid)
+}
+
+/// The JSON inside a .reg file's settings value: `REG_VALUE` if present,
+/// else the first value with the same prefix, as exported from another
+/// client version.
+fn from_reg_file(text: &str) -> Result<serde_json::Value, String> {
+    let values = script::reg_binary_values(text)?;
+    let (_, bytes) = values
+        .iter()
+        .find(|(name, _)| name == REG_VALUE)
+        .or_else(|| values.iter().find(|(name, _)| name.starts_with(REG_VALUE_PREFIX)))
+        .ok_or_else(|| format!("no {REG_VALUE_PREFIX}* binary value"))?;
+    let json = std::str::from_utf8(bytes).map_err(|e| e.to_string())?;
+    serde_json::from_str(json.trim_end_matches('\0')).map_err(|e| e.to_string())
+}
+
+// ---------------------------------------------------------------------------
+// Sharable text format
+// ---------------------------------------------------------------------------
+//
+// One `Key=Value` line per setting, in game order:
+//
+//     # hsr-settings
+//     FPS=60
+//     EnableVSync=false
+//     RenderScale=1.2
+//
+// Keys are the game's own JSON key names. Values use JSON syntax, so strings
+// are quoted and unknown nested values round-trip as one-line JSON. Blank
+// lines, lines starting with `#` and whitespace around `=` are ignored.
+
+const SHARABLE_HEADER: &str = "# hsr-settings";
+
+fn to_sharable_text(s: &GraphicsSettings) -> serde_json::Result<String> {
+    let serde_json::Value::Object(obj) = serde_json::to_value(s)? else {
+        unreachable!("GraphicsSettings serializes to an object");
+    };
+    let mut out = format!("{SHARABLE_HEADER}\n");
+    for (key, value) in obj {
+        out.push_str(&format!("{key}={value}\n"));
+    }
+    Ok(out)
+}
+
+/// Parses the sharable text format into a JSON object. The result may be
+/// partial; apply it with `merge_settings` or deserialize it for a full set.
+fn from_sharable_text(text: &str) -> Result<serde_json::Value, String> {
+    let mut obj = serde_json::Map::new();
+    for (n, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            return Err(format!("line {}: expected Key=Value", n + 1));
+        };
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(format!("line {}: missing key", n + 1));
+        }
+        let value = serde_json::from_str(value.trim())
+            .map_err(|e| format!("line {}: {key}: {e}", n + 1))?;
+        obj.insert(key.to_string(), value);
+    }
+    Ok(serde_json::Value::Object(obj))
+}
+
+/// File formats offered by the export prompt.
+#[derive(Clone, Copy, PartialEq)]
+enum ExportFormat {
+    /// Indented, for reading and hand-editing.
+    Json,
+    /// One line without whitespace, for pasting where space is limited.
+    JsonCompact,
+    Text,
+}
+
+impl ExportFormat {
+    fn label(self) -> &'static str {
+        match self {
+            ExportFormat::Json        => "JSON",
+            ExportFormat::JsonCompact => "JSON, compact",
+            ExportFormat::Text        => "key=value",
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            ExportFormat::Json        => ExportFormat::JsonCompact,
+            ExportFormat::JsonCompact => ExportFormat::Text,
+            ExportFormat::Text        => ExportFormat::Json,
+        }
+    }
+
+    fn render(self, s: &GraphicsSettings) -> serde_json::Result<String> {
+        match self {
+            ExportFormat::Json        => settings_json(s, true),
+            ExportFormat::JsonCompact => settings_json(s, false),
+            ExportFormat::Text        => to_sharable_text(s),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Markdown summary (for pasting into chat)
+// ---------------------------------------------------------------------------
+
+/// A bullet list per group with localized labels and values, as shown in the
+/// list. Lists rather than tables, since Discord doesn't render tables.
+fn markdown_summary(defs: &[SettingDef], s: &GraphicsSettings, t: &L10n) -> String {
+    let mut out = format!("**{}**\n", t.title.trim());
+    for (i, def) in defs.iter().enumerate() {
+        if i == 0 || defs[i - 1].group() != def.group() {
+            out.push_str(&format!("\n*{}*\n", def.group().label(t)));
+        }
+        let value = def.format(get_value(s, def.field), t);
+        out.push_str(&format!("- {}: {value}\n", def.label(t)));
+    }
+    out
+}
+
+// ---------------------------------------------------------------------------
+// Setting field identifiers (no fragile index mapping)
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Field {
+    Fps,
+    VSync,
+    RenderScale,
+    ResolutionQuality,
+    ShadowQuality,
+    LightQuality,
+    CharacterQuality,
+    EnvDetailQuality,
+    ReflectionQuality,
+    SfxQuality,
+    BloomQuality,
+    AaMode,
+    SelfShadow,
+    DlssQuality,
+    ParticleTrail,
+}
+
+// ---------------------------------------------------------------------------
+// TUI setting descriptors
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Group {
+    /// Pinned settings, listed again above their usual groups.
+    Favorites,
+    Display,
+    Quality,
+    Advanced,
+}
+
+impl Group {
+    fn label(self, t: &L10n) -> &str {
+        match self {
+            Group::Favorites => &t.group_favorites,
+            Group::Display   => &t.group_display,
+            Group::Quality   => &t.group_quality,
+            Group::Advanced  => &t.group_advanced,
+        }
+    }
+}
+
+/// Finer than `Group`: what a setting affects, for tinting labels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Category {
+    Display,
+    Lighting,
+    Detail,
+    Effects,
+    AntiAliasing,
+}
+
+impl Category {
+    fn color(self, theme: &Theme) -> Color {
+        theme.categories[self as usize]
+    }
+}
+
+#[derive(Clone)]
+enum SettingKind {
+    SelectI64(Vec<(&'static str, i64)>),
+    SelectF64(Vec<(&'static str, f64)>),
+    Toggle,
+}
+
+impl SettingKind {
+    /// Number of selectable positions (a toggle has two: off, on).
+    fn len(&self) -> usize {
+        match self {
+            SettingKind::SelectI64(opts) => opts.len(),
+            SettingKind::SelectF64(opts) => opts.len(),
+            SettingKind::Toggle => 2,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct SettingDef {
+    field: Field,
+    kind: SettingKind,
+}
+
+impl SettingDef {
+    /// Whether `v` is on, for toggles and two-option Off/On selects.
+    fn switch_state(&self, v: Value) -> Option<bool> {
+        match (&self.kind, v) {
+            (SettingKind::Toggle, Value::Bool(on)) => Some(on),
+            (SettingKind::SelectI64(opts), Value::Int(cur)) if opts[..] == [("Off", 0), ("On", 1)] => Some(cur == 1),
+            _ => None,
+        }
+    }
+
+    /// Option index of the current value, or `None` for a custom value not in the list.
+    fn position(&self, s: &GraphicsSettings) -> Option<usize> {
+        match &self.kind {
+            SettingKind::SelectI64(opts) => {
+                let cur = get_i64(s, self.field);
+                opts.iter().position(|(_, v)| *v == cur)
+            }
+            SettingKind::SelectF64(opts) => {
+                let cur = get_f64(s, self.field);
+                opts.iter().position(|(_, v)| (*v - cur).abs() < 0.001)
+            }
+            SettingKind::Toggle => Some(get_bool(s, self.field) as usize),
+        }
+    }
+
+    /// Whether the game can run with `v`, listed or not: any positive FPS
+    /// cap, any scale inside the option range (as `:` takes them), and
+    /// one of the options for the other settings.
+    fn accepts(&self, v: Value) -> bool {
+        match (&self.kind, v) {
+            (SettingKind::SelectI64(_), Value::Int(cur)) if self.field == Field::Fps => cur > 0,
+            (SettingKind::SelectI64(opts), Value::Int(cur)) => opts.iter().any(|(_, v)| *v == cur),
+            (SettingKind::SelectF64(opts), Value::Float(cur)) => {
+                opts.first().zip(opts.last()).is_some_and(|(lo, hi)| (lo.1 - 0.001..=hi.1 + 0.001).contains(&cur))
+            }
+            (SettingKind::Toggle, Value::Bool(_)) => true,
+            _ => false,
+        }
+    }
+
+    /// `position`, or for a custom value the option closest to it.
+    fn nearest_position(&self, s: &GraphicsSettings) -> usize {
+        if let Some(pos) = self.position(s) {
+            return pos;
+        }
+        match &self.kind {
+            SettingKind::SelectI64(opts) => {
+                let cur = get_i64(s, self.field);
+                (0..opts.len()).min_by_key(|&i| (opts[i].1 - cur).abs()).unwrap_or(0)
+            }
+            SettingKind::SelectF64(opts) => {
+                let cur = get_f64(s, self.field);
+                (0..opts.len())
+                    .min_by(|&a, &b| (opts[a].1 - cur).abs().total_cmp(&(opts[b].1 - cur).abs()))
+                    .unwrap_or(0)
+            }
+            SettingKind::Toggle => 0,
+        }
+    }
+
+    /// Option label for `v`, falling back to the raw number for custom values.
+    fn format(&self, v: Value, t: &L10n) -> String {
+        match (&self.kind, v) {
+            (SettingKind::SelectI64(opts), Value::Int(cur)) => opts
+                .iter()
+                .find(|(_, v)| *v == cur)
+                .map(|(l, _)| match *l {
+                    "Off" => t.off.to_string(),
+                    "On" => t.on.to_string(),
+                    l => l.to_string(),
+                })
+                .unwrap_or_else(|| cur.to_string()),
+            (SettingKind::SelectF64(opts), Value::Float(cur)) => opts
+                .iter()
+                .find(|(_, v)| (*v - cur).abs() < 0.001)
+                .map(|(l, _)| localize_decimal(l, t))
+                .unwrap_or_else(|| localize_decimal(&format!("{cur:.1}"), t)),
+            (_, Value::Bool(true)) => t.on.as_str().into(),
+            (_, Value::Bool(false)) => t.off.as_str().into(),
+            (_, Value::Int(cur)) => cur.to_string(),
+            (_, Value::Float(cur)) => localize_decimal(&format!("{cur:.1}"), t),
+        }
+    }
+
+    fn set_position(&self, s: &mut GraphicsSettings, pos: usize) {
+        match &self.kind {
+            SettingKind::SelectI64(opts) => set_i64(s, self.field, opts[pos].1),
+            SettingKind::SelectF64(opts) => set_f64(s, self.field, opts[pos].1),
+            SettingKind::Toggle => set_bool(s, self.field, pos == 1),
+        }
+    }
+
+    fn label<'a>(&self, t: &'a L10n) -> &'a str {
+        match self.field {
+            Field::Fps               => &t.fps,
+            Field::VSync             => &t.vsync,
+            Field::RenderScale       => &t.render_scale,
+            Field::ResolutionQuality => &t.resolution_quality,
+            Field::ShadowQuality     => &t.shadow_quality,
+            Field::LightQuality      => &t.light_quality,
+            Field::CharacterQuality  => &t.character_quality,
+            Field::EnvDetailQuality  => &t.env_detail,
+            Field::ReflectionQuality => &t.reflection_quality,
+            Field::SfxQuality        => &t.sfx_quality,
+            Field::BloomQuality      => &t.bloom_quality,
+            Field::AaMode            => &t.anti_aliasing,
+            Field::SelfShadow        => &t.self_shadow,
+            Field::DlssQuality       => &t.dlss_quality,
+            Field::ParticleTrail     => &t.particle_trail,
+        }
+    }
+
+    fn group(&self) -> Group {
+        match self.field {
+            Field::Fps
+            | Field::VSync
+            | Field::RenderScale
+            | Field::ResolutionQuality => Group::Display,
+            Field::ShadowQuality
+            | Field::LightQuality
+            | Field::CharacterQuality
+            | Field::EnvDetailQuality
+            | Field::ReflectionQuality
+            | Field::SfxQuality
+            | Field::BloomQuality
+            | Field::ParticleTrail => Group::Quality,
+            Field::AaMode
+            | Field::SelfShadow
+            | Field::DlssQuality => Group::Advanced,
+        }
+    }
+
+    fn category(&self) -> Category {
+        match self.field {
+            Field::Fps
+            | Field::VSync
+            | Field::RenderScale
+            | Field::ResolutionQuality => Category::Display,
+            Field::ShadowQuality
+            | Field::LightQuality
+            | Field::SelfShadow => Category::Lighting,
+            Field::CharacterQuality
+            | Field::EnvDetailQuality => Category::Detail,
+            Field::ReflectionQuality
+            | Field::SfxQuality
+            | Field::BloomQuality
+            | Field::ParticleTrail => Category::Effects,
+            Field::AaMode
+            | Field::DlssQuality => Category::AntiAliasing,
+        }
+    }
+}
+
+/// What the text typed into an input modal will be used for.
+#[derive(Clone, Copy)]
+enum PromptKind {
+    MergeImport,
+    /// Tab cycles the format while the path is typed.
+    Export(ExportFormat),
+    /// `<setting> <value>`, parsed by `parse_assignment`.
+    SetValue,
+    /// A code from `code::encode_code`, usually pasted from chat.
+    PresetCode,
+}
+
+struct Prompt {
+    kind: PromptKind,
+    input: String,
+    /// Why the last submission was refused; the prompt stays open with it.
+    error: Option<String>,
+}
+
+impl Prompt {
+    fn label(&self, t: &L10n) -> String {
+        match self.kind {
+            PromptKind::MergeImport => t.prompt_merge.to_string(),
+            PromptKind::Export(format) => t.fill(&t.prompt_export, &[("format", &format.label())]),
+            PromptKind::SetValue => t.prompt_set_value.to_string(),
+            PromptKind::PresetCode => t.prompt_code.to_string(),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Modal dialogs
+// ---------------------------------------------------------------------------
+
+/// A dialog drawn centered over the dimmed screen. While one is open it
+/// receives all key input.
+enum Modal {
+    /// Y/Enter answers yes, N/Esc answers no.
+    Confirm(Confirm),
+    /// One of several answers, with the highlighted index: arrows and Enter,
+    /// or an answer's shortcut key. Esc closes without answering.
+    Choice(ChoiceKind, usize),
+    /// A line of text: Enter submits, Esc closes.
+    Input(Prompt),
+    /// The preferences screen, with the selected row.
+    Preferences(usize),
+}
+
+/// Yes/no questions.
+#[derive(Clone, Copy)]
+enum Confirm {
+    /// Offered after picking a manual FPS cap while VSync is on.
+    DisableVsync,
+    /// Leaving the JSON editor with unapplied edits.
+    DiscardJsonEdit,
+    /// Reviewing the pending changes before S writes them.
+    SaveChanges,
+    /// Shift+R: every setting back to the game default.
+    ResetAll,
+    /// At startup, when only a legacy value name holds settings.
+    MigrateLegacy,
+    /// F5 with unsaved changes, which the reload discards.
+    Reload,
+}
+
+impl Confirm {
+    fn question(self, t: &L10n) -> &str {
+        match self {
+            Confirm::DisableVsync    => &t.confirm_disable_vsync,
+            Confirm::DiscardJsonEdit => &t.confirm_discard_json,
+            Confirm::SaveChanges     => &t.confirm_save,
+            Confirm::ResetAll        => &t.confirm_reset_all,
+            Confirm::MigrateLegacy   => &t.confirm_migrate_legacy,
+            Confirm::Reload          => &t.confirm_reload,
+        }
+    }
+
+    /// Key and label of the yes and no buttons.
+    fn buttons(self, t: &L10n) -> [(&str, &str); 2] {
+        match self {
+            Confirm::SaveChanges => [("Enter", &t.hint_save), ("Esc", &t.hint_cancel)],
+            _ => [("Y", &t.hint_yes), ("N", &t.hint_no)],
+        }
+    }
+}
+
+/// Multiple-choice questions.
+#[derive(Clone, Copy)]
+enum ChoiceKind {
+    /// Quitting with unsaved changes: save and quit, discard, or cancel.
+    Quit,
+    /// Level 1-5 to apply to every standard quality field.
+    QualityLevel,
+    Theme,
+    Language,
+    /// Which client's value to copy over the other's; the dialog lists
+    /// `App::client_values`' differences.
+    SyncClients,
+    /// The registry value changed since it was loaded: reload and merge,
+    /// overwrite, or cancel. The dialog lists `App::changed_outside`.
+    ChangedOutside,
+}
+
+impl ChoiceKind {
+    fn question(self, t: &L10n) -> &str {
+        match self {
+            ChoiceKind::Quit => &t.quit_unsaved,
+            ChoiceKind::QualityLevel => &t.quality_level_question,
+            ChoiceKind::Theme => &t.choose_theme,
+            ChoiceKind::Language => &t.choose_language,
+            ChoiceKind::SyncClients => &t.sync_clients_question,
+            ChoiceKind::ChangedOutside => &t.external_change_question,
+        }
+    }
+
+    /// Shortcut key and label of each answer, in display order.
+    fn options(self, t: &L10n) -> Vec<(char, &str)> {
+        match self {
+            ChoiceKind::Quit => vec![('s', &t.quit_save), ('d', &t.quit_discard), ('c', &t.quit_cancel)],
+            ChoiceKind::QualityLevel => vec![('1', "1"), ('2', "2"), ('3', "3"), ('4', "4"), ('5', "5")],
+            ChoiceKind::Theme => vec![
+                ('d', &t.theme_dark),
+                ('l', &t.theme_light),
+                ('s', &t.theme_solarized),
+                ('h', &t.theme_high_contrast),
+            ],
+            ChoiceKind::Language => ('1'..='9').zip(Lang::all()).map(|(key, lang)| (key, lang.name())).collect(),
+            ChoiceKind::SyncClients => vec![('g', &t.client_global), ('c', &t.client_cn), ('k', &t.sync_keep_both)],
+            ChoiceKind::ChangedOutside => vec![('m', &t.external_merge), ('o', &t.external_overwrite), ('c', &t.hint_cancel)],
+        }
+    }
+}
+
+/// Autosave intervals the preferences screen cycles through, in seconds.
+const AUTOSAVE_CHOICES: [u64; 5] = [0, 30, 60, 120, 300];
+
+/// Rows of the preferences screen, each backed by a config key and saved
+/// as soon as it changes.
+#[derive(Clone, Copy, PartialEq)]
+enum Pref {
+    Language,
+    SkipLanguagePicker,
+    Theme,
+    CategoryColors,
+    TierNames,
+    WrapNavigation,
+    ConfirmSaves,
+    VsyncAutofix,
+    SyncClients,
+    RowSpacing,
+    Autosave,
+}
+
+impl Pref {
+    const ALL: [Pref; 11] = [
+        Pref::Language,
+        Pref::SkipLanguagePicker,
+        Pref::Theme,
+        Pref::CategoryColors,
+        Pref::TierNames,
+        Pref::WrapNavigation,
+        Pref::ConfirmSaves,
+        Pref::VsyncAutofix,
+        Pref::SyncClients,
+        Pref::RowSpacing,
+        Pref::Autosave,
+    ];
+
+    fn label(self, t: &L10n) -> &str {
+        match self {
+            Pref::Language           => &t.pref_language,
+            Pref::SkipLanguagePicker => &t.pref_skip_picker,
+            Pref::Theme              => &t.pref_theme,
+            Pref::CategoryColors     => &t.pref_category_colors,
+            Pref::TierNames          => &t.pref_tier_names,
+            Pref::WrapNavigation     => &t.pref_wrap,
+            Pref::ConfirmSaves       => &t.pref_confirm_saves,
+            Pref::VsyncAutofix       => &t.pref_vsync_autofix,
+            Pref::SyncClients        => &t.pref_sync_clients,
+            Pref::RowSpacing         => &t.pref_row_spacing,
+            Pref::Autosave           => &t.pref_autosave,
+        }
+    }
+}
+
+/// A raw JSON editing session over the whole settings blob.
+struct JsonEdit {
+    area: TextArea,
+    /// Text the session started from, to detect edits on Esc.
+    initial: String,
+    /// Parse error from the last apply attempt.
+    error: Option<String>,
+}
+
+/// A visible line in the settings list: a group header or a setting (index into `defs`).
+#[derive(Clone, Copy, PartialEq)]
+enum Row {
+    Header(Group),
+    Setting(usize),
+}
+
+/// The configured FPS caps, sorted and without duplicates, or
+/// `FPS_OPTIONS` when none are set.
+fn fps_options(config: &Config) -> Vec<i64> {
+    let mut caps: Vec<i64> = config.fps_options.iter().flatten().copied().filter(|&v| v > 0).collect();
+    caps.sort_unstable();
+    caps.dedup();
+    if caps.is_empty() { FPS_OPTIONS.to_vec() } else { caps }
+}
+
+/// The configured refresh rate, or else the primary display's.
+fn refresh_rate(config: &Config) -> Option<i64> {
+    config.refresh_rate.filter(|&hz| hz > 0).map(i64::from).or_else(hardware::refresh_rate)
+}
+
+/// Defs of one group must stay contiguous; headers are inserted at group boundaries.
+fn setting_defs(fps: &[i64]) -> Vec<SettingDef> {
+    let quality: Vec<(&str, i64)> = (1..=5).map(|i| (leak_str(i.to_string()), i)).collect();
+
+    vec![
+        SettingDef { field: Field::Fps,               kind: SettingKind::SelectI64(
+            fps.iter().map(|&v| (leak_str(v.to_string()), v)).collect(),
+        )},
+        SettingDef { field: Field::VSync,             kind: SettingKind::Toggle },
+        SettingDef { field: Field::RenderScale,       kind: SettingKind::SelectF64(
+            (6..=20).step_by(2).map(|v| { let f = v as f64 / 10.0; (leak_str(format!("{f:.1}")), f) }).collect(),
+        )},
+        SettingDef { field: Field::ResolutionQuality, kind: SettingKind::SelectI64(quality.clone()) },
+        SettingDef { field: Field::ShadowQuality,     kind: SettingKind::SelectI64(quality.clone()) },
+        SettingDef { field: Field::LightQuality,      kind: SettingKind::SelectI64(quality.clone()) },
+        SettingDef { field: Field::CharacterQuality,  kind: SettingKind::SelectI64(quality.clone()) },
+        SettingDef { field: Field::EnvDetailQuality,  kind: SettingKind::SelectI64(quality.clone()) },
+        SettingDef { field: Field::ReflectionQuality, kind: SettingKind::SelectI64(quality.clone()) },
+        SettingDef { field: Field::SfxQuality,        kind: SettingKind::SelectI64(quality.clone()) },
+        SettingDef { field: Field::BloomQuality,      kind: SettingKind::SelectI64(quality.clone()) },
+        SettingDef { field: Field::ParticleTrail,     kind: SettingKind::SelectI64(quality) },
+        SettingDef { field: Field::AaMode,            kind: SettingKind::SelectI64(vec![("Off", 0), ("On", 1)]) },
+        SettingDef { field: Field::SelfShadow,        kind: SettingKind::SelectI64(vec![("Off", 0), ("On", 1)]) },
+        SettingDef { field: Field::DlssQuality,       kind: SettingKind::SelectI64(
+            std::iter::once(("Off", 0i64)).chain((1..=5).map(|i| (leak_str(i.to_string()), i))).collect(),
+        )},
+    ]
+}
+
+/// Swaps the decimal point of a formatted number for the language's separator.
+/// Display only: JSON and the registry always use a dot.
+fn localize_decimal(s: &str, t: &L10n) -> String {
+    s.replace('.', &t.decimal_separator)
+}
+
+fn leak_str(s: String) -> &'static str {
+    Box::leak(s.into_boxed_str())
+}
+
+// ---------------------------------------------------------------------------
+// Field accessors
+// ---------------------------------------------------------------------------
+
+/// A field's value independent of its storage type.
+#[derive(Debug, Clone, Copy)]
+enum Value {
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+}
+
+impl Value {
+    /// Floats compare with the same tolerance the option lists use.
+    fn same(self, other: Value) -> bool {
+        match (self, other) {
+            (Value::Int(a), Value::Int(b)) => a == b,
+            (Value::Float(a), Value::Float(b)) => (a - b).abs() < 0.001,
+            (Value::Bool(a), Value::Bool(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+fn set_value(s: &mut GraphicsSettings, f: Field, v: Value) {
+    match v {
+        Value::Int(v) => set_i64(s, f, v),
+        Value::Float(v) => set_f64(s, f, v),
+        Value::Bool(v) => set_bool(s, f, v),
+    }
+}
+
+/// Key of the field in the stored JSON (mirrors the serde renames on `GraphicsSettings`).
+fn json_key(f: Field) -> &'static str {
+    match f {
+        Field::Fps               => "FPS",
+        Field::VSync             => "EnableVSync",
+        Field::RenderScale       => "RenderScale",
+        Field::ResolutionQuality => "ResolutionQuality",
+        Field::ShadowQuality     => "ShadowQuality",
+        Field::LightQuality      => "LightQuality",
+        Field::CharacterQuality  => "CharacterQuality",
+        Field::EnvDetailQuality  => "EnvDetailQuality",
+        Field::ReflectionQuality => "ReflectionQuality",
+        Field::SfxQuality        => "SFXQuality",
+        Field::BloomQuality      => "BloomQuality",
+        Field::AaMode            => "AAMode",
+        Field::SelfShadow        => "EnableSelfShadow",
+        Field::DlssQuality       => "DlssQuality",
+        Field::ParticleTrail     => "ParticleTrailSmoothness",
+    }
+}
+
+fn get_value(s: &GraphicsSettings, f: Field) -> Value {
+    match f {
+        Field::VSync       => Value::Bool(get_bool(s, f)),
+        Field::RenderScale => Value::Float(get_f64(s, f)),
+        _                  => Value::Int(get_i64(s, f)),
+    }
+}
+
+/// Values commonly suggested by community guides as a good balance of
+/// quality and performance. Fields without a clear consensus return `None`.
+fn recommended_value(f: Field) -> Option<Value> {
+    match f {
+        Field::Fps               => Some(Value::Int(60)),
+        Field::VSync             => Some(Value::Bool(false)),
+        Field::RenderScale       => Some(Value::Float(1.0)),
+        Field::ShadowQuality     => Some(Value::Int(3)),
+        Field::LightQuality      => Some(Value::Int(4)),
+        Field::ReflectionQuality => Some(Value::Int(3)),
+        Field::SfxQuality        => Some(Value::Int(3)),
+        Field::BloomQuality      => Some(Value::Int(3)),
+        Field::AaMode            => Some(Value::Int(1)),
+        Field::SelfShadow        => Some(Value::Int(1)),
+        _ => None,
+    }
+}
+
+fn get_i64(s: &GraphicsSettings, f: Field) -> i64 {
+    match f {
+        Field::Fps               => s.fps,
+        Field::ResolutionQuality => s.resolution_quality,
+        Field::ShadowQuality     => s.shadow_quality,
+        Field::LightQuality      => s.light_quality,
+        Field::CharacterQuality  => s.character_quality,
+        Field::EnvDetailQuality  => s.env_detail_quality,
+        Field::ReflectionQuality => s.reflection_quality,
+        Field::SfxQuality        => s.sfx_quality,
+        Field::BloomQuality      => s.bloom_quality,
+        Field::AaMode            => s.aa_mode,
+        Field::SelfShadow        => s.enable_self_shadow,
+        Field::DlssQuality       => s.dlss_quality,
+        Field::ParticleTrail     => s.particle_trail_smoothness,
+        _ => 0,
+    }
+}
+
+fn set_i64(s: &mut GraphicsSettings, f: Field, v: i64) {
+    match f {
+        Field::Fps               => s.fps = v,
+        Field::ResolutionQuality => s.resolution_quality = v,
+        Field::ShadowQuality     => s.shadow_quality = v,
+        Field::LightQuality      => s.light_quality = v,
+        Field::CharacterQuality  => s.character_quality = v,
+        Field::EnvDetailQuality  => s.env_detail_quality = v,
+        Field::ReflectionQuality => s.reflection_quality = v,
+        Field::SfxQuality        => s.sfx_quality = v,
+        Field::BloomQuality      => s.bloom_quality = v,
+        Field::AaMode            => s.aa_mode = v,
+        Field::SelfShadow        => s.enable_self_shadow = v,
+        Field::DlssQuality       => s.dlss_quality = v,
+        Field::ParticleTrail     => s.particle_trail_smoothness = v,
+        _ => {}
+    }
+}
+
+fn get_f64(s: &GraphicsSettings, f: Field) -> f64 {
+    match f {
+        Field::RenderScale => s.render_scale,
+        _ => 0.0,
+    }
+}
+
+fn set_f64(s: &mut GraphicsSettings, f: Field, v: f64) {
+    if f == Field::RenderScale {
+        s.render_scale = v;
+    }
+}
+
+fn get_bool(s: &GraphicsSettings, f: Field) -> bool {
+    match f {
+        Field::VSync => s.enable_vsync,
+        _ => false,
+    }
+}
+
+fn set_bool(s: &mut GraphicsSettings, f: Field, v: bool) {
+    if f == Field::VSync {
+        s.enable_vsync = v;
+    }
+}
+
+fn field_differs(a: &GraphicsSettings, b: &GraphicsSettings, f: Field) -> bool {
+    !get_value(a, f).same(get_value(b, f))
+}
+
+/// Defs whose values differ between `a` and `b`, in list order.
+fn diff_settings<'a>(
+    defs: &'a [SettingDef],
+    a: &GraphicsSettings,
+    b: &GraphicsSettings,
+) -> Vec<&'a SettingDef> {
+    defs.iter().filter(|d| field_differs(a, b, d.field)).collect()
+}
+
+// ---------------------------------------------------------------------------
+// Presets
+// ---------------------------------------------------------------------------
+
+/// Whether a setting offers exactly the standard 1-5 quality range.
+fn is_quality_level(def: &SettingDef) -> bool {
+    matches!(&def.kind, SettingKind::SelectI64(opts) if opts.iter().map(|(_, v)| *v).eq(1..=5))
+}
+
+/// Overall quality levels. FPS, VSync, AA and DLSS are left alone: they
+/// depend on the monitor and GPU vendor more than on the performance budget.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Preset {
+    Low,
+    Medium,
+    High,
+}
+
+impl Preset {
+    fn label(self, t: &L10n) -> &str {
+        match self {
+            Preset::Low    => &t.preset_low,
+            Preset::Medium => &t.preset_medium,
+            Preset::High   => &t.preset_high,
+        }
+    }
+
+    fn quality(self) -> i64 {
+        match self {
+            Preset::Low    => 2,
+            Preset::Medium => 3,
+            Preset::High   => 4,
+        }
+    }
+
+    fn render_scale(self) -> f64 {
+        match self {
+            Preset::Low => 0.8,
+            _ => 1.0,
+        }
+    }
+
+    const ALL: [Preset; 3] = [Preset::Low, Preset::Medium, Preset::High];
+
+    fn apply(self, defs: &[SettingDef], s: &GraphicsSettings) -> GraphicsSettings {
+        let mut next = s.clone();
+        for def in defs.iter().filter(|d| is_quality_level(d)) {
+            set_i64(&mut next, def.field, self.quality());
+        }
+        next.render_scale = self.render_scale();
+        next
+    }
+}
+
+const GIB: u64 = 1 << 30;
+
+/// A deliberately conservative guess from dedicated VRAM alone; unknown
+/// hardware gets Medium.
+fn suggest_preset(gpu: Option<&hardware::Gpu>) -> Preset {
+    match gpu {
+        Some(gpu) if gpu.vram >= 8 * GIB => Preset::High,
+        Some(gpu) if gpu.vram >= 4 * GIB => Preset::Medium,
+        Some(_) => Preset::Low,
+        None => Preset::Medium,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Typed assignments ("fps 120", "render scale 1.2")
+// ---------------------------------------------------------------------------
+
+/// Lowercase with spaces, `_` and `-` removed, so "Render Scale",
+/// "render_scale" and "RenderScale" all compare equal.
+fn normalize_query(s: &str) -> String {
+    s.chars().filter(|c| !matches!(c, ' ' | '_' | '-')).flat_map(char::to_lowercase).collect()
+}
+
+/// Splits `input` into a setting and a value literal and checks the value
+/// against the setting's options. The setting is matched by prefix against
+/// both its localized label and its JSON key; an exact match wins over
+/// prefix matches.
+fn parse_assignment(defs: &[SettingDef], input: &str, t: &L10n) -> Result<(usize, Value), String> {
+    let Some((query, literal)) = input.trim().rsplit_once(char::is_whitespace) else {
+        return Err(t.set_value_usage.to_string());
+    };
+    let query = normalize_query(query);
+    let names = |i: usize| [normalize_query(defs[i].label(t)), normalize_query(json_key(defs[i].field))];
+    let exact: Vec<usize> = (0..defs.len()).filter(|&i| names(i).contains(&query)).collect();
+    let matches = if exact.is_empty() {
+        (0..defs.len()).filter(|&i| names(i).iter().any(|n| n.starts_with(&query))).collect()
+    } else {
+        exact
+    };
+    let idx = match matches.as_slice() {
+        [] => return Err(t.fill(&t.no_such_setting, &[("query", &input.trim())])),
+        [idx] => *idx,
+        _ => {
+            let labels: Vec<&str> = matches.iter().map(|&i| defs[i].label(t)).collect();
+            return Err(t.fill(&t.ambiguous_setting, &[("names", &labels.join(", "))]));
+        }
+    };
+
+    let def = &defs[idx];
+    let literal = literal.to_lowercase();
+    let value = match &def.kind {
+        SettingKind::Toggle => match literal.as_str() {
+            "on" | "true" | "1" => Some(Value::Bool(true)),
+            "off" | "false" | "0" => Some(Value::Bool(false)),
+            l if l == t.on.to_lowercase() => Some(Value::Bool(true)),
+            l if l == t.off.to_lowercase() => Some(Value::Bool(false)),
+            _ => None,
+        },
+        SettingKind::SelectI64(opts) => opts
+            .iter()
+            .find(|(label, v)| label.to_lowercase() == literal || v.to_string() == literal)
+            .map(|(_, v)| Value::Int(*v)),
+        // Any number inside the option range, like the game's own slider.
+        SettingKind::SelectF64(_) => literal
+            .replace(',', ".")
+            .parse::<f64>()
+            .ok()
+            .map(Value::Float)
+            .filter(|&v| def.accepts(v)),
+    };
+    let options = match &def.kind {
+        SettingKind::Toggle => format!("{}/{}", t.on, t.off),
+        SettingKind::SelectI64(opts) => opts.iter().map(|(l, _)| *l).collect::<Vec<_>>().join(", "),
+        SettingKind::SelectF64(opts) => match (opts.first(), opts.last()) {
+            (Some(lo), Some(hi)) => format!("{}-{}", localize_decimal(lo.0, t), localize_decimal(hi.0, t)),
+            _ => String::new(),
+        },
+    };
+    value.map(|v| (idx, v)).ok_or_else(|| {
+        t.fill(&t.invalid_value, &[("setting", &def.label(t)), ("options", &options)])
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Validation
+// ---------------------------------------------------------------------------
+
+/// Value combinations the game rejects or silently overrides.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Conflict {
+    /// DLSS drives the internal resolution itself, so render scale must stay at 1.0.
+    DlssRenderScale,
+    /// DLSS replaces the regular anti-aliasing pass, so AA must be off.
+    DlssAntiAliasing,
+    /// With VSync on the frame rate can't exceed the monitor's refresh
+    /// rate, given in Hz, so a cap above it has no effect. Lower caps
+    /// still apply.
+    VsyncFpsCap(i64),
+}
+
+impl Conflict {
+    /// Warnings describe combinations the game accepts but that don't do what
+    /// the user likely expects; they never block edits in safe mode.
+    fn is_warning(self) -> bool {
+        matches!(self, Conflict::VsyncFpsCap(_))
+    }
+
+    fn message(self, t: &L10n) -> &str {
+        match self {
+            Conflict::DlssRenderScale  => &t.conflict_dlss_render_scale,
+            Conflict::DlssAntiAliasing => &t.conflict_dlss_aa,
+            Conflict::VsyncFpsCap(_)   => &t.conflict_vsync_fps,
+        }
+    }
+
+    /// The setting the game overrides in this combination, and the value
+    /// it likely runs with instead.
+    fn runs_as(self) -> (Field, Value) {
+        match self {
+            Conflict::DlssRenderScale  => (Field::RenderScale, Value::Float(1.0)),
+            Conflict::DlssAntiAliasing => (Field::AaMode, Value::Int(0)),
+            Conflict::VsyncFpsCap(hz)  => (Field::Fps, Value::Int(hz)),
+        }
+    }
+}
+
+/// `refresh_rate` is the monitor's, from `refresh_rate()`; VSync caps
+/// aren't checked when it is unknown.
+fn validate(s: &GraphicsSettings, refresh_rate: Option<i64>) -> Vec<Conflict> {
+    let mut conflicts = Vec::new();
+    if let Some(hz) = refresh_rate
+        && s.enable_vsync
+        && s.fps > hz
+    {
+        conflicts.push(Conflict::VsyncFpsCap(hz));
+    }
+    if s.dlss_quality > 0 {
+        if (s.render_scale - 1.0).abs() >= 0.001 {
+            conflicts.push(Conflict::DlssRenderScale);
+        }
+        if s.aa_mode != 0 {
+            conflicts.push(Conflict::DlssAntiAliasing);
+        }
+    }
+    conflicts
+}
+
+/// What the game likely uses for `field` when another setting overrides
+/// it, e.g. render scale 1.0 with DLSS on; `None` when it runs as stored.
+fn runtime_value(s: &GraphicsSettings, field: Field, refresh_rate: Option<i64>) -> Option<Value> {
+    validate(s, refresh_rate).into_iter().map(Conflict::runs_as).find(|&(f, _)| f == field).map(|(_, v)| v)
+}
+
+/// Number of conflicts that safe mode refuses to add. Only warnings
+/// depend on the refresh rate, so it isn't needed here.
+fn blocking_conflicts(s: &GraphicsSettings) -> usize {
+    validate(s, None).iter().filter(|c| !c.is_warning()).count()
+}
+
+// ---------------------------------------------------------------------------
+// Key bindings
+// ---------------------------------------------------------------------------
+
+/// Something a key does in the settings list. The main loop dispatches
+/// through `KEYMAP` and the help overlay is generated from it, so the two
+/// can't drift apart.
+#[derive(Clone, Copy, PartialEq)]
+enum Action {
+    MoveUp,
+    MoveDown,
+    /// Folds a group header or flips a toggle.
+    Activate,
+    SwitchColumn,
+    LeftColumn,
+    RightColumn,
+    ScrollPreviewUp,
+    ScrollPreviewDown,
+    CycleNext,
+    CyclePrev,
+    /// Shift+←/→: `COARSE_STEP` options at a time.
+    CycleNextCoarse,
+    CyclePrevCoarse,
+    /// Ctrl+←/→: the last or first option.
+    CycleMax,
+    CycleMin,
+    /// Marks a row for batch edits with `CycleNext`/`CyclePrev`.
+    ToggleMark,
+    /// Sets the selected row to the tier with this number. `KEYMAP` binds
+    /// it as `SetTier(0)`; `Keymap::action_for` puts in the digit pressed.
+    SetTier(u8),
+    /// Opens the 1-5 chooser for every quality field at once.
+    SetAllQuality,
+    ApplySuggested,
+    ResetField,
+    ResetAll,
+    RevertField,
+    RevertAll,
+    Undo,
+    Redo,
+    ToggleSafeMode,
+    ToggleLock,
+    ToggleFavorite,
+    EditJson,
+    /// `:` opens a prompt that sets a value by name, e.g. "fps 120".
+    SetValue,
+    Save,
+    SaveAndQuit,
+    MergeImport,
+    Reload,
+    Export,
+    CopyValue,
+    CopyJson,
+    CopyMarkdown,
+    CopyCode,
+    ApplyCode,
+    ToggleRecommended,
+    ToggleDescription,
+    ToggleDefaults,
+    ToggleJsonKeys,
+    FavoritesOnly,
+    TogglePreview,
+    ToggleLog,
+    ChooseTheme,
+    ChooseLanguage,
+    ToggleSpacing,
+    ToggleTierNames,
+    Preferences,
+    /// Opens the newest toast's full text, for messages cut off at the edge.
+    ShowMessage,
+    Help,
+    /// Esc: dismisses a sticky toast, closes the preview, or quits.
+    Back,
+    Quit,
+}
+
+/// Help overlay sections.
+#[derive(Clone, Copy, PartialEq)]
+enum HelpSection {
+    Navigation,
+    Editing,
+    Files,
+    Application,
+}
+
+impl HelpSection {
+    const ALL: [HelpSection; 4] =
+        [HelpSection::Navigation, HelpSection::Editing, HelpSection::Files, HelpSection::Application];
+
+    fn label(self, t: &L10n) -> &str {
+        match self {
+            HelpSection::Navigation  => &t.help_navigation,
+            HelpSection::Editing     => &t.help_editing,
+            HelpSection::Files       => &t.help_files,
+            HelpSection::Application => &t.help_application,
+        }
+    }
+}
+
+impl Action {
+    fn section(self) -> HelpSection {
+        match self {
+            Action::MoveUp
+            | Action::MoveDown
+            | Action::Activate
+            | Action::SwitchColumn
+            | Action::LeftColumn
+            | Action::RightColumn
+            | Action::ScrollPreviewUp
+            | Action::ScrollPreviewDown => HelpSection::Navigation,
+            Action::CycleNext
+            | Action::CyclePrev
+            | Action::CycleNextCoarse
+            | Action::CyclePrevCoarse
+            | Action::CycleMax
+            | Action::CycleMin
+            | Action::ToggleMark
+            | Action::SetTier(_)
+            | Action::SetAllQuality
+            | Action::ApplySuggested
+            | Action::ResetField
+            | Action::ResetAll
+            | Action::RevertField
+            | Action::RevertAll
+            | Action::Undo
+            | Action::Redo
+            | Action::ToggleSafeMode
+            | Action::ToggleLock
+            | Action::ToggleFavorite
+            | Action::EditJson
+            | Action::SetValue => HelpSection::Editing,
+            Action::Save
+            | Action::SaveAndQuit
+            | Action::MergeImport
+            | Action::Reload
+            | Action::Export
+            | Action::CopyValue
+            | Action::CopyJson
+            | Action::CopyMarkdown
+            | Action::CopyCode
+            | Action::ApplyCode => HelpSection::Files,
+            Action::ToggleRecommended
+            | Action::ToggleDescription
+            | Action::ToggleDefaults
+            | Action::ToggleJsonKeys
+            | Action::FavoritesOnly
+            | Action::TogglePreview
+            | Action::ToggleLog
+            | Action::ChooseTheme
+            | Action::ChooseLanguage
+            | Action::ToggleSpacing
+            | Action::ToggleTierNames
+            | Action::Preferences
+            | Action::ShowMessage
+            | Action::Help
+            | Action::Back
+            | Action::Quit => HelpSection::Application,
+        }
+    }
+
+    /// The action's name in the config's `[keybindings]`.
+    fn name(self) -> &'static str {
+        match self {
+            Action::MoveUp            => "move-up",
+            Action::MoveDown          => "move-down",
+            Action::Activate          => "activate",
+            Action::SwitchColumn      => "switch-column",
+            Action::LeftColumn        => "left-column",
+            Action::RightColumn       => "right-column",
+            Action::ScrollPreviewUp   => "scroll-preview-up",
+            Action::ScrollPreviewDown => "scroll-preview-down",
+            Action::CycleNext         => "cycle-next",
+            Action::CyclePrev         => "cycle-prev",
+            Action::CycleNextCoarse   => "cycle-next-coarse",
+            Action::CyclePrevCoarse   => "cycle-prev-coarse",
+            Action::CycleMax          => "cycle-max",
+            Action::CycleMin          => "cycle-min",
+            Action::ToggleMark        => "toggle-mark",
+            Action::SetTier(_)        => "set-tier",
+            Action::SetAllQuality     => "set-all-quality",
+            Action::ApplySuggested    => "apply-suggested",
+            Action::ResetField        => "reset-field",
+            Action::ResetAll          => "reset-all",
+            Action::RevertField       => "revert-field",
+            Action::RevertAll         => "revert-all",
+            Action::Undo              => "undo",
+            Action::Redo              => "redo",
+            Action::ToggleSafeMode    => "toggle-safe-mode",
+            Action::ToggleLock        => "toggle-lock",
+            Action::ToggleFavorite    => "toggle-favorite",
+            Action::EditJson          => "edit-json",
+            Action::SetValue          => "set-value",
+            Action::Save              => "save",
+            Action::SaveAndQuit       => "save-and-quit",
+            Action::MergeImport       => "merge-import",
+            Action::Reload            => "reload",
+            Action::Export            => "export",
+            Action::CopyValue         => "copy-value",
+            Action::CopyJson          => "copy-json",
+            Action::CopyMarkdown      => "copy-markdown",
+            Action::CopyCode          => "copy-code",
+            Action::ApplyCode         => "apply-code",
+            Action::ToggleRecommended => "toggle-recommended",
+            Action::ToggleDescription => "toggle-description",
+            Action::ToggleDefaults    => "toggle-defaults",
+            Action::ToggleJsonKeys    => "toggle-json-keys",
+            Action::FavoritesOnly     => "favorites-only",
+            Action::TogglePreview     => "toggle-preview",
+            Action::ToggleLog         => "toggle-log",
+            Action::ChooseTheme       => "choose-theme",
+            Action::ChooseLanguage    => "choose-language",
+            Action::ToggleSpacing     => "toggle-spacing",
+            Action::ToggleTierNames   => "toggle-tier-names",
+            Action::Preferences       => "preferences",
+            Action::ShowMessage       => "show-message",
+            Action::Help              => "help",
+            Action::Back              => "back",
+            Action::Quit              => "quit",
+        }
+    }
+
+    fn description(self, t: &L10n) -> &str {
+        match self {
+            Action::MoveUp            => &t.help_move_up,
+            Action::MoveDown          => &t.help_move_down,
+            Action::Activate          => &t.help_activate,
+            Action::ToggleMark        => &t.help_mark,
+            Action::SwitchColumn      => &t.help_switch_column,
+            Action::LeftColumn        => &t.help_left_column,
+            Action::RightColumn       => &t.help_right_column,
+            Action::ScrollPreviewUp   => &t.help_scroll_up,
+            Action::ScrollPreviewDown => &t.help_scroll_down,
+            Action::CycleNext         => &t.help_cycle_next,
+            Action::CyclePrev         => &t.help_cycle_prev,
+            Action::CycleNextCoarse   => &t.help_cycle_next_coarse,
+            Action::CyclePrevCoarse   => &t.help_cycle_prev_coarse,
+            Action::CycleMax          => &t.help_cycle_max,
+            Action::CycleMin          => &t.help_cycle_min,
+            Action::SetTier(_)        => &t.help_set_tier,
+            Action::SetAllQuality     => &t.help_set_all_quality,
+            Action::ApplySuggested    => &t.help_apply_suggested,
+            Action::ResetField        => &t.help_reset_field,
+            Action::ResetAll          => &t.help_reset_all,
+            Action::RevertField       => &t.help_revert_field,
+            Action::RevertAll         => &t.help_revert_all,
+            Action::Undo              => &t.help_undo,
+            Action::Redo              => &t.help_redo,
+            Action::ToggleSafeMode    => &t.help_safe_mode,
+            Action::ToggleLock        => &t.help_lock,
+            Action::ToggleFavorite    => &t.help_favorite,
+            Action::EditJson          => &t.help_edit_json,
+            Action::SetValue          => &t.help_set_value,
+            Action::Save              => &t.help_save,
+            Action::SaveAndQuit       => &t.help_save_quit,
+            Action::MergeImport       => &t.help_merge_import,
+            Action::Reload            => &t.help_reload,
+            Action::Export            => &t.help_export,
+            Action::CopyValue         => &t.help_copy_value,
+            Action::CopyJson          => &t.help_copy_json,
+            Action::CopyMarkdown      => &t.help_copy_markdown,
+            Action::CopyCode          => &t.help_copy_code,
+            Action::ApplyCode         => &t.help_apply_code,
+            Action::ToggleRecommended => &t.help_recommended,
+            Action::ToggleDescription => &t.help_description,
+            Action::ToggleDefaults    => &t.help_defaults,
+            Action::ToggleJsonKeys    => &t.help_json_keys,
+            Action::FavoritesOnly     => &t.help_favorites_only,
+            Action::TogglePreview     => &t.help_preview,
+            Action::ToggleLog         => &t.help_log,
+            Action::ChooseTheme       => &t.help_theme,
+            Action::ChooseLanguage    => &t.help_language,
+            Action::ToggleSpacing     => &t.help_spacing,
+            Action::ToggleTierNames   => &t.help_tier_names,
+            Action::Preferences       => &t.help_preferences,
+            Action::ShowMessage       => &t.help_show_message,
+            Action::Help              => &t.help_help,
+            Action::Back              => &t.help_back,
+            Action::Quit              => &t.help_quit,
+        }
+    }
+}
+
+struct Binding {
+    action: Action,
+    /// Ctrl must match exactly. Shift only has to match when some binding
+    /// asks for it (Shift+Backspace), since terminals disagree on whether
+    /// symbols like `?` and `*` report it.
+    keys: &'static [(KeyCode, KeyModifiers)],
+}
+
+const NONE: KeyModifiers = KeyModifiers::NONE;
+const CTRL: KeyModifiers = KeyModifiers::CONTROL;
+const SHIFT: KeyModifiers = KeyModifiers::SHIFT;
+const CTRL_SHIFT: KeyModifiers = KeyModifiers::CONTROL.union(KeyModifiers::SHIFT);
+
+const DIGITS: [(KeyCode, KeyModifiers); 10] = [
+    (KeyCode::Char('0'), NONE),
+    (KeyCode::Char('1'), NONE),
+    (KeyCode::Char('2'), NONE),
+    (KeyCode::Char('3'), NONE),
+    (KeyCode::Char('4'), NONE),
+    (KeyCode::Char('5'), NONE),
+    (KeyCode::Char('6'), NONE),
+    (KeyCode::Char('7'), NONE),
+    (KeyCode::Char('8'), NONE),
+    (KeyCode::Char('9'), NONE),
+];
+
+/// Settings-list bindings in help order.
+const KEYMAP: &[Binding] = &[
+    Binding { action: Action::MoveUp,            keys: &[(KeyCode::Up, NONE), (KeyCode::Char('k'), NONE)] },
+    Binding { action: Action::MoveDown,          keys: &[(KeyCode::Down, NONE), (KeyCode::Char('j'), NONE)] },
+    Binding { action: Action::Activate,          keys: &[(KeyCode::Enter, NONE)] },
+    Binding { action: Action::ToggleMark,        keys: &[(KeyCode::Char(' '), NONE)] },
+    Binding { action: Action::SwitchColumn,      keys: &[(KeyCode::Tab, NONE), (KeyCode::BackTab, NONE)] },
+    Binding { action: Action::LeftColumn,        keys: &[(KeyCode::Left, CTRL_SHIFT)] },
+    Binding { action: Action::RightColumn,       keys: &[(KeyCode::Right, CTRL_SHIFT)] },
+    Binding { action: Action::ScrollPreviewUp,   keys: &[(KeyCode::PageUp, NONE)] },
+    Binding { action: Action::ScrollPreviewDown, keys: &[(KeyCode::PageDown, NONE)] },
+    Binding { action: Action::CycleNext,         keys: &[(KeyCode::Right, NONE), (KeyCode::Char('l'), NONE)] },
+    Binding { action: Action::CyclePrev,         keys: &[(KeyCode::Left, NONE), (KeyCode::Char('h'), NONE)] },
+    Binding { action: Action::CycleNextCoarse,   keys: &[(KeyCode::Right, SHIFT), (KeyCode::Char('L'), NONE)] },
+    Binding { action: Action::CyclePrevCoarse,   keys: &[(KeyCode::Left, SHIFT), (KeyCode::Char('H'), NONE)] },
+    Binding { action: Action::CycleMax,          keys: &[(KeyCode::Right, CTRL)] },
+    Binding { action: Action::CycleMin,          keys: &[(KeyCode::Left, CTRL)] },
+    Binding { action: Action::SetTier(0),        keys: &DIGITS },
+    Binding { action: Action::SetAllQuality,     keys: &[(KeyCode::Char('a'), NONE)] },
+    Binding { action: Action::ApplySuggested,    keys: &[(KeyCode::Char('g'), NONE)] },
+    Binding { action: Action::ResetField,        keys: &[(KeyCode::Char('r'), NONE)] },
+    Binding { action: Action::ResetAll,          keys: &[(KeyCode::Char('R'), NONE)] },
+    Binding { action: Action::RevertField,       keys: &[(KeyCode::Backspace, NONE)] },
+    Binding { action: Action::RevertAll,         keys: &[(KeyCode::Backspace, SHIFT)] },
+    Binding { action: Action::Undo,              keys: &[(KeyCode::Char('z'), CTRL)] },
+    Binding { action: Action::Redo,              keys: &[(KeyCode::Char('y'), CTRL)] },
+    Binding { action: Action::ToggleSafeMode,    keys: &[(KeyCode::Char('m'), NONE)] },
+    Binding { action: Action::ToggleLock,        keys: &[(KeyCode::Char('f'), NONE)] },
+    Binding { action: Action::ToggleFavorite,    keys: &[(KeyCode::Char('b'), NONE)] },
+    Binding { action: Action::EditJson,          keys: &[(KeyCode::Char('e'), NONE)] },
+    Binding { action: Action::SetValue,          keys: &[(KeyCode::Char(':'), NONE)] },
+    Binding { action: Action::Save,              keys: &[(KeyCode::Char('s'), NONE)] },
+    Binding { action: Action::SaveAndQuit,       keys: &[(KeyCode::Char('w'), NONE), (KeyCode::Char('s'), CTRL)] },
+    Binding { action: Action::MergeImport,       keys: &[(KeyCode::Char('i'), NONE)] },
+    Binding { action: Action::Reload,            keys: &[(KeyCode::F(5), NONE)] },
+    Binding { action: Action::Export,            keys: &[(KeyCode::Char('x'), NONE)] },
+    Binding { action: Action::CopyValue,         keys: &[(KeyCode::Char('y'), NONE)] },
+    Binding { action: Action::CopyJson,          keys: &[(KeyCode::Char('c'), NONE)] },
+    Binding { action: Action::CopyMarkdown,      keys: &[(KeyCode::Char('C'), NONE)] },
+    Binding { action: Action::CopyCode,          keys: &[(KeyCode::Char('Y'), NONE)] },
+    Binding { action: Action::ApplyCode,         keys: &[(KeyCode::Char('I'), NONE)] },
+    Binding { action: Action::ToggleRecommended, keys: &[(KeyCode::Char('*'), NONE)] },
+    Binding { action: Action::ToggleDescription, keys: &[(KeyCode::Char('d'), NONE)] },
+    Binding { action: Action::ToggleDefaults,    keys: &[(KeyCode::Char('v'), NONE)] },
+    Binding { action: Action::ToggleJsonKeys,    keys: &[(KeyCode::Char('K'), NONE)] },
+    Binding { action: Action::FavoritesOnly,     keys: &[(KeyCode::Char('B'), NONE)] },
+    Binding { action: Action::TogglePreview,     keys: &[(KeyCode::Char('p'), NONE)] },
+    Binding { action: Action::ToggleLog,         keys: &[(KeyCode::Char('o'), NONE)] },
+    Binding { action: Action::ChooseTheme,       keys: &[(KeyCode::Char('t'), NONE)] },
+    Binding { action: Action::ChooseLanguage,    keys: &[(KeyCode::Char('l'), CTRL)] },
+    Binding { action: Action::ToggleSpacing,     keys: &[(KeyCode::Char('='), NONE)] },
+    Binding { action: Action::ToggleTierNames,   keys: &[(KeyCode::Char('n'), NONE)] },
+    Binding { action: Action::Preferences,       keys: &[(KeyCode::Char(','), NONE)] },
+    Binding { action: Action::ShowMessage,       keys: &[(KeyCode::Char('!'), NONE)] },
+    Binding { action: Action::Help,              keys: &[(KeyCode::Char('?'), NONE), (KeyCode::F(1), NONE)] },
+    Binding { action: Action::Back,              keys: &[(KeyCode::Esc, NONE)] },
+    Binding { action: Action::Quit,              keys: &[(KeyCode::Char('q'), NONE)] },
+];
+
+/// `KEYMAP` with the config's `[keybindings]` applied, in the same order.
+struct Keymap {
+    bindings: Vec<(Action, Vec<(KeyCode, KeyModifiers)>)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Keymap { bindings: KEYMAP.iter().map(|b| (b.action, b.keys.to_vec())).collect() }
+    }
+}
+
+impl Keymap {
+    /// Each entry replaces all default keys of its action; an empty list
+    /// unbinds it. Fails on unknown actions, unreadable keys, and keys that
+    /// would end up on two actions. A preset hotkey may shadow a default
+    /// key, but not one the config names for an action.
+    fn new(config: &Config) -> Result<Self, String> {
+        let mut keymap = Keymap::default();
+        for (name, keys) in &config.keybindings {
+            let Some(action) = KEYMAP.iter().map(|b| b.action).find(|a| a.name() == name) else {
+                let names: Vec<_> = KEYMAP.iter().map(|b| b.action.name()).collect();
+                return Err(format!("unknown action \"{name}\" in [keybindings]; known actions: {}", names.join(", ")));
+            };
+            if matches!(action, Action::SetTier(_)) {
+                return Err("set-tier always uses the digit keys and can't be rebound".to_string());
+            }
+            let parsed = keys
+                .as_slice()
+                .iter()
+                .map(|k| parse_key(k).ok_or_else(|| format!("can't read key \"{k}\" for {name}")))
+                .collect::<Result<Vec<_>, _>>()?;
+            if let Some(slot) = keymap.bindings.iter_mut().find(|(a, _)| *a == action) {
+                slot.1 = parsed;
+            }
+        }
+
+        let hotkeys: Vec<_> = config.hotkeys.keys().filter_map(|k| parse_key(k)).collect();
+        let mut seen: Vec<((KeyCode, KeyModifiers), &str)> = Vec::new();
+        for (action, keys) in &keymap.bindings {
+            let configured = config.keybindings.contains_key(action.name());
+            for &key in keys {
+                let other = match seen.iter().find(|(k, _)| *k == key) {
+                    Some((_, other)) => Some(*other),
+                    None if configured && hotkeys.contains(&key) => Some("a preset hotkey"),
+                    None => None,
+                };
+                if let Some(other) = other {
+                    let label = key_label(key.0, key.1, &glyphs::ASCII);
+                    return Err(format!(
+                        "{label} is bound to both {other} and {}; give one of them other keys in [keybindings]",
+                        action.name()
+                    ));
+                }
+                seen.push((key, action.name()));
+            }
+        }
+        Ok(keymap)
+    }
+
+    fn action_for(&self, key: KeyEvent) -> Option<Action> {
+        let bound = |keys: &[(KeyCode, KeyModifiers)], exact_shift: bool| {
+            keys.iter().any(|&(code, mods)| {
+                code == key.code
+                    && mods.contains(CTRL) == key.modifiers.contains(CTRL)
+                    && (!exact_shift || mods.contains(SHIFT) == key.modifiers.contains(SHIFT))
+            })
+        };
+        self.bindings
+            .iter()
+            .find(|(_, keys)| bound(keys, true))
+            .or_else(|| self.bindings.iter().find(|(_, keys)| bound(keys, false)))
+            .map(|(action, _)| match (*action, key.code) {
+                (Action::SetTier(_), KeyCode::Char(c @ '0'..='9')) => Action::SetTier(c as u8 - b'0'),
+                (action, _) => action,
+            })
+    }
+
+    /// The first key of `action` as hints write it; empty when unbound.
+    fn key(&self, action: Action, glyphs: &Glyphs) -> String {
+        self.bindings
+            .iter()
+            .find(|(a, _)| *a == action)
+            .and_then(|(_, keys)| keys.first())
+            .map(|&(code, mods)| key_label(code, mods, glyphs))
+            .unwrap_or_default()
+    }
+
+    /// The first keys of two opposite actions, e.g. `\u{2191}\u{2193}` or
+    /// `Shift+\u{2190}\u{2192}`; longer names get a slash between them.
+    fn pair(&self, a: Action, b: Action, glyphs: &Glyphs) -> String {
+        let (a, b) = (self.key(a, glyphs), self.key(b, glyphs));
+        let split = |label: &str| match label.rsplit_once('+') {
+            Some((mods, name)) if !name.is_empty() => (format!("{mods}+"), name.to_string()),
+            _ => (String::new(), label.to_string()),
+        };
+        let ((mods_a, name_a), (mods_b, name_b)) = (split(&a), split(&b));
+        if mods_a != mods_b {
+            return format!("{a}/{b}");
+        }
+        let sep = if name_a.chars().count() > 1 || name_b.chars().count() > 1 { "/" } else { "" };
+        format!("{mods_a}{name_a}{sep}{name_b}")
+    }
+}
+
+/// Reads a key description from the config: `ctrl+s`, `shift+backspace`,
+/// `f5`, `pgdn`, `k`. Single characters keep their case, and `shift+k`
+/// means `K`, as terminals report it.
+fn parse_key(text: &str) -> Option<(KeyCode, KeyModifiers)> {
+    let (mods_text, name) = match text.rsplit_once('+') {
+        // "+" and "ctrl++" name the plus key itself.
+        Some((rest, "")) => (rest.strip_suffix('+').unwrap_or(rest), "+"),
+        Some((mods, name)) => (mods, name),
+        None => ("", text),
+    };
+    let mut mods = NONE;
+    for m in mods_text.split('+').filter(|m| !m.is_empty()) {
+        match m.to_ascii_lowercase().as_str() {
+            "ctrl" | "control" => mods |= CTRL,
+            "shift" => mods |= SHIFT,
+            _ => return None,
+        }
+    }
+    let mut chars = name.chars();
+    let code = match (chars.next(), chars.next()) {
+        (Some(c), None) if mods.contains(SHIFT) && c.is_ascii_alphabetic() && !mods.contains(CTRL) => {
+            mods.remove(SHIFT);
+            KeyCode::Char(c.to_ascii_uppercase())
+        }
+        (Some(c), None) if mods.contains(CTRL) => KeyCode::Char(c.to_ascii_lowercase()),
+        (Some(c), None) => KeyCode::Char(c),
+        _ => match name.to_ascii_lowercase().as_str() {
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "enter" | "return" => KeyCode::Enter,
+            "tab" => KeyCode::Tab,
+            "esc" | "escape" => KeyCode::Esc,
+            "space" => KeyCode::Char(' '),
+            "backspace" => KeyCode::Backspace,
+            "pgup" | "pageup" => KeyCode::PageUp,
+            "pgdn" | "pagedown" => KeyCode::PageDown,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "delete" | "del" => KeyCode::Delete,
+            "insert" | "ins" => KeyCode::Insert,
+            lower => match lower.strip_prefix('f').and_then(|n| n.parse().ok()) {
+                Some(n @ 1..=12) => KeyCode::F(n),
+                _ => return None,
+            },
+        },
+    };
+    Some((code, mods))
+}
+
+/// How a key is written in hints and help: `Ctrl+S`, `\u{2191}`, `F1`.
+fn key_label(code: KeyCode, mods: KeyModifiers, glyphs: &Glyphs) -> String {
+    let name = match code {
+        KeyCode::Up        => glyphs.up.to_string(),
+        KeyCode::Down      => glyphs.down.to_string(),
+        KeyCode::Left      => glyphs.left.to_string(),
+        KeyCode::Right     => glyphs.right.to_string(),
+        KeyCode::Enter     => "Enter".to_string(),
+        KeyCode::Tab       => "Tab".to_string(),
+        KeyCode::BackTab   => "Shift+Tab".to_string(),
+        KeyCode::PageUp    => "PgUp".to_string(),
+        KeyCode::PageDown  => "PgDn".to_string(),
+        KeyCode::Esc       => "Esc".to_string(),
+        KeyCode::Backspace => "Backspace".to_string(),
+        KeyCode::F(n)      => format!("F{n}"),
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) if c.is_ascii_uppercase() => format!("Shift+{c}"),
+        KeyCode::Char(c)   => c.to_uppercase().to_string(),
+        other              => format!("{other:?}"),
+    };
+    let name = if mods.contains(SHIFT) { format!("Shift+{name}") } else { name };
+    if mods.contains(CTRL) { format!("Ctrl+{name}") } else { name }
+}
+
+/// Every key of a binding, e.g. `\u{2191}/K`; the digit row reads `0-9`.
+fn binding_label(action: Action, keys: &[(KeyCode, KeyModifiers)], glyphs: &Glyphs) -> String {
+    if matches!(action, Action::SetTier(_)) {
+        return "0-9".to_string();
+    }
+    keys.iter().map(|&(code, mods)| key_label(code, mods, glyphs)).collect::<Vec<_>>().join("/")
+}
+
+// ---------------------------------------------------------------------------
+// App state
+// ---------------------------------------------------------------------------
+
+#[derive(Clone, Copy, PartialEq)]
+enum Severity {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    /// Info and success toasts clear themselves after `TOAST_TTL`;
+    /// warnings and errors stay until dismissed.
+    fn expires(self) -> bool {
+        matches!(self, Severity::Info | Severity::Success)
+    }
+
+    /// Color and glyph, so severity never rests on color alone.
+    fn look(self, theme: &Theme, glyphs: &'static Glyphs) -> (Color, &'static str) {
+        match self {
+            Severity::Info    => (theme.accent, glyphs.info),
+            Severity::Success => (theme.positive, glyphs.success),
+            Severity::Warning => (theme.warning, glyphs.warning),
+            Severity::Error   => (theme.error, glyphs.error),
+        }
+    }
+}
+
+/// Recent same-direction cycling, for held-key acceleration.
+#[derive(Clone, Copy)]
+struct Accel {
+    idx: usize,
+    delta: isize,
+    count: u32,
+    at: Instant,
+}
+
+/// Words a notice in the current language, from arguments it captured.
+type Wording = Rc<dyn Fn(&L10n) -> String>;
+
+/// A transient notification drawn in the bottom-right corner.
+#[derive(Clone)]
+struct Toast {
+    severity: Severity,
+    text: String,
+    /// Kept so a language switch can reword `text`.
+    wording: Wording,
+    at: Instant,
+}
+
+impl Toast {
+    fn expired(&self) -> bool {
+        self.severity.expires() && self.at.elapsed() >= TOAST_TTL
+    }
+
+    fn fading(&self) -> bool {
+        self.severity.expires() && self.at.elapsed() >= TOAST_TTL - TOAST_FADE
+    }
+
+    /// Time until this toast next changes appearance (starts fading or expires).
+    fn next_change(&self) -> Option<Duration> {
+        if !self.severity.expires() {
+            return None;
+        }
+        let age = self.at.elapsed();
+        let fade_at = TOAST_TTL - TOAST_FADE;
+        Some(if age < fade_at { fade_at - age } else { TOAST_TTL.saturating_sub(age) })
+    }
+}
+
+/// Result of registry I/O run off the UI thread.
+enum IoDone {
+    /// The current value, and any legacy value names found next to it.
+    Read(ReadResult, Vec<String>),
+    /// The value F5 read, replacing the settings and any edits.
+    Reload(ReadResult),
+    /// The legacy value that was copied to `REG_VALUE`, and the outcome.
+    Migrate(String, io::Result<()>),
+    /// The settings that were written, and the outcome.
+    Write(GraphicsSettings, io::Result<SaveOutcome>),
+    /// The registry value the save found in place of the one loaded; nothing
+    /// was written.
+    ChangedOutside(GraphicsSettings),
+    /// The Global and CN values, for the `sync_clients` check.
+    Clients(ReadResult, ReadResult),
+    /// The client that was overwritten with the other's value, and the outcome.
+    Synced(Client, io::Result<()>),
+}
+
+/// A field an edit changed, with its value before and after.
+struct Delta {
+    field: Field,
+    old: Value,
+    new: Value,
+}
+
+/// Command-line switches that shape the interactive session.
+struct Options {
+    /// `--no-nul`: saves never end in a NUL, whatever was read.
+    no_nul: bool,
+    /// `--yes`: saves skip the review dialog.
+    yes: bool,
+    read_only: bool,
+    /// `--theme`, overriding the configured one for this run.
+    theme: Option<ThemeName>,
+    no_color: bool,
+    glyphs: &'static Glyphs,
+    /// `--hive`: where saves go.
+    hive: Hive,
+    /// `--fresh`: start without the remembered session.
+    fresh: bool,
+    /// `--accessible` or the config's `accessible`.
+    accessible: bool,
+}
+
+struct App {
+    settings: GraphicsSettings,
+    /// Settings as last read from or written to the registry.
+    original: GraphicsSettings,
+    defs: Vec<SettingDef>,
+    cursor: usize,
+    /// Hides everything but the favorites section, when there is one.
+    favorites_only: bool,
+    /// Rows marked with Space; ←/→ then move all of them together.
+    marked: Vec<Field>,
+    /// Notifications, oldest first.
+    toasts: VecDeque<Toast>,
+    /// How many toasts were fading at the last `tick`, to tell when another
+    /// one starts.
+    fading: usize,
+    /// Session event log, oldest first: every notification and every
+    /// changed value, stamped with the time since `started`.
+    log: VecDeque<String>,
+    started: Instant,
+    /// Shows the event log panel under the list.
+    show_log: bool,
+    /// Lines the log panel is scrolled up from the newest entry.
+    log_scroll: usize,
+    /// The newest toast, kept after it expires for the message overlay.
+    last_notice: Option<Toast>,
+    /// The message overlay is open; any key closes it.
+    show_message: bool,
+    lang: Lang,
+    config: Config,
+    /// Open raw JSON editor; while open it replaces the list and receives all key input.
+    json_edit: Option<JsonEdit>,
+    /// Open dialog; while open it receives all key input.
+    modal: Option<Modal>,
+    /// Restricts `cycle` to values that don't add `validate` conflicts.
+    safe_mode: bool,
+    /// Whether the list is laid out in two columns; refreshed from the terminal size each frame.
+    wide: bool,
+    /// Rows scrolled off the top of the list, per column; kept between
+    /// frames so moving up doesn't scroll until the cursor reaches the top.
+    list_offset: usize,
+    /// Padded list labels; `fit_to` rebuilds them when they go stale.
+    labels: Labels,
+    /// Marks values matching `recommended_value` with a star.
+    show_recommended: bool,
+    /// Shows the description panel when the terminal is wide enough.
+    show_description: bool,
+    /// Shows each field's game default next to its current value.
+    show_defaults: bool,
+    /// Appends each field's registry JSON key to its label.
+    show_json_keys: bool,
+    /// Reference values for the modified-from-default marker.
+    defaults: GraphicsSettings,
+    /// Start of the current autosave interval: the last autosave, or the
+    /// first edit after the settings were last clean.
+    autosave_from: Instant,
+    /// Legacy value name offered by `Confirm::MigrateLegacy`.
+    legacy_value: Option<String>,
+    /// `sync_clients` comparison still to run after the startup read.
+    clients_pending: bool,
+    /// Global and CN settings while the sync dialog is open.
+    client_values: Option<(GraphicsSettings, GraphicsSettings)>,
+    /// The registry value a save found changed since it was loaded, while
+    /// the dialog about it is open.
+    changed_outside: Option<GraphicsSettings>,
+    /// The startup read found no registry key, so the next save creates it.
+    key_missing: bool,
+    /// Changes the game makes to the value while the app is open.
+    watch: Option<watch::Watcher>,
+    /// Registry read/write in flight on a worker thread.
+    io: Option<mpsc::Receiver<IoDone>>,
+    /// The job in `io` is a save, which quitting waits for.
+    saving: bool,
+    /// Frame of the status-bar spinner shown while `io` is pending.
+    spinner: usize,
+    accel: Option<Accel>,
+    /// Quit once the in-flight save succeeds.
+    quit_after_save: bool,
+    /// Set when the main loop should exit.
+    quit: bool,
+    /// `--read-only`: every edit, import and save is refused.
+    read_only: bool,
+    /// The config file didn't parse, so the session isn't written into it
+    /// on quit.
+    config_invalid: bool,
+    /// NO_COLOR or `--no-color`: draw with the monochrome theme whatever is configured.
+    no_color: bool,
+    /// `--theme`, used over the configured theme until one is picked in the app.
+    /// Never saved.
+    theme_override: Option<ThemeName>,
+    glyphs: &'static Glyphs,
+    /// Where saves go; `--hive` picks it.
+    hive: Hive,
+    store: Store,
+    /// Plain layout for screen readers and unusual terminals: high-contrast
+    /// colors, one column, no gauges, and toggles spelled `[ON]`/`[OFF]`.
+    accessible: bool,
+    /// Where the settings on screen were read from.
+    loaded_hive: Hive,
+    keymap: Keymap,
+    /// The keybinding overlay is open; it ignores every key but scrolling and closing.
+    show_help: bool,
+    help_scroll: u16,
+    /// Edits to step back through with Ctrl+Z; each entry is one user action.
+    undo: VecDeque<Vec<Delta>>,
+    /// Edits undone since the last new edit, for Ctrl+Y.
+    redo: Vec<Vec<Delta>>,
+    /// Heuristic preset for this machine, applied with G.
+    suggested: Preset,
+    /// What VSync caps the frame rate to, when known; see `refresh_rate()`.
+    refresh_rate: Option<i64>,
+    /// Whether S shows the confirmation dialog first (config + `--yes`).
+    confirm_saves: bool,
+    /// Whether saves end the blob in a NUL; follows the value that was read.
+    append_nul: bool,
+    /// Shows the live JSON preview in place of the description panel.
+    show_preview: bool,
+    preview_scroll: u16,
+}
+
+impl App {
+    fn new(lang: Lang, options: &Options) -> Self {
+        let (config, warnings) = Config::load_checked();
+        let mut app = Self::with_config(lang, options, config);
+        app.config_invalid = warnings.iter().any(|w| matches!(w, config::Warning::Invalid { .. }));
+        let gpu = hardware::detect_gpu();
+        app.suggested = suggest_preset(gpu.as_ref());
+        app.refresh_rate = refresh_rate(&app.config);
+        let store = app.store.clone();
+        app.run_io(move || IoDone::Read(store.read(), legacy_values()));
+        app.clients_pending = app.config.sync_clients && !app.read_only;
+        for warning in warnings {
+            app.notify(Severity::Warning, move |t| match &warning {
+                config::Warning::UnknownKey(key) => t.fill(&t.config_unknown_key, &[("key", key)]),
+                config::Warning::Invalid { message, backed_up: true } => t.failure(&t.config_invalid, message),
+                config::Warning::Invalid { message, backed_up: false } => t.failure(&t.config_invalid_kept, message),
+            });
+        }
+        for problem in translations::problems() {
+            app.notify(Severity::Warning, move |t| match problem {
+                translations::Problem::Missing(file, keys, fallback) => {
+                    t.fill(&t.translation_missing, &[("file", file), ("n", &keys.len()), ("lang", fallback)])
+                }
+                translations::Problem::Unknown(file, keys) => {
+                    t.fill(&t.translation_unknown, &[("file", file), ("keys", &keys.join(", "))])
+                }
+                translations::Problem::Placeholders(file, keys) => {
+                    t.fill(&t.translation_placeholders, &[("file", file), ("keys", &keys.join(", "))])
+                }
+                translations::Problem::BuiltIn(file) => t.fill(&t.translation_built_in, &[("file", file)]),
+                translations::Problem::Invalid(file, e) => {
+                    t.failure(&t.fill(&t.translation_invalid, &[("file", file)]), e)
+                }
+            });
+        }
+        if !app.config.preset_hint_shown {
+            app.config.preset_hint_shown = true;
+            let gpu_name = gpu.map(|g| g.name);
+            let preset = app.suggested;
+            app.notify(Severity::Info, move |t| {
+                let gpu_name = gpu_name.as_deref().unwrap_or(&t.unknown_gpu);
+                t.fill(&t.preset_suggested, &[("gpu", &gpu_name), ("preset", &preset.label(t))])
+            });
+            if let Err(e) = app.config.save() {
+                app.notify_failure(|t| &t.config_save_failed, e);
+            }
+        }
+        app
+    }
+
+    /// The app with `config` and default settings, before anything is read.
+    fn with_config(lang: Lang, options: &Options, config: Config) -> Self {
+        // `main` already refused a bad `[keybindings]`; a file edited since
+        // falls back to the defaults.
+        let keymap = Keymap::new(&config).unwrap_or_default();
+        let session = if options.fresh { Session::default() } else { config.session.clone() };
+        let defs = setting_defs(&fps_options(&config));
+        let labels = Labels::new(&defs, lang, false);
+        let mut app = Self {
+            settings: GraphicsSettings::default(),
+            original: GraphicsSettings::default(),
+            defs,
+            cursor: 0,
+            favorites_only: false,
+            marked: Vec::new(),
+            toasts: VecDeque::new(),
+            fading: 0,
+            log: VecDeque::new(),
+            started: Instant::now(),
+            show_log: false,
+            log_scroll: 0,
+            last_notice: None,
+            show_message: false,
+            lang,
+            confirm_saves: !options.yes && !config.skip_save_confirmation,
+            config,
+            json_edit: None,
+            modal: None,
+            safe_mode: false,
+            wide: false,
+            list_offset: 0,
+            labels,
+            show_recommended: true,
+            show_description: session.show_description,
+            show_defaults: true,
+            show_json_keys: false,
+            defaults: GraphicsSettings::default(),
+            show_preview: session.show_preview,
+            preview_scroll: 0,
+            autosave_from: Instant::now(),
+            legacy_value: None,
+            clients_pending: false,
+            client_values: None,
+            changed_outside: None,
+            key_missing: false,
+            watch: None,
+            io: None,
+            saving: false,
+            spinner: 0,
+            accel: None,
+            quit_after_save: false,
+            quit: false,
+            read_only: options.read_only,
+            config_invalid: false,
+            no_color: options.no_color,
+            theme_override: options.theme,
+            glyphs: options.glyphs,
+            hive: options.hive,
+            store: Store::Registry,
+            accessible: options.accessible,
+            loaded_hive: Hive::CurrentUser,
+            keymap,
+            show_help: false,
+            help_scroll: 0,
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            suggested: Preset::Medium,
+            refresh_rate: None,
+            append_nul: !options.no_nul,
+        };
+        if let Some(key) = &session.cursor {
+            let idx = app.defs.iter().position(|d| json_key(d.field) == key);
+            if let Some(row) = idx.and_then(|idx| app.rows().iter().position(|r| *r == Row::Setting(idx))) {
+                app.cursor = row;
+            }
+        }
+        app
+    }
+
+    /// Runs registry I/O on a worker thread so the UI can show a spinner
+    /// meanwhile. If no thread can be spawned the job runs synchronously.
+    fn run_io(&mut self, job: impl Fn() -> IoDone + Clone + Send + 'static) {
+        let (tx, rx) = mpsc::channel();
+        let worker_tx = tx.clone();
+        let worker_job = job.clone();
+        let spawned = std::thread::Builder::new().spawn(move || {
+            let _ = worker_tx.send(worker_job());
+        });
+        if spawned.is_err() {
+            let _ = tx.send(job());
+        }
+        self.io = Some(rx);
+    }
+
+    /// Shows a toast; any subsystem reports events through this. `wording`
+    /// is called again when the language changes while it is on screen.
+    fn notify(&mut self, severity: Severity, wording: impl Fn(&L10n) -> String + 'static) {
+        let wording: Wording = Rc::new(wording);
+        let text = self.glyphs.text(&wording(self.t())).into_owned();
+        self.log(&text);
+        let toast = Toast { severity, text, wording, at: Instant::now() };
+        self.last_notice = Some(toast.clone());
+        self.toasts.push_back(toast);
+        while self.toasts.len() > MAX_TOASTS {
+            self.toasts.pop_front();
+        }
+    }
+
+    /// Error notice: what failed, then the cause.
+    fn notify_failure(&mut self, what: fn(&L10n) -> &str, e: impl fmt::Display) {
+        let e = e.to_string();
+        self.notify(Severity::Error, move |t| t.failure(what(t), &e));
+    }
+
+    /// Rewords the toasts and the kept message in the current language.
+    /// The event log keeps the wording things happened in.
+    fn reword_notices(&mut self) {
+        let t = self.t();
+        let glyphs = self.glyphs;
+        for toast in self.toasts.iter_mut().chain(self.last_notice.as_mut()) {
+            toast.text = glyphs.text(&(toast.wording)(t)).into_owned();
+        }
+    }
+
+    fn log(&mut self, text: &str) {
+        let text = self.glyphs.text(text);
+        let secs = self.started.elapsed().as_secs();
+        self.log.push_back(format!("{:>3}:{:02}  {text}", secs / 60, secs % 60));
+        if self.log.len() > LOG_CAPACITY {
+            self.log.pop_front();
+        }
+    }
+
+    fn scroll_log(&mut self, delta: i32) {
+        let max = self.log.len().saturating_sub(1) as i32;
+        self.log_scroll = (self.log_scroll as i32 + delta).clamp(0, max) as usize;
+    }
+
+    /// Dismisses the newest toast that doesn't expire on its own.
+    fn dismiss_toast(&mut self) {
+        if let Some(i) = self.toasts.iter().rposition(|t| !t.severity.expires()) {
+            self.toasts.remove(i);
+        }
+    }
+
+    fn has_sticky_toast(&self) -> bool {
+        self.toasts.iter().any(|t| !t.severity.expires())
+    }
+
+    /// How long the main loop may block waiting for input before something
+    /// on screen needs updating; `None` means it can wait indefinitely.
+    fn next_wakeup(&self) -> Option<Duration> {
+        if self.busy() {
+            return Some(SPINNER_TICK);
+        }
+        let autosave = self.autosave_due().map(|due| due.saturating_duration_since(Instant::now()));
+        self.toasts.iter().filter_map(Toast::next_change).chain(autosave).min()
+    }
+
+    /// When pending changes are next written automatically; `None` when
+    /// autosave is off, there is nothing to save, or it has to wait. It
+    /// waits while a dialog or the JSON editor is open so the save doesn't
+    /// land in the middle of a decision; closing them takes a key press,
+    /// which wakes the loop to check again.
+    fn autosave_due(&self) -> Option<Instant> {
+        if self.config.autosave_secs == 0
+            || self.read_only
+            || self.busy()
+            || self.modal.is_some()
+            || self.json_edit.is_some()
+            || !self.is_dirty()
+        {
+            return None;
+        }
+        // An interval too long to represent never comes due.
+        self.autosave_from.checked_add(Duration::from_secs(self.config.autosave_secs))
+    }
+
+    /// Timed updates: spinner animation, toast fading and expiry, and
+    /// autosave. Returns whether anything changed that the screen shows.
+    fn tick(&mut self) -> bool {
+        let busy = self.busy();
+        if busy {
+            self.spinner = self.spinner.wrapping_add(1);
+        }
+        let toasts = self.toasts.len();
+        self.toasts.retain(|t| !t.expired());
+        let fading = self.toasts.iter().filter(|t| t.fading()).count();
+        let mut changed = busy || self.toasts.len() != toasts || fading != self.fading;
+        self.fading = fading;
+        if self.autosave_due().is_some_and(|due| due <= Instant::now()) {
+            // Restart the interval up front so a failing write retries on the
+            // next interval rather than immediately.
+            self.autosave_from = Instant::now();
+            self.save();
+            changed = true;
+        }
+        changed
+    }
+
+    fn busy(&self) -> bool {
+        self.io.is_some()
+    }
+
+    /// Applies the result of finished I/O, if any. Returns whether
+    /// anything changed that the screen shows.
+    fn poll_io(&mut self) -> bool {
+        let Some(rx) = &self.io else {
+            // The client comparison waits for the startup read and any
+            // dialog it opened, so the two never overlap.
+            if self.clients_pending && self.modal.is_none() {
+                self.clients_pending = false;
+                self.run_io(|| IoDone::Clients(read_settings(), read_settings_from(Client::Cn)));
+                return true;
+            }
+            return false;
+        };
+        let done = match rx.try_recv() {
+            Ok(done) => done,
+            Err(mpsc::TryRecvError::Empty) => return false,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.io = None;
+                self.saving = false;
+                return true;
+            }
+        };
+        self.io = None;
+        self.saving = false;
+        let t = self.t();
+        match done {
+            IoDone::Read(result, legacy) if !legacy.is_empty() => {
+                let names = legacy.join(", ");
+                if !matches!(result, ReadResult::Missing) || self.read_only {
+                    self.notify(Severity::Warning, move |t| t.fill(&t.legacy_ignored, &[("names", &names)]));
+                } else {
+                    self.legacy_value = legacy.into_iter().next();
+                    self.modal = Some(Modal::Confirm(Confirm::MigrateLegacy));
+                }
+                match result {
+                    ReadResult::Found(stored) => self.load(*stored),
+                    ReadResult::Missing => self.key_missing = true,
+                    other => self.notify(Severity::Error, move |t| other.problem(t).unwrap_or_default()),
+                }
+            }
+            IoDone::Read(ReadResult::Found(stored), _) => self.load(*stored),
+            IoDone::Read(ReadResult::Missing, _) => {
+                self.key_missing = true;
+                self.notify(Severity::Warning, |t| t.no_registry.clone());
+            }
+            // Corrupt data stays on screen until dismissed.
+            IoDone::Read(result, _) => {
+                if result.problem(t).is_some() {
+                    self.notify(Severity::Error, move |t| result.problem(t).unwrap_or_default());
+                }
+            }
+            IoDone::Reload(ReadResult::Found(stored)) => {
+                self.load(*stored);
+                self.key_missing = false;
+                self.notify(Severity::Success, |t| t.reloaded.clone());
+            }
+            IoDone::Reload(ReadResult::Missing) => {
+                self.key_missing = true;
+                self.notify(Severity::Warning, |t| t.no_registry.clone());
+            }
+            IoDone::Reload(result) => {
+                self.notify(Severity::Error, move |t| result.problem(t).unwrap_or_default());
+            }
+            IoDone::Migrate(name, Ok(())) => {
+                self.notify(Severity::Success, move |t| t.fill(&t.legacy_migrated, &[("name", &name)]));
+                self.run_io(|| IoDone::Read(read_settings(), Vec::new()));
+            }
+            IoDone::Migrate(_, Err(e)) => {
+                self.notify_failure(|t| &t.legacy_migrate_failed, e);
+            }
+            IoDone::ChangedOutside(current) => {
+                self.changed_outside = Some(current);
+                self.modal = Some(Modal::Choice(ChoiceKind::ChangedOutside, 0));
+            }
+            IoDone::Write(written, Ok(outcome)) => {
+                self.original = written;
+                self.key_missing = false;
+                self.notify(Severity::Success, move |t| outcome.message(t));
+                self.quit |= self.quit_after_save;
+            }
+            IoDone::Clients(ReadResult::Found(global), ReadResult::Found(cn)) => {
+                if !diff_settings(&self.defs, &global.settings, &cn.settings).is_empty() {
+                    self.client_values = Some((global.settings, cn.settings));
+                    self.modal = Some(Modal::Choice(ChoiceKind::SyncClients, 2));
+                }
+            }
+            // Only one client installed, or one value unreadable: nothing to sync.
+            IoDone::Clients(..) => {}
+            IoDone::Synced(to, Ok(())) => {
+                self.notify(Severity::Success, move |t| t.fill(&t.clients_synced, &[("client", &to.label(t))]));
+                if to == Client::Global {
+                    self.run_io(|| IoDone::Read(read_settings(), Vec::new()));
+                }
+            }
+            IoDone::Synced(_, Err(e)) => {
+                self.notify_failure(|t| &t.clients_sync_failed, e);
+            }
+            IoDone::Write(_, Err(e)) => {
+                self.quit_after_save = false;
+                self.notify_failure(|t| &t.save_failed, e);
+            }
+        }
+        true
+    }
+
+    /// Takes a change the watcher found once no I/O or other dialog is in
+    /// the way. Returns whether anything changed that the screen shows.
+    fn poll_watch(&mut self) -> bool {
+        let asking = matches!(self.modal, Some(Modal::Choice(ChoiceKind::ChangedOutside, _)));
+        if self.busy() || (self.modal.is_some() && !asking) || self.json_edit.is_some() {
+            return false;
+        }
+        let Some(change) = self.watch.as_ref().and_then(watch::Watcher::latest) else {
+            return false;
+        };
+        self.outside_change(change)
+    }
+
+    /// Reloads when there are no edits; otherwise asks whether to merge
+    /// them into the new value or overwrite it. Changes that arrive while
+    /// the question is open update it rather than asking again.
+    fn outside_change(&mut self, change: watch::Change) -> bool {
+        match change {
+            watch::Change::Missing if self.key_missing => return false,
+            watch::Change::Missing => {
+                self.key_missing = true;
+                self.notify(Severity::Warning, |t| t.no_registry.clone());
+            }
+            watch::Change::Value(hive, bytes) => match parse_value(&bytes, hive) {
+                ReadResult::Found(stored) => {
+                    // Saves made here come back as changes too.
+                    let same = serde_json::to_value(&stored.settings).ok() == serde_json::to_value(&self.original).ok();
+                    if same && !self.key_missing {
+                        return false;
+                    }
+                    self.key_missing = false;
+                    if self.is_dirty() {
+                        self.changed_outside = Some(stored.settings);
+                        if !matches!(self.modal, Some(Modal::Choice(ChoiceKind::ChangedOutside, _))) {
+                            self.modal = Some(Modal::Choice(ChoiceKind::ChangedOutside, 0));
+                        }
+                    } else {
+                        self.load(*stored);
+                        self.notify(Severity::Info, |t| t.external_reloaded.clone());
+                    }
+                }
+                other => self.notify(Severity::Error, move |t| other.problem(t).unwrap_or_default()),
+            },
+        }
+        true
+    }
+
+    /// Takes freshly read settings as the new baseline.
+    fn load(&mut self, stored: Stored) {
+        self.original = stored.settings.clone();
+        self.settings = stored.settings;
+        self.undo.clear();
+        self.redo.clear();
+        self.append_nul &= stored.nul_terminated;
+        self.loaded_hive = stored.hive;
+        if self.loaded_hive != Hive::CurrentUser || self.hive != Hive::CurrentUser {
+            let (read, write) = (stored.hive, self.hive);
+            self.notify(Severity::Info, move |t| {
+                t.fill(&t.hive_notice, &[("read", &read.name()), ("write", &write.name())])
+            });
+        }
+    }
+
+    fn sync_clients(&mut self, from: Client, to: Client) {
+        self.client_values = None;
+        self.run_io(move || IoDone::Synced(to, copy_client(from, to)));
+    }
+
+    /// Picks the one- or two-column list layout for a screen of `size`,
+    /// scrolls the list just enough to keep the cursor in view, and
+    /// rebuilds the labels when their language changed.
+    fn fit_to(&mut self, size: Rect) {
+        if self.labels.lang != self.lang || self.labels.json_keys != self.show_json_keys {
+            self.labels = Labels::new(&self.defs, self.lang, self.show_json_keys);
+        }
+        let list_area = screen_areas(self, size).list;
+        self.wide = !self.accessible && list_area.width.saturating_sub(2) > TWO_COLUMN_MIN_WIDTH;
+        let visible = self.visible_rows(list_area.height.saturating_sub(2));
+        let rows = self.rows();
+        let split = self.column_split(&rows);
+        let cursor_line = if self.cursor >= split { self.cursor - split } else { self.cursor };
+        let total = split.max(rows.len() - split);
+        // The offset only moves when the cursor leaves the view, and never
+        // leaves blank lines below the last row after a resize.
+        let offset = self.list_offset.min(cursor_line).max((cursor_line + 1).saturating_sub(visible));
+        self.list_offset = offset.min(total.saturating_sub(visible));
+    }
+
+    /// How many rows fit in `height` lines, each followed by `row_spacing`
+    /// blank lines except the last.
+    fn visible_rows(&self, height: u16) -> usize {
+        let spacing = self.config.row_spacing as usize;
+        ((height as usize + spacing) / (spacing + 1)).max(1)
+    }
+
+    /// Records where this run left off and saves it for the next launch.
+    /// Only the `[session]` table is written, and nothing at all in
+    /// read-only runs or over a config file that didn't parse.
+    fn remember_session(&mut self) {
+        self.config.session = Session {
+            language: Some(self.lang.code().to_string()),
+            cursor: match self.selected_row() {
+                Some(Row::Setting(idx)) => Some(json_key(self.defs[idx].field).to_string()),
+                _ => None,
+            },
+            show_description: self.show_description,
+            show_preview: self.show_preview,
+        };
+        if self.read_only || self.config_invalid {
+            return;
+        }
+        // Nowhere left to report a failure; the next run just starts fresh.
+        let _ = config::save_session(&self.config.session);
+    }
+
+    fn theme(&self) -> &'static Theme {
+        if self.no_color {
+            &theme::MONO
+        } else if self.accessible {
+            ThemeName::HighContrast.theme()
+        } else {
+            self.theme_name().theme()
+        }
+    }
+
+    /// The theme in use before NO_COLOR and accessibility apply.
+    fn theme_name(&self) -> ThemeName {
+        self.theme_override.unwrap_or(self.config.theme)
+    }
+
+    fn glyphs(&self) -> &'static Glyphs {
+        self.glyphs
+    }
+
+    fn t(&self) -> &'static L10n {
+        l10n(self.lang)
+    }
+
+    /// Favorites come first, as a second row for the same setting; both
+    /// rows edit the same field.
+    fn rows(&self) -> Vec<Row> {
+        let mut rows = Vec::new();
+        let favorites = self.favorites();
+        if !favorites.is_empty() {
+            rows.push(Row::Header(Group::Favorites));
+            if !self.is_collapsed(Group::Favorites) {
+                rows.extend(favorites.iter().map(|&i| Row::Setting(i)));
+            }
+            if self.favorites_only {
+                return rows;
+            }
+        }
+        for (i, def) in self.defs.iter().enumerate() {
+            let group = def.group();
+            if i == 0 || self.defs[i - 1].group() != group {
+                rows.push(Row::Header(group));
+            }
+            if !self.is_collapsed(group) {
+                rows.push(Row::Setting(i));
+            }
+        }
+        rows
+    }
+
+    /// Settings in the favorites section, in list order, once each.
+    fn favorites(&self) -> Vec<usize> {
+        (0..self.defs.len())
+            .filter(|&i| self.config.favorites.contains(&self.defs[i].field))
+            .collect()
+    }
+
+    /// Index of the first row of the right-hand column. Columns only break at
+    /// group headers, picking the boundary that balances the two heights best.
+    /// Returns `rows.len()` in single-column mode.
+    fn column_split(&self, rows: &[Row]) -> usize {
+        if !self.wide {
+            return rows.len();
+        }
+        (1..rows.len())
+            .filter(|&i| matches!(rows[i], Row::Header(_)))
+            .min_by_key(|&i| i.max(rows.len() - i))
+            .unwrap_or(rows.len())
+    }
+
+    /// Moves the cursor to the same line of the other column, or of column
+    /// `target` (0 = left, 1 = right) when given.
+    fn switch_column(&mut self, target: Option<usize>) {
+        let rows = self.rows();
+        let split = self.column_split(&rows);
+        if split == 0 || split >= rows.len() {
+            return;
+        }
+        let in_right = self.cursor >= split;
+        let go_right = target.map_or(!in_right, |c| c == 1);
+        if go_right && !in_right {
+            self.cursor = (split + self.cursor).min(rows.len() - 1);
+        } else if !go_right && in_right {
+            self.cursor = (self.cursor - split).min(split - 1);
+        }
+    }
+
+    fn selected_row(&self) -> Option<Row> {
+        self.rows().get(self.cursor).copied()
+    }
+
+    fn is_collapsed(&self, group: Group) -> bool {
+        self.config.collapsed.contains(&group)
+    }
+
+    fn group_len(&self, group: Group) -> usize {
+        match group {
+            Group::Favorites => self.favorites().len(),
+            _ => self.defs.iter().filter(|d| d.group() == group).count(),
+        }
+    }
+
+    fn toggle_group(&mut self, group: Group) {
+        if self.is_collapsed(group) {
+            self.config.collapsed.retain(|g| *g != group);
+        } else {
+            self.config.collapsed.push(group);
+        }
+        if let Err(e) = self.config.save() {
+            self.notify_failure(|t| &t.config_save_failed, e);
+        }
+    }
+
+    /// Switches between the compact layout and one blank line between rows.
+    fn toggle_spacing(&mut self) {
+        self.config.row_spacing = if self.config.row_spacing == 0 { 1 } else { 0 };
+        if let Err(e) = self.config.save() {
+            self.notify_failure(|t| &t.config_save_failed, e);
+        }
+    }
+
+    /// Switches quality tiers between the game's names and 1-5.
+    fn toggle_tier_names(&mut self) {
+        self.config.show_tier_names = !self.config.show_tier_names;
+        if let Err(e) = self.config.save() {
+            self.notify_failure(|t| &t.config_save_failed, e);
+        }
+    }
+
+    /// Switches the UI language. A configured language follows the choice;
+    /// otherwise the session remembers it on quit.
+    fn set_language(&mut self, lang: Lang) {
+        self.lang = lang;
+        self.reword_notices();
+        if self.config.language.is_some() {
+            self.config.set_language(Some(lang));
+            if let Err(e) = self.config.save() {
+                self.notify_failure(|t| &t.config_save_failed, e);
+            }
+        }
+    }
+
+    fn set_theme(&mut self, name: ThemeName) {
+        self.theme_override = None;
+        self.config.theme = name;
+        if let Err(e) = self.config.save() {
+            self.notify_failure(|t| &t.config_save_failed, e);
+        }
+    }
+
+    /// Step multiplier for held arrow keys: consecutive presses in the same
+    /// direction on the same row speed up; a pause or reversal resets it.
+    fn accelerate(&mut self, idx: usize, delta: isize) -> isize {
+        let now = Instant::now();
+        let count = match self.accel {
+            Some(a) if a.idx == idx && a.delta == delta && now - a.at < ACCEL_RESET => a.count + 1,
+            _ => 1,
+        };
+        self.accel = Some(Accel { idx, delta, count, at: now });
+        match count {
+            0..ACCEL_THRESHOLD => 1,
+            ACCEL_THRESHOLD..ACCEL_FAST => 2,
+            _ => 3,
+        }
+    }
+
+    /// In read-only mode, notes that the action was refused and returns true.
+    fn refuse_read_only(&mut self) -> bool {
+        if self.read_only {
+            self.notify(Severity::Warning, |t| t.read_only_refused.clone());
+        }
+        self.read_only
+    }
+
+    fn is_locked(&self, field: Field) -> bool {
+        self.config.locked.contains(&field)
+    }
+
+    /// Notes that `field` is locked and returns true.
+    fn refuse_locked(&mut self, field: Field) -> bool {
+        let locked = self.is_locked(field);
+        if locked {
+            self.notify(Severity::Info, |t| t.locked_refused.clone());
+        }
+        locked
+    }
+
+    /// Copies every locked field's current value into `candidate`, so bulk
+    /// changes (imports, level sets) leave them alone.
+    fn keep_locked(&self, candidate: &mut GraphicsSettings) {
+        for &field in &self.config.locked {
+            set_value(candidate, field, get_value(&self.settings, field));
+        }
+    }
+
+    /// B: pins or unpins the selected setting. The cursor follows the
+    /// setting to its usual place, since the favorites rows shift.
+    fn toggle_favorite(&mut self) {
+        let Some(Row::Setting(idx)) = self.selected_row() else {
+            return;
+        };
+        let field = self.defs[idx].field;
+        if self.config.favorites.contains(&field) {
+            self.config.favorites.retain(|f| *f != field);
+        } else {
+            self.config.favorites.push(field);
+        }
+        let rows = self.rows();
+        self.cursor = rows
+            .iter()
+            .rposition(|r| *r == Row::Setting(idx))
+            .unwrap_or(self.cursor.min(rows.len() - 1));
+        if let Err(e) = self.config.save() {
+            self.notify_failure(|t| &t.config_save_failed, e);
+        }
+    }
+
+    /// Shift+B: lists only the favorites section while there is one.
+    fn toggle_favorites_only(&mut self) {
+        self.favorites_only = !self.favorites_only;
+        self.cursor = self.cursor.min(self.rows().len() - 1);
+    }
+
+    /// F: locks or unlocks the selected setting; the set is saved to the config.
+    fn toggle_lock(&mut self) {
+        let Some(Row::Setting(idx)) = self.selected_row() else {
+            return;
+        };
+        let field = self.defs[idx].field;
+        if self.is_locked(field) {
+            self.config.locked.retain(|f| *f != field);
+        } else {
+            self.config.locked.push(field);
+        }
+        if let Err(e) = self.config.save() {
+            self.notify_failure(|t| &t.config_save_failed, e);
+        }
+    }
+
+    /// Space: marks or unmarks the selected row for batch edits. Only the
+    /// standard 1-5 quality selects can be marked, so one step means the same
+    /// thing on every marked row.
+    fn toggle_mark(&mut self) {
+        let idx = match self.selected_row() {
+            Some(Row::Setting(idx)) => idx,
+            Some(Row::Header(group)) => return self.toggle_group(group),
+            None => return,
+        };
+        let field = self.defs[idx].field;
+        if let Some(i) = self.marked.iter().position(|&f| f == field) {
+            self.marked.remove(i);
+        } else if is_quality_level(&self.defs[idx]) {
+            self.marked.push(field);
+        } else {
+            self.notify(Severity::Info, |t| t.mark_incompatible.clone());
+        }
+    }
+
+    /// ←/→ with rows marked: steps every marked row one level, stopping at
+    /// the ends instead of wrapping. Recorded as a single undo step.
+    fn cycle_marked(&mut self, delta: isize) {
+        if self.refuse_read_only() {
+            return;
+        }
+        let mut candidate = self.settings.clone();
+        for def in self.defs.iter().filter(|d| self.marked.contains(&d.field)) {
+            if self.is_locked(def.field) {
+                continue;
+            }
+            let pos = def.position(&candidate).unwrap_or(0) as isize;
+            let next = pos.saturating_add(delta).clamp(0, def.kind.len() as isize - 1);
+            def.set_position(&mut candidate, next as usize);
+        }
+        self.apply_change(candidate);
+    }
+
+    /// Moves `delta` options without wrapping, stopping at the first or last.
+    /// A custom value starts from the option nearest to it.
+    fn jump(&mut self, delta: isize) {
+        if !self.marked.is_empty() {
+            return self.cycle_marked(delta);
+        }
+        let Some(Row::Setting(idx)) = self.selected_row() else {
+            return;
+        };
+        if self.refuse_read_only() || self.refuse_locked(self.defs[idx].field) {
+            return;
+        }
+        let def = &self.defs[idx];
+        let pos = def.nearest_position(&self.settings) as isize;
+        let next = pos.saturating_add(delta).clamp(0, def.kind.len() as isize - 1);
+        let mut candidate = self.settings.clone();
+        def.set_position(&mut candidate, next as usize);
+        if self.safe_mode && blocking_conflicts(&candidate) > blocking_conflicts(&self.settings) {
+            self.notify(Severity::Info, |t| t.safe_mode_rejected.clone());
+            return;
+        }
+        let field = def.field;
+        self.apply_change(candidate);
+        self.after_change(field);
+    }
+
+    fn cycle(&mut self, delta: isize) {
+        if !self.marked.is_empty() {
+            return self.cycle_marked(delta);
+        }
+        let Some(Row::Setting(idx)) = self.selected_row() else {
+            return;
+        };
+        if self.refuse_read_only() || self.refuse_locked(self.defs[idx].field) {
+            return;
+        }
+        let len = self.defs[idx].kind.len() as isize;
+        // Short lists are quick to traverse anyway.
+        let step = if len >= ACCEL_MIN_OPTIONS { self.accelerate(idx, delta) } else { 1 };
+        let def = &self.defs[idx];
+        let pos = def.position(&self.settings).unwrap_or(0) as isize;
+        // A sped-up step stops at the first or last option rather than
+        // wrapping past it; from there the next press wraps as usual.
+        let delta = match (pos + delta * step).clamp(0, len - 1) - pos {
+            0 => delta,
+            clamped => clamped,
+        };
+        let baseline = blocking_conflicts(&self.settings);
+
+        // In safe mode, step past options that would introduce a new conflict.
+        for step in 1..len {
+            let next = (pos + delta * step).rem_euclid(len) as usize;
+            if next as isize == pos {
+                continue;
+            }
+            let mut candidate = self.settings.clone();
+            def.set_position(&mut candidate, next);
+            if !self.safe_mode || blocking_conflicts(&candidate) <= baseline {
+                let field = def.field;
+                self.apply_change(candidate);
+                self.after_change(field);
+                return;
+            }
+        }
+        self.notify(Severity::Info, |t| t.safe_mode_blocked.clone());
+    }
+
+    /// R: puts the selected setting back to the game default.
+    fn reset_field(&mut self) {
+        let Some(Row::Setting(idx)) = self.selected_row() else {
+            return;
+        };
+        let field = self.defs[idx].field;
+        if self.refuse_read_only() || self.refuse_locked(field) {
+            return;
+        }
+        let def = self.defs[idx].clone();
+        let default = get_value(&self.defaults, field);
+        let mut next = self.settings.clone();
+        set_value(&mut next, field, default);
+        self.apply_change(next);
+        self.notify(Severity::Success, move |t| {
+            t.fill(&t.reset_field, &[("label", &def.label(t)), ("value", &def.format(default, t))])
+        });
+    }
+
+    /// Shift+R (after confirmation): every unlocked setting back to the game default.
+    fn reset_all(&mut self) {
+        let mut next = self.settings.clone();
+        for def in &self.defs {
+            set_value(&mut next, def.field, get_value(&self.defaults, def.field));
+        }
+        self.keep_locked(&mut next);
+        let changed = diff_settings(&self.defs, &self.settings, &next).len();
+        self.apply_change(next);
+        self.notify(Severity::Success, move |t| t.fill(&t.reset_all, &[("n", &changed)]));
+    }
+
+    /// Backspace: drops the pending edit on the selected setting, restoring
+    /// the value last read from or written to the registry.
+    fn revert_field(&mut self) {
+        let Some(Row::Setting(idx)) = self.selected_row() else {
+            return;
+        };
+        let field = self.defs[idx].field;
+        if !self.is_pending(idx) || self.refuse_read_only() || self.refuse_locked(field) {
+            return;
+        }
+        let mut next = self.settings.clone();
+        set_value(&mut next, field, get_value(&self.original, field));
+        self.apply_change(next);
+        let def = self.defs[idx].clone();
+        self.notify(Severity::Info, move |t| t.fill(&t.reverted_field, &[("label", &def.label(t))]));
+    }
+
+    /// Shift+Backspace: drops every pending edit without re-reading the registry.
+    fn revert_all(&mut self) {
+        if !self.is_dirty() || self.refuse_read_only() {
+            return;
+        }
+        let changed = self.pending_changes().len();
+        let mut next = self.original.clone();
+        self.keep_locked(&mut next);
+        self.apply_change(next);
+        self.notify(Severity::Info, move |t| t.fill(&t.reverted_all, &[("n", &changed)]));
+    }
+
+    /// Y: copies the selected setting as it appears in the stored JSON,
+    /// e.g. `"ShadowQuality": 3`.
+    fn copy_value(&mut self) {
+        let Some(Row::Setting(idx)) = self.selected_row() else {
+            return;
+        };
+        let key = json_key(self.defs[idx].field);
+        let value = serde_json::to_value(&self.settings).ok().and_then(|v| v.get(key).cloned());
+        let Some(value) = value else {
+            return;
+        };
+        let text = format!("\"{key}\": {value}");
+        match clipboard::copy(&text) {
+            Ok(()) => self.notify(Severity::Success, move |t| t.fill(&t.copied, &[("text", &text)])),
+            Err(e) => self.notify_failure(|t| &t.copy_failed, e),
+        }
+    }
+
+    /// C: copies the full settings JSON, unknown keys included.
+    fn copy_json(&mut self) {
+        match settings_json(&self.settings, true) {
+            Ok(json) => self.copy_text(&json),
+            Err(e) => self.notify_failure(|t| &t.copy_failed, e),
+        }
+    }
+
+    /// Shift+C: copies the settings as a Markdown list, grouped like the screen.
+    fn copy_markdown(&mut self) {
+        self.copy_text(&markdown_summary(&self.defs, &self.settings, self.t()));
+    }
+
+    /// Shift+Y: copies the settings as a preset code.
+    fn copy_code(&mut self) {
+        let code = code::encode_code(&self.settings);
+        match clipboard::copy(&code) {
+            Ok(()) => self.notify(Severity::Success, move |t| t.fill(&t.code_copied, &[("code", &code)])),
+            Err(e) => self.notify_failure(|t| &t.copy_failed, e),
+        }
+    }
+
+    fn copy_text(&mut self, text: &str) {
+        match clipboard::copy(text) {
+            Ok(()) => {
+                let n = text.len();
+                self.notify(Severity::Success, move |t| t.fill(&t.copied_bytes, &[("n", &n)]));
+            }
+            Err(e) => self.notify_failure(|t| &t.copy_failed, e),
+        }
+    }
+
+    /// G: applies the preset suggested for this machine's GPU.
+    fn apply_suggested(&mut self) {
+        self.apply_preset(self.suggested);
+    }
+
+    /// The preset bound to a function key in the config's `[hotkeys]`.
+    fn hotkey_preset(&self, code: KeyCode) -> Option<Preset> {
+        let KeyCode::F(n) = code else {
+            return None;
+        };
+        self.config.hotkeys.get(&format!("F{n}")).copied()
+    }
+
+    /// The preset the current settings match exactly, if any.
+    fn matching_preset(&self) -> Option<Preset> {
+        Preset::ALL
+            .into_iter()
+            .find(|p| diff_settings(&self.defs, &self.settings, &p.apply(&self.defs, &self.settings)).is_empty())
+    }
+
+    fn apply_preset(&mut self, preset: Preset) {
+        if self.refuse_read_only() {
+            return;
+        }
+        let mut next = preset.apply(&self.defs, &self.settings);
+        self.keep_locked(&mut next);
+        let changed = diff_settings(&self.defs, &self.settings, &next).len();
+        self.apply_change(next);
+        self.notify(Severity::Success, move |t| {
+            t.fill(&t.preset_applied, &[("preset", &preset.label(t)), ("n", &changed)])
+        });
+    }
+
+    /// Sets every field offering exactly the standard 1-5 quality range.
+    fn set_all_quality(&mut self, level: i64) {
+        let mut next = self.settings.clone();
+        for def in self.defs.iter().filter(|d| is_quality_level(d)) {
+            set_i64(&mut next, def.field, level);
+        }
+        self.keep_locked(&mut next);
+        let changed = diff_settings(&self.defs, &self.settings, &next).len();
+        self.apply_change(next);
+        self.notify(Severity::Success, move |t| {
+            t.fill(&t.quality_level_set, &[("n", &changed), ("level", &level)])
+        });
+    }
+
+    /// Sets a `SelectI64` row directly to the option whose value is `value`
+    /// (the quality tiers are 1-5). Ignored when no such option exists.
+    fn set_tier(&mut self, value: i64) {
+        let Some(Row::Setting(idx)) = self.selected_row() else {
+            return;
+        };
+        if self.refuse_read_only() || self.refuse_locked(self.defs[idx].field) {
+            return;
+        }
+        let def = &self.defs[idx];
+        let SettingKind::SelectI64(opts) = &def.kind else {
+            return;
+        };
+        if !opts.iter().any(|(_, v)| *v == value) {
+            return;
+        }
+        let mut candidate = self.settings.clone();
+        set_i64(&mut candidate, def.field, value);
+        if self.safe_mode && blocking_conflicts(&candidate) > blocking_conflicts(&self.settings) {
+            self.notify(Severity::Info, |t| t.safe_mode_rejected.clone());
+            return;
+        }
+        let field = def.field;
+        self.apply_change(candidate);
+        self.after_change(field);
+    }
+
+    /// Every edit goes through here: replaces the settings and records the
+    /// changed fields as one undo step, dropping the redo history.
+    fn apply_change(&mut self, next: GraphicsSettings) {
+        if !self.is_dirty() {
+            self.autosave_from = Instant::now();
+        }
+        let deltas: Vec<Delta> = diff_settings(&self.defs, &self.settings, &next)
+            .iter()
+            .map(|def| Delta {
+                field: def.field,
+                old: get_value(&self.settings, def.field),
+                new: get_value(&next, def.field),
+            })
+            .collect();
+        self.settings = next;
+        if deltas.is_empty() {
+            return;
+        }
+        let t = self.t();
+        for d in &deltas {
+            let def = self.defs.iter().find(|def| def.field == d.field).expect("delta of a known field");
+            self.log(&format!("{} {} \u{2192} {}", def.label(t), def.format(d.old, t), def.format(d.new, t)));
+        }
+        self.redo.clear();
+        self.undo.push_back(deltas);
+        if self.undo.len() > UNDO_DEPTH {
+            self.undo.pop_front();
+        }
+    }
+
+    /// Ctrl+Z / Ctrl+Y: steps back or forward one edit.
+    fn undo_redo(&mut self, redo: bool) {
+        let step = if redo { self.redo.pop() } else { self.undo.pop_back() };
+        let Some(deltas) = step else {
+            self.notify(Severity::Info, move |t| if redo { &t.nothing_to_redo } else { &t.nothing_to_undo }.to_string());
+            return;
+        };
+        let mut next = self.settings.clone();
+        for d in &deltas {
+            set_value(&mut next, d.field, if redo { d.new } else { d.old });
+        }
+        self.keep_locked(&mut next);
+        self.settings = next;
+        let single = match deltas.as_slice() {
+            [d] => {
+                let def = self.defs.iter().find(|def| def.field == d.field).expect("delta of a known field");
+                let (from, to) = if redo { (d.old, d.new) } else { (d.new, d.old) };
+                Some((def.clone(), from, to))
+            }
+            _ => None,
+        };
+        let n = deltas.len();
+        self.notify(Severity::Info, move |t| {
+            let change = match &single {
+                Some((def, from, to)) => {
+                    format!("{} {} \u{2192} {}", def.label(t), def.format(*from, t), def.format(*to, t))
+                }
+                None => t.fill(&t.n_changes, &[("n", &n)]),
+            };
+            t.fill(if redo { &t.redid } else { &t.undid }, &[("change", &change)])
+        });
+        if redo {
+            self.undo.push_back(deltas);
+        } else {
+            self.redo.push(deltas);
+        }
+    }
+
+    /// Follow-up checks after the user edits a field.
+    fn after_change(&mut self, field: Field) {
+        if field == Field::Fps
+            && self.config.fps_vsync_autofix
+            && !self.is_locked(Field::VSync)
+            && validate(&self.settings, self.refresh_rate).iter().any(|c| matches!(c, Conflict::VsyncFpsCap(_)))
+        {
+            self.modal = Some(Modal::Confirm(Confirm::DisableVsync));
+        }
+    }
+
+    /// Routes a key press: the message and help overlays, dialogs and the
+    /// JSON editor take it first, then preset hotkeys, then the keymap.
+    fn handle_key(&mut self, key: KeyEvent) {
+        if self.show_message {
+            self.show_message = false;
+            return;
+        }
+        if self.busy() {
+            // Edits would race the in-flight read/write; only allow quitting.
+            // A save is waited for, a read can be abandoned.
+            let quit = matches!(key.code, KeyCode::Char('q') | KeyCode::Esc);
+            if self.saving {
+                match self.keymap.action_for(key) {
+                    _ if quit => self.quit_after_pending_save(),
+                    Some(Action::SaveAndQuit) => self.quit_after_pending_save(),
+                    Some(Action::Save) => self.notify(Severity::Info, |t| t.save_in_progress.clone()),
+                    _ => {}
+                }
+            } else if quit {
+                self.quit = true;
+            }
+            return;
+        }
+        if self.show_help {
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('?') | KeyCode::F(1) => self.show_help = false,
+                KeyCode::Up | KeyCode::Char('k') => self.scroll_help(-1),
+                KeyCode::Down | KeyCode::Char('j') => self.scroll_help(1),
+                KeyCode::PageUp => self.scroll_help(-10),
+                KeyCode::PageDown => self.scroll_help(10),
+                _ => {}
+            }
+            return;
+        }
+        if self.modal.is_some() {
+            self.modal_key(key.code);
+            return;
+        }
+        if self.json_edit.is_some() {
+            self.json_edit_key(key);
+            return;
+        }
+        // Configured hotkeys override the default binding of the same key.
+        if let Some(preset) = self.hotkey_preset(key.code) {
+            self.apply_preset(preset);
+            return;
+        }
+        if let Some(action) = self.keymap.action_for(key) {
+            self.handle(action);
+        }
+    }
+
+    /// Carries out a settings-list action.
+    fn handle(&mut self, action: Action) {
+        match action {
+            Action::MoveUp => {
+                if self.cursor > 0 {
+                    self.cursor -= 1;
+                } else if self.config.wrap_navigation {
+                    self.cursor = self.rows().len().saturating_sub(1);
+                }
+            }
+            Action::MoveDown => {
+                if self.cursor + 1 < self.rows().len() {
+                    self.cursor += 1;
+                } else if self.config.wrap_navigation {
+                    self.cursor = 0;
+                }
+            }
+            Action::ToggleMark => self.toggle_mark(),
+            Action::Activate => match self.selected_row() {
+                Some(Row::Header(group)) => self.toggle_group(group),
+                Some(Row::Setting(idx)) if matches!(self.defs[idx].kind, SettingKind::Toggle) => {
+                    self.cycle(1);
+                }
+                _ => {}
+            },
+            Action::SwitchColumn => self.switch_column(None),
+            Action::LeftColumn => self.switch_column(Some(0)),
+            Action::RightColumn => self.switch_column(Some(1)),
+            Action::ScrollPreviewUp if self.show_preview => self.scroll_preview(-5),
+            Action::ScrollPreviewDown if self.show_preview => self.scroll_preview(5),
+            Action::ScrollPreviewUp if self.show_log => self.scroll_log(5),
+            Action::ScrollPreviewDown if self.show_log => self.scroll_log(-5),
+            Action::ScrollPreviewUp | Action::ScrollPreviewDown => {}
+            Action::CycleNext => self.cycle(1),
+            Action::CyclePrev => self.cycle(-1),
+            Action::CycleNextCoarse => self.jump(COARSE_STEP),
+            Action::CyclePrevCoarse => self.jump(-COARSE_STEP),
+            Action::CycleMax => self.jump(isize::MAX),
+            Action::CycleMin => self.jump(isize::MIN),
+            Action::SetTier(tier) => self.set_tier(tier.into()),
+            Action::SetAllQuality => {
+                if !self.refuse_read_only() {
+                    self.modal = Some(Modal::Choice(ChoiceKind::QualityLevel, 2));
+                }
+            }
+            Action::ResetField => self.reset_field(),
+            Action::ResetAll => {
+                if !self.refuse_read_only() {
+                    self.modal = Some(Modal::Confirm(Confirm::ResetAll));
+                }
+            }
+            Action::RevertField => self.revert_field(),
+            Action::RevertAll => self.revert_all(),
+            Action::Undo => self.undo_redo(false),
+            Action::Redo => self.undo_redo(true),
+            Action::ApplySuggested => self.apply_suggested(),
+            Action::ToggleSafeMode => self.toggle_safe_mode(),
+            Action::ToggleLock => self.toggle_lock(),
+            Action::ToggleFavorite => self.toggle_favorite(),
+            Action::FavoritesOnly => self.toggle_favorites_only(),
+            Action::EditJson => self.open_json_edit(),
+            Action::SetValue => self.open_prompt(PromptKind::SetValue),
+            Action::Save => self.request_save(),
+            Action::SaveAndQuit => self.save_and_quit(),
+            Action::MergeImport => self.open_prompt(PromptKind::MergeImport),
+            Action::Reload => self.request_reload(),
+            Action::Export => self.open_prompt(PromptKind::Export(ExportFormat::Json)),
+            Action::CopyValue => self.copy_value(),
+            Action::CopyJson => self.copy_json(),
+            Action::CopyCode => self.copy_code(),
+            Action::ApplyCode => self.open_prompt(PromptKind::PresetCode),
+            Action::CopyMarkdown => self.copy_markdown(),
+            Action::ToggleRecommended => self.toggle_recommended(),
+            Action::ToggleDescription => self.show_description = !self.show_description,
+            Action::ToggleDefaults => self.show_defaults = !self.show_defaults,
+            Action::ToggleJsonKeys => self.show_json_keys = !self.show_json_keys,
+            Action::TogglePreview => {
+                self.show_preview = !self.show_preview;
+                self.preview_scroll = 0;
+            }
+            Action::ToggleLog => {
+                self.show_log = !self.show_log;
+                self.log_scroll = 0;
+            }
+            Action::ToggleSpacing => self.toggle_spacing(),
+            Action::ToggleTierNames => self.toggle_tier_names(),
+            Action::Preferences => self.modal = Some(Modal::Preferences(0)),
+            Action::ShowMessage => self.show_message = self.last_notice.is_some(),
+            Action::ChooseTheme => {
+                let current = ThemeName::ALL.iter().position(|&n| n == self.theme_name()).unwrap_or(0);
+                self.modal = Some(Modal::Choice(ChoiceKind::Theme, current));
+            }
+            Action::ChooseLanguage => {
+                let current = Lang::all().iter().position(|&l| l == self.lang).unwrap_or(0);
+                self.modal = Some(Modal::Choice(ChoiceKind::Language, current));
+            }
+            Action::Help => {
+                self.show_help = true;
+                self.help_scroll = 0;
+            }
+            Action::Back if self.has_sticky_toast() => self.dismiss_toast(),
+            Action::Back if self.show_preview => self.show_preview = false,
+            Action::Back if !self.marked.is_empty() => self.marked.clear(),
+            Action::Back | Action::Quit => self.request_quit(),
+        }
+    }
+
+    /// Feeds a key to the open modal.
+    fn modal_key(&mut self, code: KeyCode) {
+        let t = self.t();
+        match self.modal.as_mut() {
+            None => {}
+            Some(Modal::Confirm(confirm)) => {
+                let confirm = *confirm;
+                match code {
+                    KeyCode::Char('y') | KeyCode::Enter => self.answer_confirm(confirm, true),
+                    KeyCode::Char('n') | KeyCode::Esc => self.answer_confirm(confirm, false),
+                    _ => {}
+                }
+            }
+            Some(Modal::Choice(kind, selected)) => {
+                let kind = *kind;
+                let options = kind.options(t);
+                let n = options.len();
+                match code {
+                    KeyCode::Left | KeyCode::Up | KeyCode::BackTab => *selected = (*selected + n - 1) % n,
+                    KeyCode::Right | KeyCode::Down | KeyCode::Tab => *selected = (*selected + 1) % n,
+                    KeyCode::Enter => {
+                        let answer = *selected;
+                        self.answer_choice(kind, answer);
+                    }
+                    KeyCode::Esc => self.modal = None,
+                    KeyCode::Char(c) => {
+                        if let Some(answer) = options.iter().position(|(key, _)| *key == c) {
+                            self.answer_choice(kind, answer);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Some(Modal::Input(prompt)) => match code {
+                KeyCode::Esc => self.modal = None,
+                KeyCode::Backspace => {
+                    prompt.input.pop();
+                    prompt.error = None;
+                }
+                KeyCode::Char(c) => {
+                    prompt.input.push(c);
+                    prompt.error = None;
+                }
+                KeyCode::Tab => {
+                    if let PromptKind::Export(format) = prompt.kind {
+                        prompt.kind = PromptKind::Export(format.next());
+                    }
+                }
+                KeyCode::Enter => {
+                    if let Some(Modal::Input(prompt)) = self.modal.take() {
+                        self.submit_prompt(prompt);
+                    }
+                }
+                _ => {}
+            },
+            Some(Modal::Preferences(selected)) => {
+                let n = Pref::ALL.len();
+                let pref = Pref::ALL[*selected];
+                match code {
+                    KeyCode::Up | KeyCode::Char('k') => *selected = (*selected + n - 1) % n,
+                    KeyCode::Down | KeyCode::Char('j') => *selected = (*selected + 1) % n,
+                    KeyCode::Right | KeyCode::Char('l') | KeyCode::Enter | KeyCode::Char(' ') => {
+                        self.cycle_pref(pref, 1);
+                    }
+                    KeyCode::Left | KeyCode::Char('h') => self.cycle_pref(pref, -1),
+                    KeyCode::Esc | KeyCode::Char(',') | KeyCode::Char('q') => self.modal = None,
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    /// How a preference's current value reads on the preferences screen.
+    fn pref_value(&self, pref: Pref) -> String {
+        let t = self.t();
+        let on_off = |on: bool| if on { &t.on } else { &t.off }.to_string();
+        match pref {
+            Pref::Language => match self.config.language() {
+                Some(lang) if lang.missing() > 0 => {
+                    format!("{} {}", lang.name(), t.fill(&t.pref_untranslated, &[("n", &lang.missing())]))
+                }
+                lang => lang.map_or(t.pref_ask.as_str(), Lang::name).to_string(),
+            },
+            Pref::Theme => {
+                let i = ThemeName::ALL.iter().position(|&n| n == self.theme_name()).unwrap_or(0);
+                ChoiceKind::Theme.options(t)[i].1.to_string()
+            }
+            Pref::SkipLanguagePicker => on_off(self.config.skip_language_picker),
+            Pref::CategoryColors => on_off(self.config.category_colors),
+            Pref::TierNames => on_off(self.config.show_tier_names),
+            Pref::WrapNavigation => on_off(self.config.wrap_navigation),
+            Pref::ConfirmSaves => on_off(!self.config.skip_save_confirmation),
+            Pref::VsyncAutofix => on_off(self.config.fps_vsync_autofix),
+            Pref::SyncClients => on_off(self.config.sync_clients),
+            Pref::RowSpacing => self.config.row_spacing.to_string(),
+            Pref::Autosave => match self.config.autosave_secs {
+                0 => t.off.to_string(),
+                n => t.fill(&t.pref_seconds, &[("n", &n)]),
+            },
+        }
+    }
+
+    /// Steps a preference through its values, applies it to the running
+    /// app and saves the config.
+    fn cycle_pref(&mut self, pref: Pref, delta: isize) {
+        // Index of the next entry in a list of `len`, wrapping at both ends.
+        let step = |i: usize, len: usize| (i as isize + delta).rem_euclid(len as isize) as usize;
+        let config = &mut self.config;
+        match pref {
+            Pref::Language => {
+                let choices: Vec<Option<Lang>> =
+                    std::iter::once(None).chain(Lang::all().into_iter().map(Some)).collect();
+                let i = choices.iter().position(|&l| l == config.language()).unwrap_or(0);
+                config.set_language(choices[step(i, choices.len())]);
+                if let Some(lang) = config.language() {
+                    self.lang = lang;
+                    self.reword_notices();
+                }
+            }
+            Pref::Theme => {
+                let current = self.theme_override.take().unwrap_or(config.theme);
+                let i = ThemeName::ALL.iter().position(|&n| n == current).unwrap_or(0);
+                config.theme = ThemeName::ALL[step(i, ThemeName::ALL.len())];
+            }
+            Pref::SkipLanguagePicker => config.skip_language_picker = !config.skip_language_picker,
+            Pref::CategoryColors => config.category_colors = !config.category_colors,
+            Pref::TierNames => config.show_tier_names = !config.show_tier_names,
+            Pref::WrapNavigation => config.wrap_navigation = !config.wrap_navigation,
+            Pref::ConfirmSaves => {
+                config.skip_save_confirmation = !config.skip_save_confirmation;
+                self.confirm_saves = !config.skip_save_confirmation;
+            }
+            Pref::VsyncAutofix => config.fps_vsync_autofix = !config.fps_vsync_autofix,
+            Pref::SyncClients => config.sync_clients = !config.sync_clients,
+            Pref::RowSpacing => config.row_spacing = step(config.row_spacing as usize, 3) as u16,
+            Pref::Autosave => {
+                // A hand-written interval steps from the nearest choice below it.
+                let i = AUTOSAVE_CHOICES.iter().rposition(|&n| n <= config.autosave_secs).unwrap_or(0);
+                let i = if AUTOSAVE_CHOICES[i] == config.autosave_secs || delta > 0 {
+                    step(i, AUTOSAVE_CHOICES.len())
+                } else {
+                    i
+                };
+                config.autosave_secs = AUTOSAVE_CHOICES[i];
+                self.autosave_from = Instant::now();
+            }
+        }
+        if let Err(e) = self.config.save() {
+            self.notify_failure(|t| &t.config_save_failed, e);
+        }
+    }
+
+    /// Closes the confirmation and acts on the answer.
+    fn answer_confirm(&mut self, confirm: Confirm, yes: bool) {
+        self.modal = None;
+        if !yes {
+            return;
+        }
+        match confirm {
+            Confirm::DisableVsync => {
+                if !self.refuse_locked(Field::VSync) {
+                    let mut next = self.settings.clone();
+                    next.enable_vsync = false;
+                    self.apply_change(next);
+                }
+            }
+            Confirm::DiscardJsonEdit => self.json_edit = None,
+            Confirm::SaveChanges => self.save(),
+            Confirm::ResetAll => self.reset_all(),
+            Confirm::MigrateLegacy => {
+                if let Some(name) = self.legacy_value.take() {
+                    self.run_io(move || IoDone::Migrate(name.clone(), migrate_legacy(&name)));
+                }
+            }
+            Confirm::Reload => self.reload(),
+        }
+    }
+
+    /// Closes the choice and acts on the answer at `index` in `kind.options`.
+    fn answer_choice(&mut self, kind: ChoiceKind, index: usize) {
+        self.modal = None;
+        match (kind, index) {
+            (ChoiceKind::Quit, 0) => self.save_and_quit(),
+            (ChoiceKind::Quit, 1) => self.quit = true,
+            (ChoiceKind::Quit, _) => {}
+            (ChoiceKind::QualityLevel, i) => self.set_all_quality(i as i64 + 1),
+            (ChoiceKind::Theme, i) => self.set_theme(ThemeName::ALL[i]),
+            (ChoiceKind::Language, i) => self.set_language(Lang::all()[i]),
+            (ChoiceKind::SyncClients, 0) => self.sync_clients(Client::Global, Client::Cn),
+            (ChoiceKind::SyncClients, 1) => self.sync_clients(Client::Cn, Client::Global),
+            (ChoiceKind::SyncClients, _) => self.client_values = None,
+            (ChoiceKind::ChangedOutside, i) => {
+                let current = self.changed_outside.take();
+                match (i, current) {
+                    (0, Some(current)) => {
+                        self.quit_after_save = false;
+                        self.merge_outside_changes(current);
+                    }
+                    (1, _) => self.write(false),
+                    _ => self.quit_after_save = false,
+                }
+            }
+        }
+    }
+
+    fn open_json_edit(&mut self) {
+        if self.refuse_read_only() {
+            return;
+        }
+        let initial = settings_json(&self.settings, true).unwrap_or_default();
+        self.json_edit = Some(JsonEdit { area: TextArea::new(&initial), initial, error: None });
+    }
+
+    fn json_edit_key(&mut self, key: KeyEvent) {
+        let Some(edit) = self.json_edit.as_mut() else {
+            return;
+        };
+        let area = &mut edit.area;
+        match key.code {
+            KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.apply_json_edit();
+            }
+            KeyCode::Esc => {
+                if area.text() == edit.initial {
+                    self.json_edit = None;
+                } else {
+                    self.modal = Some(Modal::Confirm(Confirm::DiscardJsonEdit));
+                }
+            }
+            KeyCode::Char(c) => area.insert(c),
+            KeyCode::Tab => {
+                area.insert(' ');
+                area.insert(' ');
+            }
+            KeyCode::Enter => area.newline(),
+            KeyCode::Backspace => area.backspace(),
+            KeyCode::Delete => area.delete(),
+            KeyCode::Left => area.left(),
+            KeyCode::Right => area.right(),
+            KeyCode::Up => area.vertical(-1),
+            KeyCode::Down => area.vertical(1),
+            KeyCode::PageUp => area.vertical(-10),
+            KeyCode::PageDown => area.vertical(10),
+            KeyCode::Home => area.home(),
+            KeyCode::End => area.end(),
+            _ => {}
+        }
+    }
+
+    /// Parses the edited text; on success it replaces the in-memory settings,
+    /// otherwise the error is shown and the cursor moves to its location.
+    /// Locked fields keep their values, and in safe mode the edit is kept
+    /// open when it would add a blocking conflict.
+    fn apply_json_edit(&mut self) {
+        let t = self.t();
+        let Some(text) = self.json_edit.as_ref().map(|e| e.area.text()) else {
+            return;
+        };
+        let error = match serde_json::from_str::<GraphicsSettings>(&text) {
+            Ok(mut settings) => {
+                self.keep_locked(&mut settings);
+                if !self.safe_mode || blocking_conflicts(&settings) <= blocking_conflicts(&self.settings) {
+                    self.apply_change(settings);
+                    self.json_edit = None;
+                    self.notify(Severity::Success, |t| t.json_applied.clone());
+                    return;
+                }
+                let conflicts: Vec<&str> = validate(&settings, self.refresh_rate)
+                    .into_iter()
+                    .filter(|c| !c.is_warning())
+                    .map(|c| c.message(t))
+                    .collect();
+                format!("{} {}", t.safe_mode_rejected, conflicts.join(" "))
+            }
+            Err(e) => {
+                if let Some(edit) = self.json_edit.as_mut() {
+                    edit.area.set_cursor(e.line().saturating_sub(1), e.column().saturating_sub(1));
+                }
+                t.failure(&t.json_invalid, e)
+            }
+        };
+        if let Some(edit) = self.json_edit.as_mut() {
+            edit.error = Some(error);
+        }
+    }
+
+    fn toggle_recommended(&mut self) {
+        self.show_recommended = !self.show_recommended;
+        let shown = self.show_recommended;
+        self.notify(Severity::Info, move |t| if shown { &t.recommended_shown } else { &t.recommended_hidden }.to_string());
+    }
+
+    /// Settings whose in-memory value differs from the registry snapshot.
+    fn pending_changes(&self) -> Vec<&SettingDef> {
+        diff_settings(&self.defs, &self.settings, &self.original)
+    }
+
+    /// Whether the in-memory settings differ from the last read or save.
+    /// Every key counts, including those without a row and unknown ones,
+    /// so raw JSON edits to them are still saved.
+    fn is_dirty(&self) -> bool {
+        serde_json::to_value(&self.settings).ok() != serde_json::to_value(&self.original).ok()
+    }
+
+    fn is_pending(&self, idx: usize) -> bool {
+        field_differs(&self.settings, &self.original, self.defs[idx].field)
+    }
+
+    fn is_modified_from_default(&self, idx: usize) -> bool {
+        field_differs(&self.settings, &self.defaults, self.defs[idx].field)
+    }
+
+    /// Whether the given setting currently holds its recommended value.
+    fn is_recommended(&self, idx: usize) -> bool {
+        let field = self.defs[idx].field;
+        recommended_value(field).is_some_and(|r| r.same(get_value(&self.settings, field)))
+    }
+
+    fn toggle_safe_mode(&mut self) {
+        self.safe_mode = !self.safe_mode;
+        let on = self.safe_mode;
+        self.notify(Severity::Info, move |t| if on { &t.safe_mode_on } else { &t.safe_mode_off }.to_string());
+    }
+
+    /// S: reviews pending changes before writing, or skips the write when
+    /// there is nothing to save.
+    fn request_save(&mut self) {
+        if self.refuse_read_only() {
+            return;
+        }
+        // Creating the key is worth saving even without edits, and always
+        // goes through the review so the step is spelled out.
+        if !self.is_dirty() && !self.key_missing {
+            self.notify(Severity::Info, |t| t.no_changes.clone());
+        } else if self.confirm_saves || self.key_missing {
+            self.modal = Some(Modal::Confirm(Confirm::SaveChanges));
+        } else {
+            self.save();
+        }
+    }
+
+    fn save(&mut self) {
+        self.write(true);
+    }
+
+    /// With `check`, re-reads the value the write would replace, in the
+    /// hive saves go to, and holds the write back when it no longer matches
+    /// what was loaded, e.g. because the game ran and saved its own changes
+    /// in the meantime.
+    fn write(&mut self, check: bool) {
+        let settings = self.settings.clone();
+        let original = self.original.clone();
+        let append_nul = self.append_nul;
+        let hive = self.hive;
+        let store = self.store.clone();
+        self.saving = true;
+        self.run_io(move || {
+            if check
+                && let ReadResult::Found(current) = store.read_hive(hive)
+                && serde_json::to_value(&current.settings).ok() != serde_json::to_value(&original).ok()
+            {
+                return IoDone::ChangedOutside(current.settings);
+            }
+            let result = store.write(&settings, append_nul, hive);
+            IoDone::Write(settings.clone(), result)
+        });
+    }
+
+    /// Q during a save: quits once it succeeds. A failed save, or one held
+    /// back by outside changes, keeps the app open as S and quit would.
+    fn quit_after_pending_save(&mut self) {
+        if !self.quit_after_save {
+            self.quit_after_save = true;
+            self.notify(Severity::Info, |t| t.quit_when_saved.clone());
+        }
+    }
+
+    /// Takes `current` as the new baseline and replays the pending edits on
+    /// top of it; where both changed a setting, the edit wins.
+    fn merge_outside_changes(&mut self, current: GraphicsSettings) {
+        let mut merged = current.clone();
+        let edits = self.pending_changes();
+        for def in &edits {
+            set_value(&mut merged, def.field, get_value(&self.settings, def.field));
+        }
+        if self.settings.enable_metal_fxsu != self.original.enable_metal_fxsu {
+            merged.enable_metal_fxsu = self.settings.enable_metal_fxsu;
+        }
+        if self.settings.enable_half_res_transparent != self.original.enable_half_res_transparent {
+            merged.enable_half_res_transparent = self.settings.enable_half_res_transparent;
+        }
+        for (key, value) in &self.settings.extra {
+            if self.original.extra.get(key) != Some(value) {
+                merged.extra.insert(key.clone(), value.clone());
+            }
+        }
+        for key in self.original.extra.keys() {
+            if !self.settings.extra.contains_key(key) {
+                merged.extra.remove(key);
+            }
+        }
+        let n = edits.len();
+        self.original = current;
+        self.settings = merged;
+        self.notify(Severity::Info, move |t| t.fill(&t.external_merged, &[("n", &n)]));
+    }
+
+    /// F5: reads the registry again, asking first when that would discard
+    /// unsaved changes.
+    fn request_reload(&mut self) {
+        if self.is_dirty() {
+            self.modal = Some(Modal::Confirm(Confirm::Reload));
+        } else {
+            self.reload();
+        }
+    }
+
+    fn reload(&mut self) {
+        let store = self.store.clone();
+        self.run_io(move || IoDone::Reload(store.read()));
+    }
+
+    /// Q/Esc: quits, or asks first when there are unsaved changes.
+    fn request_quit(&mut self) {
+        if !self.is_dirty() {
+            self.quit = true;
+        } else {
+            self.modal = Some(Modal::Choice(ChoiceKind::Quit, 0));
+        }
+    }
+
+    /// Ctrl+S/W: saves without review and quits once the write lands.
+    fn save_and_quit(&mut self) {
+        if !self.is_dirty() {
+            self.quit = true;
+        } else {
+            self.quit_after_save = true;
+            self.save();
+        }
+    }
+
+    fn scroll_preview(&mut self, delta: i32) {
+        let lines = settings_json(&self.settings, true).map_or(0, |j| j.lines().count());
+        let max = lines.saturating_sub(1) as i32;
+        self.preview_scroll = (self.preview_scroll as i32 + delta).clamp(0, max) as u16;
+    }
+
+    fn scroll_help(&mut self, delta: i32) {
+        let max = help_lines(self).len().saturating_sub(1) as i32;
+        self.help_scroll = (self.help_scroll as i32 + delta).clamp(0, max) as u16;
+    }
+
+    fn open_prompt(&mut self, kind: PromptKind) {
+        if matches!(kind, PromptKind::MergeImport | PromptKind::SetValue | PromptKind::PresetCode)
+            && self.refuse_read_only()
+        {
+            return;
+        }
+        self.modal = Some(Modal::Input(Prompt { kind, input: String::new(), error: None }));
+    }
+
+    fn submit_prompt(&mut self, prompt: Prompt) {
+        match prompt.kind {
+            PromptKind::MergeImport => self.merge_import(prompt.input.trim().trim_matches('"')),
+            PromptKind::Export(format) => self.export(prompt.input.trim().trim_matches('"'), format),
+            PromptKind::SetValue => {
+                if let Err(error) = self.set_typed_value(&prompt.input) {
+                    self.modal = Some(Modal::Input(Prompt { error: Some(error), ..prompt }));
+                }
+            }
+            PromptKind::PresetCode => {
+                if let Err(error) = self.apply_code(&prompt.input) {
+                    self.modal = Some(Modal::Input(Prompt { error: Some(error), ..prompt }));
+                }
+            }
+        }
+    }
+
+    /// Stages every setting a preset code carries, except locked ones.
+    /// A code with a value outside a setting's options is refused whole.
+    fn apply_code(&mut self, input: &str) -> Result<(), String> {
+        let t = self.t();
+        let decoded = code::decode_code(input).map_err(|e| match e {
+            CodeError::NotACode => t.code_not_a_code.to_string(),
+            CodeError::BadCharacter(c) => t.fill(&t.code_bad_character, &[("c", &c)]),
+            CodeError::Newer(n) => t.fill(&t.code_newer, &[("n", &n)]),
+            CodeError::UnknownVersion(n) => t.fill(&t.code_unknown_version, &[("n", &n)]),
+            CodeError::Truncated => t.code_truncated.to_string(),
+            CodeError::Damaged => t.code_damaged.to_string(),
+        })?;
+        // Custom values are staged like an import's; only ones the game
+        // can't use are refused.
+        if let Some(def) = self.defs.iter().find(|def| !def.accepts(get_value(&decoded, def.field))) {
+            let value = def.format(get_value(&decoded, def.field), t);
+            return Err(t.fill(&t.code_out_of_range, &[("setting", &def.label(t)), ("value", &value)]));
+        }
+        let mut candidate = self.settings.clone();
+        for def in &self.defs {
+            set_value(&mut candidate, def.field, get_value(&decoded, def.field));
+        }
+        self.keep_locked(&mut candidate);
+        let changed = diff_settings(&self.defs, &self.settings, &candidate).len();
+        self.apply_change(candidate);
+        self.notify(Severity::Success, move |t| t.fill(&t.code_applied, &[("n", &changed)]));
+        Ok(())
+    }
+
+    /// Applies a typed assignment with the same checks as cycling: locks
+    /// and, in safe mode, blocking conflicts.
+    fn set_typed_value(&mut self, input: &str) -> Result<(), String> {
+        let t = self.t();
+        let (idx, value) = parse_assignment(&self.defs, input, t)?;
+        let field = self.defs[idx].field;
+        if self.is_locked(field) {
+            return Err(t.locked_refused.to_string());
+        }
+        let mut candidate = self.settings.clone();
+        set_value(&mut candidate, field, value);
+        if self.safe_mode && blocking_conflicts(&candidate) > blocking_conflicts(&self.settings) {
+            return Err(t.safe_mode_rejected.to_string());
+        }
+        self.apply_change(candidate);
+        self.after_change(field);
+        Ok(())
+    }
+
+    /// Writes the in-memory settings (saved or not) to a file.
+    fn export(&mut self, path: &str, format: ExportFormat) {
+        let result = format
+            .render(&self.settings)
+            .map_err(io::Error::other)
+            .and_then(|text| std::fs::write(path, text));
+        match result {
+            Ok(()) => {
+                let path = path.to_string();
+                self.notify(Severity::Success, move |t| t.fill(&t.exported, &[("path", &path)]));
+            }
+            Err(e) => self.notify_failure(|t| &t.export_failed, e),
+        }
+    }
+
+    /// Merges a partial JSON file into the in-memory settings without saving.
+    fn merge_import(&mut self, path: &str) {
+        match read_json_value(path) {
+            Ok(overlay) => {
+                let mut merged = merge_settings(self.settings.clone(), overlay);
+                self.keep_locked(&mut merged);
+                let changed = diff_settings(&self.defs, &self.settings, &merged).len();
+                self.apply_change(merged);
+                self.notify(Severity::Success, move |t| t.fill(&t.merged, &[("n", &changed)]));
+            }
+            Err(e) => self.notify_failure(|t| &t.import_failed, e),
+        }
+    }
+
+    /// On/off settings read as a switch, `[ On ●]` or `[● Off]`, so they
+    /// stand apart from multi-value settings. Quality tiers read as the
+    /// game names them unless `show_tier_names` is off.
+    fn value_display(&self, idx: usize) -> String {
+        let def = &self.defs[idx];
+        let t = self.t();
+        let knob = self.glyphs().knob;
+        let value = get_value(&self.settings, def.field);
+        match def.switch_state(value) {
+            Some(on) if self.accessible => if on { "[ON]" } else { "[OFF]" }.to_string(),
+            Some(true) => format!("[ {} {knob}]", t.on),
+            Some(false) => format!("[{knob} {} ]", t.off),
+            None => match value {
+                Value::Int(level) if self.config.show_tier_names && is_quality_level(def) => {
+                    t.tier(level).map_or_else(|| def.format(value, t), str::to_string)
+                }
+                _ => def.format(value, t),
+            },
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Language picker
+// ---------------------------------------------------------------------------
+
+/// The picker, listing the built-in languages and then `community`, the
+/// names of the installed translation files.
+fn draw_lang_picker(frame: &mut Frame, community: &[&str], cursor: usize, theme: &Theme, glyphs: &Glyphs) {
+    let built_in = [
+        "English",
+        "한국어 (Korean)",
+        "日本語 (Japanese)",
+        "Русский (Russian)",
+        "Español (Spanish)",
+        "Português (Brasil) (Portuguese)",
+    ];
+    let options: Vec<&str> = built_in.into_iter().chain(community.iter().copied()).collect();
+
+    // Title, blank, options, blank, hint, and the two borders.
+    let area = frame.area();
+    let [_, center, _] = Layout::vertical([
+        Constraint::Fill(1),
+        Constraint::Length(options.len() as u16 + 6),
+        Constraint::Fill(1),
+    ])
+    .areas(area);
     let [_, box_area, _] = Layout::horizontal([
         Constraint::Fill(1),
-        Constraint::Length(34),
+        Constraint::Length(52),
+        Constraint::Fill(1),
+    ])
+    .areas(center);
+
+    let mut lines: Vec<Line> = vec![
+        Line::from(Span::styled(
+            "  Select Language / 언어 선택 / 言語選択 / Язык",
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+
+    for (i, label) in options.iter().enumerate() {
+        let selected = i == cursor;
+        let pointer = if selected { glyphs.pointer } else { "  " };
+        let style = if selected {
+            Style::default().fg(theme.highlight).add_modifier(Modifier::BOLD | theme.selected)
+        } else {
+            Style::default().fg(theme.text)
+        };
+        // Digits run out after nine; the rest are reached with the arrows.
+        let key = if i < 9 { format!("[{}]", i + 1) } else { "   ".to_string() };
+        lines.push(Line::from(vec![
+            Span::styled(pointer, style),
+            Span::styled(format!("{key} {label}"), style),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "  Enter to confirm",
+        Style::default().fg(theme.muted),
+    )));
+
+    let block = Block::default().borders(Borders::ALL);
+    frame.render_widget(Paragraph::new(lines).block(block), box_area);
+}
+
+fn pick_language(
+    terminal: &mut ratatui::DefaultTerminal,
+    theme: &Theme,
+    glyphs: &Glyphs,
+    preselected: Option<Lang>,
+) -> io::Result<Option<Lang>> {
+    let langs = Lang::all();
+    let mut cursor = preselected.and_then(|l| langs.iter().position(|&x| x == l)).unwrap_or(0);
+    let community: Vec<&str> = translations::all().iter().map(|t| t.name.as_str()).collect();
+
+    loop {
+        terminal.draw(|f| draw_lang_picker(f, &community, cursor, theme, glyphs))?;
+
+        if let Event::Key(key) = event::read()? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(None),
+                KeyCode::Up | KeyCode::Char('k') => {
+                    if cursor > 0 { cursor -= 1; }
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    if cursor < langs.len() - 1 { cursor += 1; }
+                }
+                KeyCode::Char(c @ '1'..='9') if (c as usize - '1' as usize) < langs.len() => {
+                    return Ok(Some(langs[c as usize - '1' as usize]));
+                }
+                KeyCode::Enter => return Ok(Some(langs[cursor])),
+                _ => {}
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Settings TUI rendering
+// ---------------------------------------------------------------------------
+
+fn header_line(app: &App, group: Group, pointer: &'static str, selected: bool) -> Line<'static> {
+    let t = app.t();
+    let theme = app.theme();
+    let style = if selected {
+        Style::default().fg(theme.highlight).add_modifier(Modifier::BOLD | theme.selected)
+    } else {
+        Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
+    };
+    let header = if app.is_collapsed(group) {
+        let count = t.fill(&t.group_count, &[("n", &app.group_len(group))]);
+        format!("{} {} {count}", group.label(t), app.glyphs().collapsed)
+    } else {
+        format!("{} {}", group.label(t), app.glyphs().expanded)
+    };
+    Line::from(vec![
+        Span::styled(pointer, style),
+        Span::styled(header, style),
+    ])
+}
+
+/// Left-aligns `s` in a column of `width` terminal cells. Unlike `{:<N}`,
+/// which counts chars, this accounts for double-width CJK characters.
+fn pad_to_width(s: &str, width: usize) -> String {
+    let pad = width.saturating_sub(s.width());
+    format!("{s}{}", " ".repeat(pad))
+}
+
+/// The label column's width: `LABEL_WIDTH`, widened when a translation
+/// has a longer label so values still line up.
+fn label_width(defs: &[SettingDef], t: &L10n) -> usize {
+    defs.iter().map(|d| d.label(t).width()).max().unwrap_or(0).max(LABEL_WIDTH)
+}
+
+/// The list's setting labels, bare and padded to the label column, by
+/// index into `defs`. Built when the language or the JSON-key display
+/// changes rather than for every row of every frame.
+struct Labels {
+    lang: Lang,
+    json_keys: bool,
+    width: usize,
+    /// What narrow rows truncate; padding can't be told apart from a
+    /// label's own trailing spaces.
+    text: Vec<String>,
+    padded: Vec<String>,
+}
+
+impl Labels {
+    fn new(defs: &[SettingDef], lang: Lang, json_keys: bool) -> Labels {
+        let t = l10n(lang);
+        let mut width = label_width(defs, t);
+        if json_keys {
+            width += defs.iter().map(|d| json_key(d.field).len()).max().unwrap_or(0) + 3;
+        }
+        let text: Vec<String> = defs
+            .iter()
+            .map(|d| if json_keys { format!("{} [{}]", d.label(t), json_key(d.field)) } else { d.label(t).to_string() })
+            .collect();
+        let padded = text.iter().map(|label| pad_to_width(label, width)).collect();
+        Labels { lang, json_keys, width, text, padded }
+    }
+}
+
+/// Right-aligning counterpart of `pad_to_width`, for numeric-style columns.
+fn pad_start_to_width(s: &str, width: usize) -> String {
+    let pad = width.saturating_sub(s.width());
+    format!("{}{s}", " ".repeat(pad))
+}
+
+/// Cuts `s` down to `width` terminal cells, ending in an ellipsis when
+/// anything was dropped.
+fn truncate_to_width(s: &str, width: usize, ellipsis: &str) -> String {
+    if s.width() <= width {
+        return s.to_string();
+    }
+    let mut out = String::new();
+    let mut used = 0;
+    for c in s.chars() {
+        let w = c.width().unwrap_or(0);
+        if used + w + ellipsis.width() > width {
+            break;
+        }
+        out.push(c);
+        used += w;
+    }
+    if width > 0 {
+        out.push_str(ellipsis);
+    }
+    out
+}
+
+/// Label and value styles of a list row.
+fn row_styles(theme: &Theme, selected: bool, pending: bool) -> (Style, Style) {
+    if selected {
+        (
+            Style::default().fg(theme.highlight).add_modifier(Modifier::BOLD | theme.selected),
+            Style::default().fg(theme.positive).add_modifier(Modifier::BOLD),
+        )
+    } else if pending {
+        (Style::default().fg(theme.pending), Style::default().fg(theme.pending))
+    } else {
+        (Style::default().fg(theme.text), Style::default().fg(theme.muted))
+    }
+}
+
+/// `width` is the column's width in cells. When it is too narrow the label
+/// is shortened first so the value stays visible, and the default column is
+/// dropped rather than wrapped.
+fn row_line(app: &App, row: Row, selected: bool, width: usize) -> Line<'_> {
+    let t = app.t();
+    let theme = app.theme();
+    let glyphs = app.glyphs();
+    let pointer = if selected { glyphs.pointer } else { "  " };
+
+    let idx = match row {
+        Row::Setting(idx) => idx,
+        Row::Header(group) => return header_line(app, group, pointer, selected),
+    };
+
+    let def = &app.defs[idx];
+    let pending = app.is_pending(idx);
+    let pending_marker = if pending { "*" } else { " " };
+    let modified = app.is_modified_from_default(idx);
+    let default_marker = if modified { glyphs.modified } else { " " };
+    // Continuous settings draw a gauge over the option range ahead of the number.
+    let gauge = match (&def.kind, get_value(&app.settings, def.field)) {
+        (SettingKind::SelectF64(opts), Value::Float(cur)) if !opts.is_empty() && !app.accessible => {
+            let gauge_width = if selected { GAUGE_WIDTH_SELECTED } else { GAUGE_WIDTH };
+            slider::gauge(cur, opts[0].1, opts[opts.len() - 1].1, gauge_width, glyphs.fine_gauge)
+        }
+        _ => String::new(),
+    };
+    let switch = def.switch_state(get_value(&app.settings, def.field));
+    let value = if switch.is_some() {
+        format!("  {}", app.value_display(idx))
+    } else if gauge.is_empty() {
+        format!("  {} {} {}", glyphs.prev, app.value_display(idx), glyphs.next)
+    } else {
+        format!(" {}", app.value_display(idx))
+    };
+    let gauge_cells = if gauge.is_empty() { 0 } else { gauge.width() + 2 };
+    let star = if app.show_recommended && app.is_recommended(idx) {
+        format!(" {}", glyphs.recommended)
+    } else {
+        String::new()
+    };
+    let lock = if app.is_locked(def.field) { format!(" {}", glyphs.locked) } else { String::new() };
+    let runs_as = runtime_value(&app.settings, def.field, app.refresh_rate)
+        .map(|v| format!(" {}", t.fill(&t.may_run_as, &[("value", &def.format(v, t))])))
+        .unwrap_or_default();
+    let checkbox = match (app.marked.is_empty(), app.marked.contains(&def.field)) {
+        (true, _) => "",
+        (false, true) => "[x] ",
+        (false, false) => "[ ] ",
+    };
+    // Pointer plus both markers take six cells ahead of the label.
+    let after_label = gauge_cells + value.width() + star.width() + lock.width();
+    let label_width = app.labels.width.min(width.saturating_sub(6 + checkbox.len() + after_label));
+    let label: Cow<str> = if label_width == app.labels.width {
+        Cow::Borrowed(&app.labels.padded[idx])
+    } else {
+        Cow::Owned(pad_to_width(&truncate_to_width(&app.labels.text[idx], label_width, glyphs.ellipsis), label_width))
+    };
+
+    let (style, mut val_style) = row_styles(theme, selected, pending);
+    // Unsaved edits keep their own color; saved values away from the game
+    // default take the marker's, so both read at a glance.
+    if modified && !selected && !pending {
+        val_style = val_style.fg(theme.marker);
+    }
+    let label_style = if app.config.category_colors && !selected && !pending {
+        style.fg(def.category().color(theme))
+    } else {
+        style
+    };
+    if let Some(on) = switch
+        && !pending
+    {
+        val_style = val_style.fg(if on { theme.positive } else { theme.muted });
+    }
+
+    let mut spans = vec![
+        Span::styled(pointer, style),
+        Span::styled(pending_marker, Style::default().fg(theme.pending)),
+        Span::styled(format!("{default_marker} "), Style::default().fg(theme.marker)),
+        Span::styled(checkbox, Style::default().fg(theme.accent)),
+        Span::styled(label, label_style),
+    ];
+    if !gauge.is_empty() {
+        let gauge_style = if selected { theme.positive } else { theme.accent };
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(gauge, Style::default().fg(gauge_style).bg(theme.field_bg)));
+    }
+    spans.extend([
+        Span::styled(value, val_style),
+        Span::styled(star, Style::default().fg(theme.highlight)),
+        Span::raw(lock),
+    ]);
+    // Dropped rather than squeezing the label when the row is too narrow.
+    let mut used = 6 + checkbox.len() + label_width + after_label;
+    if !runs_as.is_empty() && used + runs_as.width() <= width {
+        used += runs_as.width();
+        spans.push(Span::styled(runs_as, Style::default().fg(theme.warning)));
+    }
+    if app.show_defaults {
+        let default = def.format(get_value(&app.defaults, def.field), t);
+        // Pad so the default column lines up regardless of value width.
+        let pad = (6 + checkbox.len() + label_width + 14).saturating_sub(used) + 1;
+        let text = format!("{}({} {default})", " ".repeat(pad), t.default_value);
+        if used + text.width() <= width {
+            spans.push(Span::styled(text, Style::default().fg(theme.muted)));
+        }
+    }
+    Line::from(spans)
+}
+
+/// Where each part of the settings screen goes.
+struct ScreenAreas {
+    header: Rect,
+    /// The list and side panel together, which the JSON editor takes over.
+    body: Rect,
+    list: Rect,
+    side: Option<Rect>,
+    log: Option<Rect>,
+    status: Rect,
+}
+
+fn screen_areas(app: &App, area: Rect) -> ScreenAreas {
+    let [header, body, status] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(0),
+        Constraint::Length(3),
+    ])
+    .areas(area);
+    let (body, log) = if app.show_log && app.json_edit.is_none() {
+        let [body, log] = Layout::vertical([Constraint::Min(0), Constraint::Length(LOG_HEIGHT)]).areas(body);
+        (body, Some(log))
+    } else {
+        (body, None)
+    };
+    let (list, side) = body_areas(app, body);
+    ScreenAreas { header, body, list, side, log, status }
+}
+
+/// Splits the body between the list and the side panel. The panel is
+/// dropped when hidden or when it would leave the list too little room.
+fn body_areas(app: &App, body: Rect) -> (Rect, Option<Rect>) {
+    let wanted = app.show_preview || app.show_description;
+    if !wanted || body.width < SIDE_PANEL_MIN_TOTAL {
+        return (body, None);
+    }
+    let [list, panel] =
+        Layout::horizontal([Constraint::Min(0), Constraint::Length(SIDE_PANEL_WIDTH)]).areas(body);
+    (list, Some(panel))
+}
+
+/// The JSON a save would write right now, with lines of pending fields highlighted.
+fn draw_preview(frame: &mut Frame, app: &App, area: Rect) {
+    let t = app.t();
+    let theme = app.theme();
+    let json = settings_json(&app.settings, true).unwrap_or_else(|e| e.to_string());
+    // Pending lines get the list's `*` marker in a gutter as well as the color.
+    let pending: Vec<String> = app
+        .pending_changes()
+        .iter()
+        .map(|d| format!("\"{}\":", json_key(d.field)))
+        .collect();
+
+    let lines: Vec<Line> = json
+        .lines()
+        .map(|l| {
+            let key = l.trim_start();
+            if pending.iter().any(|p| key.starts_with(p.as_str())) {
+                Line::styled(format!("* {l}"), Style::default().fg(theme.pending).add_modifier(Modifier::BOLD))
+            } else {
+                Line::raw(format!("  {l}"))
+            }
+        })
+        .collect();
+
+    let max_scroll = lines.len().saturating_sub(1) as u16;
+    let block = Block::default().borders(Borders::ALL).title(t.preview_title.as_str());
+    frame.render_widget(
+        Paragraph::new(lines).scroll((app.preview_scroll.min(max_scroll), 0)).block(block),
+        area,
+    );
+}
+
+fn draw_description(frame: &mut Frame, app: &App, area: Rect) {
+    let t = app.t();
+    let theme = app.theme();
+    let block = Block::default().borders(Borders::ALL).title(t.desc_title.as_str());
+    let Some(Row::Setting(idx)) = app.selected_row() else {
+        let hint = Paragraph::new(Span::styled(t.desc_group.as_str(), Style::default().fg(theme.muted)))
+            .wrap(Wrap { trim: true })
+            .block(block);
+        frame.render_widget(hint, area);
+        return;
+    };
+
+    let def = &app.defs[idx];
+    let cost = match descriptions::cost(def.field) {
+        descriptions::Cost::Low    => &t.cost_low,
+        descriptions::Cost::Medium => &t.cost_medium,
+        descriptions::Cost::High   => &t.cost_high,
+    };
+    let label_style = Style::default().fg(theme.accent).add_modifier(Modifier::BOLD);
+
+    let mut lines = vec![
+        Line::from(Span::styled(def.label(t), label_style)),
+        Line::from(""),
+        Line::from(descriptions::description(app.lang, def.field)),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(format!("{}: ", t.desc_cost), Style::default().fg(theme.muted)),
+            Span::raw(cost),
+        ]),
+    ];
+    if let Some(rec) = recommended_value(def.field) {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{}: ", t.desc_recommended), Style::default().fg(theme.muted)),
+            Span::raw(def.format(rec, t)),
+        ]));
+    }
+
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }).block(block), area);
+}
+
+/// Key/action pairs that apply right now, most specific first.
+fn hints(app: &App) -> Vec<(String, &'static str)> {
+    let t = app.t();
+    let glyphs = app.glyphs();
+    let key = |k: &str, action: &'static str| (k.to_string(), action);
+    let up_down = format!("{}{}", glyphs.up, glyphs.down);
+    let left_right = format!("{}{}", glyphs.left, glyphs.right);
+
+    if app.show_help {
+        return vec![key(&up_down, &t.hint_scroll), key("Esc", &t.hint_close)];
+    }
+    if app.show_message {
+        return Vec::new();
+    }
+    match &app.modal {
+        Some(Modal::Confirm(confirm)) => {
+            return confirm.buttons(t).map(|(k, action)| key(k, action)).to_vec();
+        }
+        Some(Modal::Choice(..)) => {
+            return vec![
+                key(&left_right, &t.hint_navigate),
+                key("Enter", &t.hint_confirm),
+                key("Esc", &t.hint_cancel),
+            ];
+        }
+        Some(Modal::Input(prompt)) => {
+            let mut hints = vec![key("Enter", &t.hint_confirm), key("Esc", &t.hint_cancel)];
+            if matches!(prompt.kind, PromptKind::Export(_)) {
+                hints.insert(0, key("Tab", &t.hint_format));
+            }
+            return hints;
+        }
+        Some(Modal::Preferences(_)) => {
+            return vec![
+                key(&up_down, &t.hint_navigate),
+                key(&left_right, &t.hint_change),
+                key("Esc", &t.hint_close),
+            ];
+        }
+        None => {}
+    }
+    if app.json_edit.is_some() {
+        return vec![key("Ctrl+S", &t.hint_apply), key("Esc", &t.hint_cancel)];
+    }
+
+    // The settings list follows the effective keymap; an unbound action's
+    // hint comes out empty and is dropped at the end.
+    let keymap = &app.keymap;
+    let bound = |action, label: &'static str| (keymap.key(action, glyphs), label);
+    let pair = |a, b, label: &'static str| (keymap.pair(a, b, glyphs), label);
+    let left_right = |label: &'static str| pair(Action::CyclePrev, Action::CycleNext, label);
+    let mut hints = vec![pair(Action::MoveUp, Action::MoveDown, &t.hint_navigate)];
+    if !app.toasts.is_empty() {
+        hints.insert(0, bound(Action::ShowMessage, &t.hint_full_message));
+    }
+    if app.has_sticky_toast() {
+        hints.insert(0, bound(Action::Back, &t.hint_dismiss));
+    }
+    if !app.marked.is_empty() {
+        hints.push(left_right(&t.hint_change_marked));
+        hints.push(bound(Action::ToggleMark, &t.hint_mark));
+        if !app.has_sticky_toast() && !app.show_preview {
+            hints.push(bound(Action::Back, &t.hint_clear_marks));
+        }
+    } else {
+        match app.selected_row() {
+            Some(Row::Header(group)) => {
+                let action = if app.is_collapsed(group) { &t.hint_unfold } else { &t.hint_fold };
+                hints.push(bound(Action::Activate, action));
+            }
+            Some(Row::Setting(_)) if app.read_only => {}
+            Some(Row::Setting(idx)) => match &app.defs[idx].kind {
+                SettingKind::Toggle => hints.push(bound(Action::Activate, &t.hint_toggle)),
+                SettingKind::SelectI64(opts) => {
+                    hints.push(left_right(&t.hint_change));
+                    if is_quality_level(&app.defs[idx]) {
+                        hints.push(bound(Action::ToggleMark, &t.hint_mark));
+                    }
+                    let digits: Vec<i64> =
+                        opts.iter().map(|(_, v)| *v).filter(|v| (0..=9).contains(v)).collect();
+                    if let (Some(lo), Some(hi)) = (digits.iter().min(), digits.iter().max())
+                        && digits.len() > 1
+                    {
+                        hints.push((format!("{lo}-{hi}"), &t.hint_set));
+                    }
+                }
+                SettingKind::SelectF64(_) => hints.push(left_right(&t.hint_change)),
+            },
+            None => {}
+        }
+        // Large steps only pay off on long option lists.
+        if let Some(Row::Setting(idx)) = app.selected_row()
+            && !app.read_only
+            && app.defs[idx].kind.len() as isize >= ACCEL_MIN_OPTIONS
+        {
+            hints.push(pair(Action::CyclePrevCoarse, Action::CycleNextCoarse, &t.hint_coarse));
+            hints.push(pair(Action::CycleMin, Action::CycleMax, &t.hint_min_max));
+        }
+    }
+    if app.wide {
+        hints.push(bound(Action::SwitchColumn, &t.hint_column));
+    }
+    if app.show_preview {
+        hints.push(pair(Action::ScrollPreviewUp, Action::ScrollPreviewDown, &t.hint_scroll));
+        hints.push(bound(Action::TogglePreview, &t.hint_close));
+    } else if app.show_log {
+        hints.push(pair(Action::ScrollPreviewUp, Action::ScrollPreviewDown, &t.hint_scroll));
+    }
+    if app.show_log {
+        hints.push(bound(Action::ToggleLog, &t.hint_close));
+    }
+    if !app.read_only {
+        hints.push(bound(Action::Save, &t.hint_save));
+    }
+    if app.is_dirty() {
+        hints.push(bound(Action::SaveAndQuit, &t.hint_save_quit));
+    }
+    hints.extend([bound(Action::Quit, &t.hint_quit), bound(Action::ToggleDescription, &t.hint_info)]);
+    if !app.show_preview {
+        hints.push(bound(Action::TogglePreview, &t.hint_preview));
+    }
+    if !app.read_only {
+        hints.push(bound(Action::EditJson, &t.hint_edit));
+    }
+    hints.push(bound(Action::Help, &t.hint_help));
+    hints.retain(|(k, _)| !k.is_empty());
+    hints
+}
+
+fn hint_line(app: &App) -> Line<'static> {
+    let mut spans = vec![Span::raw(" ")];
+    for (key, action) in hints(app) {
+        spans.push(Span::styled(key, Style::default().add_modifier(Modifier::BOLD)));
+        spans.push(Span::raw(format!(" {action}  ")));
+    }
+    Line::from(spans)
+}
+
+fn draw_list(frame: &mut Frame, app: &App, list_area: Rect) {
+    let inner_block = Block::default().borders(Borders::ALL).title(hint_line(app));
+    let inner = inner_block.inner(list_area);
+    frame.render_widget(inner_block, list_area);
+
+    let spacing = app.config.row_spacing as usize;
+    let visible_height = app.visible_rows(inner.height);
+    let rows = app.rows();
+    let split = app.column_split(&rows);
+    let (left, right) = rows.split_at(split);
+
+    // Both columns scroll together, by the offset `fit_to` keeps.
+    let total = left.len().max(right.len());
+    let scroll_offset = app.list_offset;
+
+    let column_lines = |column: &[Row], first: usize, area: Rect| -> Vec<Line> {
+        column
+            .iter()
+            .enumerate()
+            .skip(scroll_offset)
+            .take(visible_height)
+            .flat_map(|(i, row)| {
+                let line = row_line(app, *row, first + i == app.cursor, area.width as usize);
+                std::iter::once(line).chain(std::iter::repeat_n(Line::default(), spacing))
+            })
+            .collect()
+    };
+
+    if right.is_empty() {
+        frame.render_widget(Paragraph::new(column_lines(left, 0, inner)), inner);
+    } else {
+        let [left_area, right_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).areas(inner);
+        frame.render_widget(Paragraph::new(column_lines(left, 0, left_area)), left_area);
+        frame.render_widget(Paragraph::new(column_lines(right, split, right_area)), right_area);
+    }
+
+    if total > visible_height {
+        let mut sb_state = ScrollbarState::new(total - visible_height).position(scroll_offset);
+        frame.render_stateful_widget(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight).symbols(app.glyphs().scrollbar.clone()),
+            list_area,
+            &mut sb_state,
+        );
+    }
+}
+
+/// Newest entries at the bottom, like a terminal; `log_scroll` pages back.
+fn draw_log(frame: &mut Frame, app: &App, area: Rect) {
+    let t = app.t();
+    let theme = app.theme();
+    let visible = area.height.saturating_sub(2) as usize;
+    let total = app.log.len();
+    let end = total - app.log_scroll.min(total);
+    let start = end.saturating_sub(visible);
+    let lines: Vec<Line> = app
+        .log
+        .range(start..end)
+        .map(|entry| Line::styled(entry.clone(), Style::default().fg(theme.text)))
+        .collect();
+    let block = Block::default().borders(Borders::ALL).title(t.log_title.as_str());
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+
+    if total > visible {
+        let mut sb_state = ScrollbarState::new(total.saturating_sub(visible)).position(start);
+        frame.render_stateful_widget(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight).symbols(app.glyphs().scrollbar.clone()),
+            area,
+            &mut sb_state,
+        );
+    }
+}
+
+fn too_small(area: Rect) -> bool {
+    area.width < MIN_WIDTH || area.height < MIN_HEIGHT
+}
+
+/// Centered notice shown instead of the UI while the terminal is too small.
+fn draw_too_small(frame: &mut Frame, t: &L10n, theme: &Theme, glyphs: &Glyphs) {
+    let area = frame.area();
+    let text = t.fill(&glyphs.text(&t.too_small), &[("w", &MIN_WIDTH), ("h", &MIN_HEIGHT)]);
+    let [_, middle, _] = Layout::vertical([
+        Constraint::Fill(1),
+        Constraint::Length(3),
         Constraint::Fill(1),
     ])
-    .areas(center);
+    .areas(area);
+    let notice = Paragraph::new(vec![
+        Line::from(Span::styled(text, Style::default().fg(theme.warning))),
+        Line::from(Span::styled(
+            format!("{}x{}", area.width, area.height),
+            Style::default().fg(theme.muted),
+        )),
+    ])
+    .alignment(Alignment::Center)
+    .wrap(Wrap { trim: true });
+    frame.render_widget(notice, middle);
+}
+
+fn draw_settings(frame: &mut Frame, app: &App) {
+    let t = app.t();
+    let theme = app.theme();
+    let glyphs = app.glyphs();
+    if too_small(frame.area()) {
+        draw_too_small(frame, t, theme, glyphs);
+        return;
+    }
+
+    let ScreenAreas {
+        header: header_area,
+        body: body_area,
+        list: list_area,
+        side: side_area,
+        log: log_area,
+        status: status_area,
+    } = screen_areas(app, frame.area());
+
+    // Header
+    let mut title = vec![Span::styled(
+        t.title.as_str(),
+        Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+    )];
+    if app.is_dirty() {
+        title.push(Span::styled(format!("{} ", t.title_unsaved), Style::default().fg(theme.pending)));
+    }
+    let conflicts = validate(&app.settings, app.refresh_rate);
+    if let Some(first) = conflicts.first() {
+        let mut text = format!("{} {} ", glyphs.warning, glyphs.text(first.message(t)));
+        if conflicts.len() > 1 {
+            text.push_str(&format!("(+{}) ", conflicts.len() - 1));
+        }
+        title.push(Span::styled(text, Style::default().fg(theme.warning)));
+    }
+    let header = Paragraph::new(Line::from(title))
+    .block(Block::default().borders(Borders::ALL));
+    frame.render_widget(header, header_area);
+
+    // Body: the JSON editor takes over the whole area while open
+    if let Some(edit) = &app.json_edit {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(t.json_edit_title.as_str())
+            .title(hint_line(app));
+        edit.area.render(frame, body_area, block);
+    } else {
+        draw_list(frame, app, list_area);
+        if let Some(area) = side_area {
+            if app.show_preview {
+                draw_preview(frame, app, area);
+            } else {
+                draw_description(frame, app, area);
+            }
+        }
+    }
+
+    if let Some(area) = log_area {
+        draw_log(frame, app, area);
+    }
+
+    // Status bar: persistent state only; events go to toasts
+    let pending = app.pending_changes().len();
+    let mut state = vec![if pending > 0 {
+        Span::styled(
+            format!(" {}", t.fill(&t.unsaved_changes, &[("n", &pending)])),
+            Style::default().fg(theme.pending),
+        )
+    } else {
+        Span::styled(format!(" {}", t.no_unsaved_changes), Style::default().fg(theme.muted))
+    }];
+    if app.safe_mode {
+        state.push(Span::styled(format!("  {}", t.safe_mode_badge), Style::default().fg(theme.positive)));
+    }
+    if app.loaded_hive != Hive::CurrentUser || app.hive != Hive::CurrentUser {
+        let badge = format!("  {} {} {}", app.loaded_hive.short_name(), glyphs.arrow, app.hive.short_name());
+        state.push(Span::styled(badge, Style::default().fg(theme.warning)));
+    }
+    if app.read_only {
+        state.push(Span::styled(format!("  {}", t.read_only_badge), Style::default().fg(theme.accent)));
+    } else if app.config.autosave_secs > 0 {
+        let badge = t.fill(&t.autosave_badge, &[("n", &app.config.autosave_secs)]);
+        state.push(Span::styled(format!("  {badge}"), Style::default().fg(theme.warning)));
+    }
+    if let Some(preset) = app.matching_preset() {
+        let badge = t.fill(&t.preset_badge, &[("preset", &preset.label(t))]);
+        state.push(Span::styled(format!("  {badge}"), Style::default().fg(theme.marker)));
+    }
+
+    let json_error = app.json_edit.as_ref().and_then(|e| e.error.as_deref());
+    let status_line = match json_error {
+        Some(error) => Line::from(Span::styled(
+            format!(" {} {error}", glyphs.error),
+            Style::default().fg(theme.error),
+        )),
+        None if app.busy() => Line::from(Span::styled(
+            format!(
+                " {} {}",
+                glyphs.spinner[app.spinner % glyphs.spinner.len()],
+                glyphs.text(if app.saving { &t.saving } else { &t.working })
+            ),
+            Style::default().fg(theme.accent),
+        )),
+        None => Line::from(state),
+    };
+    let status = Paragraph::new(status_line).block(Block::default().borders(Borders::ALL));
+    frame.render_widget(status, status_area);
+
+    draw_toasts(frame, app, status_area.y);
+    draw_modal(frame, app);
+    if app.show_help {
+        draw_help(frame, app);
+    }
+    if app.show_message {
+        draw_message(frame, app);
+    }
+}
+
+/// The help overlay's text: each section's effective bindings, in `KEYMAP`
+/// order. Unbound actions are left out.
+fn help_lines(app: &App) -> Vec<Line<'static>> {
+    let (t, theme, glyphs) = (app.t(), app.theme(), app.glyphs());
+    let mut lines = Vec::new();
+    for section in HelpSection::ALL {
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::styled(
+            section.label(t),
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+        ));
+        for (action, keys) in app.keymap.bindings.iter().filter(|(a, k)| a.section() == section && !k.is_empty()) {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  {}", pad_to_width(&binding_label(*action, keys, glyphs), 16)),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(action.description(t)),
+            ]));
+        }
+    }
+    lines
+}
+
+fn draw_help(frame: &mut Frame, app: &App) {
+    let t = app.t();
+    let lines = help_lines(app);
+    let width = lines.iter().map(Line::width).max().unwrap_or(0) as u16 + 4;
+    let screen = frame.area();
+    frame.buffer_mut().set_style(screen, Style::default().add_modifier(Modifier::DIM));
+    let area = centered(screen, width.max(50), lines.len() as u16 + 2);
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(lines).scroll((app.help_scroll, 0)).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(t.help_title.as_str())
+                .title_bottom(hint_line(app)),
+        ),
+        area,
+    );
+}
+
+/// The newest toast's text, wrapped in full, in case it was cut off.
+fn draw_message(frame: &mut Frame, app: &App) {
+    let Some(Toast { severity, text, .. }) = &app.last_notice else {
+        return;
+    };
+    let t = app.t();
+    let theme = app.theme();
+    let glyphs = app.glyphs();
+    let (color, icon) = severity.look(theme, glyphs);
+    let screen = frame.area();
+    let width = 70.min(screen.width.saturating_sub(4)).max(20);
+    let lines: Vec<Line> = wrap_text(&format!("{icon} {text}"), width.saturating_sub(4) as usize)
+        .into_iter()
+        .map(|line| Line::styled(line, Style::default().fg(color)))
+        .collect();
+    frame.buffer_mut().set_style(screen, Style::default().add_modifier(Modifier::DIM));
+    let area = centered(screen, width, lines.len() as u16 + 2);
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .padding(Padding::horizontal(1))
+                .title(t.message_title.as_str())
+                .title_bottom(Line::styled(t.message_close.as_str(), Style::default().fg(theme.muted))),
+        ),
+        area,
+    );
+}
+
+/// Greedy word wrap to `width` display columns. Words wider than a line,
+/// and text without spaces such as Korean or Japanese, break between
+/// characters.
+fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split(' ') {
+            let gap = usize::from(!line.is_empty());
+            if !line.is_empty() && line.width() + gap + word.width() > width {
+                lines.push(std::mem::take(&mut line));
+            } else if gap == 1 {
+                line.push(' ');
+            }
+            for c in word.chars() {
+                if line.width() + c.width().unwrap_or(0) > width {
+                    lines.push(std::mem::take(&mut line));
+                }
+                line.push(c);
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Centers a `width` x `height` box in `area`, shrinking it to fit.
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
+
+/// Dims everything drawn so far, then draws the open modal centered on top:
+/// the question, the kind-specific body, and the modal's key hints.
+fn draw_modal(frame: &mut Frame, app: &App) {
+    let Some(modal) = &app.modal else {
+        return;
+    };
+    let t = app.t();
+    let theme = app.theme();
+    let question_style = Style::default().fg(theme.highlight).add_modifier(Modifier::BOLD);
+    let (question, body) = match modal {
+        Modal::Confirm(Confirm::SaveChanges) => {
+            (Confirm::SaveChanges.question(t).to_string(), save_review_lines(app))
+        }
+        Modal::Confirm(confirm) => (confirm.question(t).to_string(), Vec::new()),
+        Modal::Choice(kind, selected) => {
+            let mut options = Vec::new();
+            for (i, (_, label)) in kind.options(t).into_iter().enumerate() {
+                let style = if i == *selected {
+                    // Reverse video on top of the highlight background would
+                    // swap it back to the terminal's, so it's only for themes
+                    // without one.
+                    let style = Style::default().fg(theme.on_highlight).bg(theme.highlight).add_modifier(Modifier::BOLD);
+                    if theme.highlight == Color::Reset { style.add_modifier(theme.selected) } else { style }
+                } else {
+                    Style::default().fg(theme.text)
+                };
+                options.push(Span::styled(format!(" {label} "), style));
+                options.push(Span::raw("  "));
+            }
+            options.pop();
+            let mut body = match kind {
+                ChoiceKind::SyncClients => client_diff_lines(app),
+                ChoiceKind::ChangedOutside => outside_change_lines(app),
+                _ => Vec::new(),
+            };
+            body.push(Line::from(options));
+            (kind.question(t).to_string(), body)
+        }
+        Modal::Input(prompt) => {
+            let mut body = vec![Line::styled(
+                format!("{}{}", prompt.input, app.glyphs().cursor),
+                Style::default().fg(theme.text).bg(theme.field_bg),
+            )];
+            if let Some(error) = &prompt.error {
+                body.push(Line::styled(error.clone(), Style::default().fg(theme.error)));
+            }
+            (prompt.label(t), body)
+        }
+        Modal::Preferences(selected) => (t.prefs_title.to_string(), preference_lines(app, *selected)),
+    };
+
+    let mut lines = vec![Line::styled(question, question_style), Line::from("")];
+    if !body.is_empty() {
+        lines.extend(body);
+        lines.push(Line::from(""));
+    }
+    lines.push(hint_line(app));
+
+    let min_width = if matches!(modal, Modal::Input(_)) { 60 } else { 40 };
+    let width = lines.iter().map(Line::width).max().unwrap_or(0) as u16 + 4;
+    let screen = frame.area();
+    frame.buffer_mut().set_style(screen, Style::default().add_modifier(Modifier::DIM));
+    let area = centered(screen, width.max(min_width), lines.len() as u16 + 2);
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(lines).block(Block::default().borders(Borders::ALL)), area);
+}
+
+/// Preferences body: one row per `Pref`, drawn like the settings list.
+fn preference_lines(app: &App, selected: usize) -> Vec<Line<'static>> {
+    let t = app.t();
+    let glyphs = app.glyphs();
+    let label_width = Pref::ALL.iter().map(|p| p.label(t).width()).max().unwrap_or(0);
+    Pref::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, &pref)| {
+            let selected = i == selected;
+            let (style, val_style) = row_styles(app.theme(), selected, false);
+            Line::from(vec![
+                Span::styled(if selected { glyphs.pointer } else { "  " }, style),
+                Span::styled(pad_to_width(pref.label(t), label_width), style),
+                Span::styled(format!("  {} {} {}", glyphs.prev, app.pref_value(pref), glyphs.next), val_style),
+            ])
+        })
+        .collect()
+}
+
+/// Sync dialog body: each setting that differs, with both clients' values.
+fn client_diff_lines(app: &App) -> Vec<Line<'static>> {
+    let Some((global, cn)) = &app.client_values else {
+        return Vec::new();
+    };
+    let t = app.t();
+    let theme = app.theme();
+    let label_width = label_width(&app.defs, t);
+    let mut lines = vec![Line::styled(
+        format!("{}{}{}", pad_to_width("", label_width), pad_to_width(&t.client_global, 12), t.client_cn),
+        Style::default().fg(theme.muted),
+    )];
+    for def in diff_settings(&app.defs, global, cn) {
+        lines.push(Line::raw(format!(
+            "{}{}{}",
+            pad_to_width(def.label(t), label_width),
+            pad_to_width(&def.format(get_value(global, def.field), t), 12),
+            def.format(get_value(cn, def.field), t),
+        )));
+    }
+    lines.push(Line::from(""));
+    lines
+}
+
+/// Body of the changed-outside dialog: each setting that changed since
+/// it was loaded, as loaded -> now.
+fn outside_change_lines(app: &App) -> Vec<Line<'static>> {
+    let Some(current) = &app.changed_outside else {
+        return Vec::new();
+    };
+    let t = app.t();
+    let theme = app.theme();
+    let label_width = label_width(&app.defs, t);
+    let mut lines: Vec<Line> = diff_settings(&app.defs, &app.original, current)
+        .into_iter()
+        .map(|def| {
+            Line::from(vec![
+                Span::raw(pad_to_width(def.label(t), label_width)),
+                Span::styled(def.format(get_value(&app.original, def.field), t), Style::default().fg(theme.muted)),
+                Span::raw(format!(" {} ", app.glyphs().arrow)),
+                Span::styled(def.format(get_value(current, def.field), t), Style::default().fg(theme.warning)),
+            ])
+        })
+        .collect();
+    lines.push(Line::from(""));
+    lines
+}
+
+/// Save confirmation body: every pending change as old -> new, plus active conflicts.
+fn save_review_lines(app: &App) -> Vec<Line<'static>> {
+    let t = app.t();
+    let theme = app.theme();
+    let glyphs = app.glyphs();
+    let label_width = label_width(&app.defs, t);
+    let mut lines: Vec<Line> = app
+        .pending_changes()
+        .iter()
+        .map(|def| {
+            Line::from(vec![
+                Span::raw(pad_to_width(def.label(t), label_width)),
+                Span::styled(
+                    def.format(get_value(&app.original, def.field), t),
+                    Style::default().fg(theme.muted),
+                ),
+                Span::raw(format!(" {} ", glyphs.arrow)),
+                Span::styled(
+                    def.format(get_value(&app.settings, def.field), t),
+                    Style::default().fg(theme.positive).add_modifier(Modifier::BOLD),
+                ),
+            ])
+        })
+        .collect();
+    if app.key_missing {
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::styled(
+            format!("{} {}", glyphs.info, glyphs.text(&t.save_creates_key)),
+            Style::default().fg(theme.accent),
+        ));
+    }
+    let conflicts = validate(&app.settings, app.refresh_rate);
+    if !conflicts.is_empty() {
+        lines.push(Line::from(""));
+        for c in conflicts {
+            lines.push(Line::styled(
+                format!("{} {}", glyphs.warning, glyphs.text(c.message(t))),
+                Style::default().fg(theme.warning),
+            ));
+        }
+    }
+    lines
+}
+
+/// Stacks toasts bottom-up in the right corner, newest lowest, ending above row `bottom`.
+fn draw_toasts(frame: &mut Frame, app: &App, bottom: u16) {
+    let theme = app.theme();
+    let glyphs = app.glyphs();
+    let area = frame.area();
+    let max_width = area.width / 2;
+    let mut y = bottom;
+    for toast in app.toasts.iter().rev() {
+        if y < area.y + 3 {
+            break;
+        }
+        y -= 3;
+        let (color, icon) = toast.severity.look(theme, glyphs);
+        let text = format!("{icon} {}", toast.text);
+        let mut style = Style::default().fg(color);
+        if toast.fading() {
+            style = style.add_modifier(Modifier::DIM);
+        }
+        let width = (text.width() as u16 + 4).min(max_width);
+        let rect = Rect::new(area.right().saturating_sub(width + 1), y, width, 3);
+        frame.render_widget(Clear, rect);
+        frame.render_widget(
+            Paragraph::new(Span::styled(text, style))
+                .block(Block::default().borders(Borders::ALL).border_style(style)),
+            rect,
+        );
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Command-line modes
+// ---------------------------------------------------------------------------
+
+fn load_json_file(path: &str) -> io::Result<GraphicsSettings> {
+    serde_json::from_value(read_json_value(path)?)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{path}: {e}")))
+}
+
+/// `--import <file.json> [--merge]`: writes the file to the registry. With
+/// `--merge`, only the keys present in the file replace the live values.
+fn run_import(path: &str, merge: bool, no_nul: bool, hive: Hive) -> io::Result<()> {
+    let stored = read_settings().stored();
+    let append_nul = !no_nul && stored.as_ref().is_none_or(|s| s.nul_terminated);
+    let settings = if merge {
+        merge_settings(stored.map(|s| s.settings).unwrap_or_default(), read_json_value(path)?)
+    } else {
+        load_json_file(path)?
+    };
+    let outcome = write_settings(&settings, append_nul, hive)?;
+    println!("{}", outcome.message(l10n(Lang::En)));
+    Ok(())
+}
+
+/// `--export <file.json> [--compact]`: writes the live registry value to a file.
+fn run_export(path: &str, format: ExportFormat) -> io::Result<()> {
+    let t = l10n(Lang::En);
+    let stored = match read_settings() {
+        ReadResult::Found(stored) => *stored,
+        ReadResult::Missing => return Err(io::Error::new(io::ErrorKind::NotFound, t.no_registry.as_str())),
+        other => {
+            let problem = other.problem(t).unwrap_or_default();
+            return Err(io::Error::new(io::ErrorKind::InvalidData, problem));
+        }
+    };
+    let text = format.render(&stored.settings).map_err(io::Error::other)?;
+    std::fs::write(path, text)?;
+    println!("{}", t.fill(&t.exported, &[("path", &path)]));
+    Ok(())
+}
+
+/// `--emit-script <file.reg | file.ps1>`: writes a script that sets the
+/// live registry value, byte for byte, on a machine without this tool.
+fn run_emit_script(path: &str, no_nul: bool, hive: Hive) -> io::Result<()> {
+    let t = l10n(Lang::En);
+    let stored = match read_settings() {
+        ReadResult::Found(stored) => *stored,
+        ReadResult::Missing => return Err(io::Error::new(io::ErrorKind::NotFound, t.no_registry.as_str())),
+        other => {
+            let problem = other.problem(t).unwrap_or_default();
+            return Err(io::Error::new(io::ErrorKind::InvalidData, problem));
+        }
+    };
+    let bytes = registry_bytes(&stored.settings, !no_nul && stored.nul_terminated)?;
+    let target = script::Target { hive: hive.name(), drive: hive.short_name(), key: REG_PATH, value: REG_VALUE };
+    let powershell = std::path::Path::new(path).extension().is_some_and(|ext| ext.eq_ignore_ascii_case("ps1"));
+    if powershell {
+        std::fs::write(path, script::powershell(&target, &bytes))?;
+    } else {
+        std::fs::write(path, script::reg_file(&target, &bytes))?;
+    }
+    println!("{}", t.fill(&t.exported, &[("path", &path)]));
+    Ok(())
+}
+
+/// `print` / `--plain`: every setting the value has and its current value,
+/// one aligned line each in list order, with the TUI's labels and formatting.
+fn run_print(lang: Lang) -> io::Result<()> {
+    let t = l10n(lang);
+    let stored = match read_settings() {
+        ReadResult::Found(stored) => *stored,
+        ReadResult::Missing => {
+            eprintln!("{}", t.no_registry);
+            std::process::exit(EXIT_MISSING);
+        }
+        other => {
+            let problem = other.problem(t).unwrap_or_default();
+            return Err(io::Error::new(io::ErrorKind::InvalidData, problem));
+        }
+    };
+    let config = Config::load();
+    let defs = setting_defs(&fps_options(&config));
+    let refresh_rate = refresh_rate(&config);
+    let width = defs.iter().map(|d| d.label(t).width()).max().unwrap_or(0);
+    for def in &defs {
+        let mut value = def.format(get_value(&stored.settings, def.field), t);
+        if let Some(v) = runtime_value(&stored.settings, def.field, refresh_rate) {
+            value = format!("{value} {}", t.fill(&t.may_run_as, &[("value", &def.format(v, t))]));
+        }
+        println!("{}  {value}", pad_to_width(def.label(t), width));
+    }
+    Ok(())
+}
 
-    let options = [
-        ("1", "English"),
-        ("2", "한국어 (Korean)"),
-        ("3", "日本語 (Japanese)"),
-    ];
+/// `--render <file.txt>`: draws the first frame the app would show, once
+/// the registry value is read, into a file instead of the terminal. The
+/// capture keeps colors as ANSI escapes unless color is turned off.
+fn run_render(path: &str, options: &Options, width: u16, height: u16) -> io::Result<()> {
+    let config = Config::load();
+    let lang = config.language().or(config.session.language()).unwrap_or(Lang::En);
+    let mut app = App::new(lang, options);
+    // A dialog from the client comparison would cover the list.
+    app.clients_pending = false;
+    while app.busy() {
+        std::thread::sleep(Duration::from_millis(10));
+        app.poll_io();
+    }
+    app.fit_to(Rect::new(0, 0, width, height));
+    let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+    let frame = terminal.draw(|f| draw_settings(f, &app))?;
+    std::fs::write(path, snapshot::to_text(frame.buffer, !options.no_color)?)
+}
 
-    let mut lines: Vec<Line> = vec![
-        Line::from(Span::styled(
-            "  Select Language / 언어 선택 / 言語選択",
-            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
-        )),
-        Line::from(""),
-    ];
+/// Parses `120x40` as columns and rows.
+fn parse_size(s: &str) -> Option<(u16, u16)> {
+    let (w, h) = s.split_once(['x', 'X'])?;
+    Some((w.parse().ok().filter(|&w| w > 0)?, h.parse().ok().filter(|&h| h > 0)?))
+}
 
-    for (i, (key, label)) in options.iter().enumerate() {
-        let selected = i == cursor;
-        let pointer = if selected { "\u{25b8} " } else { "  " };
-        let style = if selected {
-            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
-        } else {
-            Style::default().fg(Color::White)
+/// `--diff <file.json>`: prints the fields where the file and the live registry value differ.
+fn run_diff(path: &str) -> io::Result<()> {
+    let t = l10n(Lang::En);
+    let file = load_json_file(path)?;
+    let result = read_settings();
+    if let Some(problem) = result.problem(t) {
+        eprintln!("{problem}");
+    }
+    let live = result.stored().map(|s| s.settings).unwrap_or_default();
+
+    let defs = setting_defs(&fps_options(&Config::load()));
+    let diffs = diff_settings(&defs, &file, &live);
+    if diffs.is_empty() {
+        println!("No differences.");
+        return Ok(());
+    }
+    println!("{}  {:>8}  {:>8}", pad_to_width("Setting", LABEL_WIDTH), "File", "Live");
+    for def in diffs {
+        println!(
+            "{}  {}  {}",
+            pad_to_width(def.label(t), LABEL_WIDTH),
+            pad_start_to_width(&def.format(get_value(&file, def.field), t), 8),
+            pad_start_to_width(&def.format(get_value(&live, def.field), t), 8),
+        );
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Main
+// ---------------------------------------------------------------------------
+
+fn main() -> io::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().is_some_and(|a| a == "lang") {
+        if args.get(1).is_none_or(|a| a != "template") {
+            eprintln!("usage: hsr-settings lang template [<file.toml>]");
+            std::process::exit(2);
+        }
+        let path = args.get(2).map_or("template.toml", String::as_str);
+        translations::write_template(std::path::Path::new(path))?;
+        println!("Wrote {path}. Translate it and save it as lang\\<code>.toml in %APPDATA%\\hsr-settings.");
+        return Ok(());
+    }
+    // Before anything reads the config, so configured codes resolve.
+    translations::load();
+    let no_nul = args.iter().any(|a| a == "--no-nul");
+    let yes = args.iter().any(|a| a == "--yes" || a == "-y");
+    let read_only = args.iter().any(|a| a == "--read-only");
+    let fresh = args.iter().any(|a| a == "--fresh");
+    let accessible = args.iter().any(|a| a == "--accessible") || Config::load().accessible;
+    // https://no-color.org: any non-empty value turns color off.
+    let no_color = args.iter().any(|a| a == "--no-color")
+        || std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    let glyphs = if accessible || args.iter().any(|a| a == "--ascii") {
+        &glyphs::ASCII
+    } else if args.iter().any(|a| a == "--unicode") {
+        &glyphs::UNICODE
+    } else {
+        glyphs::detect()
+    };
+    let theme = match args.iter().position(|a| a == "--theme") {
+        Some(i) => match args.get(i + 1).and_then(|name| ThemeName::parse(name)) {
+            Some(name) => Some(name),
+            None => {
+                eprintln!("usage: hsr-settings --theme <dark | light | solarized | high-contrast>");
+                std::process::exit(2);
+            }
+        },
+        None => None,
+    };
+    let hive = match args.iter().position(|a| a == "--hive") {
+        Some(i) => match args.get(i + 1).and_then(|name| Hive::parse(name)) {
+            Some(hive) => hive,
+            None => {
+                eprintln!("usage: hsr-settings --hive <hkcu | hklm>");
+                std::process::exit(2);
+            }
+        },
+        None => Hive::CurrentUser,
+    };
+    let lang = match args.iter().position(|a| a == "--lang") {
+        Some(i) => match args.get(i + 1).and_then(|name| Lang::parse(name)) {
+            Some(lang) => Some(lang),
+            None => {
+                eprintln!("usage: hsr-settings [print] --lang <en | ko | ja | ru | es | pt-br>");
+                std::process::exit(2);
+            }
+        },
+        None => None,
+    };
+    if args.first().is_some_and(|a| a == "print") || args.iter().any(|a| a == "--plain") {
+        return run_print(lang.or(Config::load().language()).unwrap_or(Lang::En));
+    }
+    if let Some(i) = args.iter().position(|a| a == "--diff") {
+        let Some(path) = args.get(i + 1) else {
+            eprintln!("usage: hsr-settings --diff <file.json>");
+            std::process::exit(2);
         };
-        lines.push(Line::from(vec![
-            Span::styled(pointer, style),
-            Span::styled(format!("[{key}] {label}"), style),
-        ]));
+        return run_diff(path);
+    }
+    if let Some(i) = args.iter().position(|a| a == "--export") {
+        let Some(path) = args.get(i + 1) else {
+            eprintln!("usage: hsr-settings --export <file.json> [--pretty | --compact]");
+            std::process::exit(2);
+        };
+        let compact = args.iter().any(|a| a == "--compact");
+        return run_export(path, if compact { ExportFormat::JsonCompact } else { ExportFormat::Json });
+    }
+    if let Some(i) = args.iter().position(|a| a == "--emit-script") {
+        let Some(path) = args.get(i + 1) else {
+            eprintln!("usage: hsr-settings --emit-script <file.reg | file.ps1> [--hive <hkcu | hklm>] [--no-nul]");
+            std::process::exit(2);
+        };
+        return run_emit_script(path, no_nul, hive);
+    }
+    if let Some(i) = args.iter().position(|a| a == "--import") {
+        let Some(path) = args.get(i + 1) else {
+            eprintln!("usage: hsr-settings --import <file.json> [--merge]");
+            std::process::exit(2);
+        };
+        return run_import(path, args.iter().any(|a| a == "--merge"), no_nul, hive);
+    }
+    // `--import` reads .reg files too; this spelling refuses anything else.
+    if let Some(i) = args.iter().position(|a| a == "--import-reg") {
+        let Some(path) = args.get(i + 1) else {
+            eprintln!("usage: hsr-settings --import-reg <file.reg> [--merge]");
+            std::process::exit(2);
+        };
+        let text = std::fs::read(path).ok().and_then(|b| script::decode_text(&b).ok());
+        if !text.is_some_and(|t| script::is_reg_file(&t)) {
+            eprintln!("hsr-settings: {path}: not a registry export (.reg)");
+            std::process::exit(2);
+        }
+        return run_import(path, args.iter().any(|a| a == "--merge"), no_nul, hive);
     }
 
-    lines.push(Line::from(""));
-    lines.push(Line::from(Span::styled(
-        "  Enter to confirm",
-        Style::default().fg(Color::DarkGray),
-    )));
+    if let Err(e) = Keymap::new(&Config::load()) {
+        eprintln!("hsr-settings: {e}");
+        std::process::exit(2);
+    }
+    let options = Options { no_nul, yes, read_only, theme, no_color, glyphs, hive, fresh, accessible };
+    // Undocumented: for bug reports and layout snapshots.
+    if let Some(i) = args.iter().position(|a| a == "--render") {
+        let size = match args.iter().position(|a| a == "--size") {
+            Some(i) => args.get(i + 1).and_then(|s| parse_size(s)),
+            None => Some((120, 40)),
+        };
+        let (Some(path), Some((width, height))) = (args.get(i + 1), size) else {
+            eprintln!("usage: hsr-settings --render <file.txt> [--size <columns>x<rows>] [--no-color]");
+            std::process::exit(2);
+        };
+        return run_render(path, &options, width, height);
+    }
 
-    let block = Block::default().borders(Borders::ALL);
-    frame.render_widget(Paragraph::new(lines).block(block), box_area);
-}
+    let mut terminal = ratatui::init();
+
+    let config = Config::load();
+    let picker_theme = if no_color {
+        &theme::MONO
+    } else if accessible {
+        ThemeName::HighContrast.theme()
+    } else {
+        theme.unwrap_or(config.theme).theme()
+    };
+    let remembered = config.session.language().filter(|_| !fresh);
+    // The system language only decides alone when the last run didn't
+    // pick something else.
+    let detected = locale::detect();
+    let system = detected
+        .as_ref()
+        .filter(|d| d.confident && remembered.is_none_or(|r| r == d.lang))
+        .map(|d| d.lang);
+    let skipped = lang
+        .or(config.language())
+        .or(remembered.filter(|_| config.skip_language_picker))
+        .or(system);
+    let preselected = remembered.or(detected.map(|d| d.lang));
+    let lang = match skipped {
+        Some(l) => l,
+        None => match pick_language(&mut terminal, picker_theme, glyphs, preselected)? {
+            Some(l) => l,
+            None => {
+                ratatui::restore();
+                return Ok(());
+            }
+        },
+    };
 
-fn pick_language(terminal: &mut ratatui::DefaultTerminal) -> io::Result<Option<Lang>> {
-    let langs = [Lang::En, Lang::Ko, Lang::Ja];
-    let mut cursor: usize = 0;
+    let mut app = App::new(lang, &options);
+    app.watch = watch::Watcher::spawn().ok();
 
+    let mut redraw = true;
     loop {
-        terminal.draw(|f| draw_lang_picker(f, cursor))?;
+        redraw |= app.poll_io();
+        redraw |= app.poll_watch();
+        if app.quit {
+            break;
+        }
+        if redraw {
+            let size = terminal.size()?;
+            app.fit_to(Rect::new(0, 0, size.width, size.height));
+            terminal.draw(|f| draw_settings(f, &app))?;
+            redraw = false;
+        }
 
-        if let Event::Key(key) = event::read()? {
+        // Woken when the screen next changes without input, e.g. a toast
+        // starts fading; `tick` says whether it did.
+        let timeout = app.next_wakeup().map_or(TICK, |wait| wait.min(TICK));
+        if !event::poll(timeout)? {
+            redraw |= app.tick();
+            continue;
+        }
+        let event = event::read()?;
+        if let Event::Resize(..) = event {
+            redraw = true;
+        }
+        if let Event::Key(key) = event {
             if key.kind != KeyEventKind::Press {
                 continue;
             }
-            match key.code {
-                KeyCode::Char('q') | KeyCode::Esc => return Ok(None),
-                KeyCode::Up | KeyCode::Char('k') => {
-                    if cursor > 0 { cursor -= 1; }
-                }
-                KeyCode::Down | KeyCode::Char('j') => {
-                    if cursor < langs.len() - 1 { cursor += 1; }
-                }
-                KeyCode::Char('1') => return Ok(Some(Lang::En)),
-                KeyCode::Char('2') => return Ok(Some(Lang::Ko)),
-                KeyCode::Char('3') => return Ok(Some(Lang::Ja)),
-                KeyCode::Enter => return Ok(Some(langs[cursor])),
-                _ => {}
+            redraw = true;
+            app.handle_key(key);
+            if app.quit {
+                break;
             }
         }
     }
+
+    app.remember_session();
+    ratatui::restore();
+    Ok(())
 }
 
-// ---------------------------------------------------------------------------
-// Settings TUI rendering
-// ---------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-fn draw_settings(frame: &mut Frame, app: &App) {
-    let t = app.t();
+    fn options() -> Options {
+        Options {
+            no_nul: false,
+            yes: false,
+            read_only: false,
+            theme: None,
+            no_color: true,
+            glyphs: &glyphs::UNICODE,
+            hive: Hive::CurrentUser,
+            fresh: true,
+            accessible: false,
+        }
+    }
 
-    let [header_area, list_area, status_area] = Layout::vertical([
-        Constraint::Length(3),
-        Constraint::Min(0),
-        Constraint::Length(3),
-    ])
-    .areas(frame.area());
+    fn app() -> App {
+        App::with_config(Lang::En, &options(), Config::default())
+    }
 
-    // Header
-    let header = Paragraph::new(Line::from(vec![Span::styled(
-        t.title,
-        Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
-    )]))
-    .block(Block::default().borders(Borders::ALL));
-    frame.render_widget(header, header_area);
+    /// Points `app` at an in-memory value holding `settings`, loads it, and
+    /// returns the value for inspection.
+    fn stored(app: &mut App, settings: &GraphicsSettings) -> Values {
+        let value = std::sync::Arc::new(std::sync::Mutex::new([Some(registry_bytes(settings, true).unwrap()), None]));
+        app.store = Store::Memory(value.clone());
+        app.reload();
+        wait_io(app);
+        value
+    }
 
-    // Settings list
-    let inner_block = Block::default()
-        .borders(Borders::ALL)
-        .title(t.hint);
-    let inner = inner_block.inner(list_area);
-    frame.render_widget(inner_block, list_area);
+    fn wait_io(app: &mut App) {
+        while app.busy() {
+            std::thread::sleep(Duration::from_millis(1));
+            app.poll_io();
+        }
+    }
 
-    let visible_height = inner.height as usize;
-    let total = app.defs.len();
+    /// The settings screen at `width` x `height` as plain text.
+    fn render(app: &mut App, width: u16, height: u16) -> String {
+        snapshot::to_text(&draw(app, width, height), false).unwrap()
+    }
 
-    let scroll_offset = if app.cursor >= visible_height {
-        app.cursor - visible_height + 1
-    } else {
-        0
-    };
+    fn draw(app: &mut App, width: u16, height: u16) -> ratatui::buffer::Buffer {
+        app.fit_to(Rect::new(0, 0, width, height));
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|f| draw_settings(f, app)).unwrap().buffer.clone()
+    }
 
-    let lines: Vec<Line> = app
-        .defs
-        .iter()
-        .enumerate()
-        .skip(scroll_offset)
-        .take(visible_height)
-        .map(|(i, def)| {
-            let selected = i == app.cursor;
-            let pointer = if selected { "\u{25b8} " } else { "  " };
-            let label = format!("{:<24}", def.label(t));
-            let value = format!("  \u{25c2} {} \u{25b8}", app.value_display(i));
-
-            let style = if selected {
-                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(Color::White)
-            };
-            let val_style = if selected {
-                Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(Color::DarkGray)
-            };
+    #[test]
+    fn padding_counts_terminal_cells() {
+        for text in ["60", "中", "켜기", "オフ"] {
+            assert_eq!(pad_to_width(text, 8).width(), 8);
+            assert_eq!(pad_start_to_width(text, 8).width(), 8);
+            assert!(pad_start_to_width(text, 8).ends_with(text));
+        }
+        assert_eq!(pad_start_to_width("パーティクルトレイル", 8), "パーティクルトレイル");
+    }
 
-            Line::from(vec![
-                Span::styled(pointer, style),
-                Span::styled(label, style),
-                Span::styled(value, val_style),
-            ])
-        })
-        .collect();
+    #[test]
+    fn wide_labels_keep_the_value_column_aligned() {
+        for lang in [Lang::Ko, Lang::Ja] {
+            let mut app = App::with_config(lang, &options(), Config::default());
+            let text = render(&mut app, 100, 30);
+            let t = app.t();
+            let mut columns = Vec::new();
+            for line in text.lines() {
+                assert!(line.width() <= 100, "{line}");
+                if !app.defs.iter().any(|def| line.contains(&format!(" {} ", def.label(t)))) {
+                    continue;
+                }
+                let value = line.find(['◂', '[', '█']).unwrap();
+                // The render scale gauge has no default after it.
+                let default = line.find('(').map(|default| line[..default].width());
+                columns.push((line[..value].width(), default));
+            }
+            assert_eq!(columns.len(), app.defs.len(), "{text}");
+            assert!(columns.iter().all(|&(value, _)| value == columns[0].0), "{lang:?}\n{text}");
+            let defaults: Vec<usize> = columns.iter().filter_map(|&(_, default)| default).collect();
+            assert!(defaults.iter().all(|&default| default == defaults[0]), "{lang:?}\n{text}");
+        }
+    }
 
-    frame.render_widget(Paragraph::new(lines), inner);
+    /// Compares a frame with its golden capture under `tests/data/snapshots`.
+    /// Set `UPDATE_SNAPSHOTS=1` to write the current frames instead.
+    fn assert_snapshot(name: &str, text: &str) {
+        let path = std::path::Path::new(file!()).parent().unwrap().join("../tests/data/snapshots").join(name);
+        if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+            std::fs::write(&path, text).unwrap();
+        }
+        let golden = std::fs::read_to_string(&path).unwrap_or_default();
+        assert!(golden == text, "{} differs from the frame drawn:\n{text}", path.display());
+    }
 
-    if total > visible_height {
-        let mut sb_state = ScrollbarState::new(total).position(scroll_offset);
-        frame.render_stateful_widget(
-            Scrollbar::new(ScrollbarOrientation::VerticalRight),
-            list_area,
-            &mut sb_state,
-        );
+    #[test]
+    fn settings_screen_matches_its_snapshots() {
+        let mut app = app();
+        assert_snapshot("settings_100x30.txt", &render(&mut app, 100, 30));
+        assert_snapshot("settings_narrow_60x24.txt", &render(&mut app, 60, 24));
+        // Too short for the whole list: the scrollbar shows, thumb at the bottom.
+        select(&mut app, Field::DlssQuality);
+        assert_snapshot("settings_scrolled_100x16.txt", &render(&mut app, 100, 16));
     }
 
-    // Status bar
-    let status_style = if app.status.contains(t.saved) {
-        Style::default().fg(Color::Green)
-    } else if !app.status.is_empty() {
-        Style::default().fg(Color::Yellow)
-    } else {
-        Style::default().fg(Color::DarkGray)
-    };
-    let status = Paragraph::new(Span::styled(format!(" {}", app.status), status_style))
-        .block(Block::default().borders(Borders::ALL));
-    frame.render_widget(status, status_area);
-}
+    #[test]
+    fn cached_labels_render_like_per_frame_ones() {
+        // Recorded before labels were cached, so these pin the output to
+        // what every frame used to format for itself.
+        let mut app = app();
+        app.show_json_keys = true;
+        assert_snapshot("settings_json_keys_100x30.txt", &render(&mut app, 100, 30));
+        // Too narrow for the whole label: truncated from the bare text.
+        assert_snapshot("settings_json_keys_narrow_50x24.txt", &render(&mut app, 50, 24));
+        app.lang = Lang::Ja;
+        assert_snapshot("settings_json_keys_ja_50x24.txt", &render(&mut app, 50, 24));
+    }
 
-// ---------------------------------------------------------------------------
-// Main
-// ---------------------------------------------------------------------------
+    #[test]
+    fn language_picker_matches_its_snapshot() {
+        let mut terminal = Terminal::new(TestBackend::new(70, 20)).unwrap();
+        let community = ["Deutsch (German)"];
+        let frame = terminal.draw(|f| draw_lang_picker(f, &community, 2, &theme::MONO, &glyphs::UNICODE)).unwrap();
+        assert_snapshot("lang_picker_70x20.txt", &snapshot::to_text(frame.buffer, false).unwrap());
+    }
 
-fn main() -> io::Result<()> {
-    let mut terminal = ratatui::init();
+    fn press(app: &mut App, code: KeyCode) {
+        app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
 
-    let lang = match pick_language(&mut terminal)? {
-        Some(l) => l,
-        None => {
-            ratatui::restore();
-            return Ok(());
+    /// The line of the frame holding the selection pointer.
+    fn selected_line(text: &str) -> &str {
+        text.lines().find(|line| line.contains(glyphs::UNICODE.pointer)).unwrap()
+    }
+
+    #[test]
+    fn the_list_scrolls_only_as_far_as_the_cursor_needs() {
+        let mut app = app();
+        let rows = app.rows().len();
+        let text = render(&mut app, 100, 16);
+        let visible = 8;
+        assert!(selected_line(&text).contains("Display"), "{text}");
+        assert_eq!(app.list_offset, 0);
+
+        // The bottom end: the last row sits on the last line.
+        for _ in 1..rows {
+            press(&mut app, KeyCode::Down);
         }
-    };
+        let text = render(&mut app, 100, 16);
+        assert_eq!(app.list_offset, rows - visible);
+        assert!(selected_line(&text).contains("DLSS Quality"), "{text}");
 
-    let mut app = App::new(lang);
+        // Up through the view leaves it where it is...
+        for _ in 1..visible {
+            press(&mut app, KeyCode::Up);
+            render(&mut app, 100, 16);
+        }
+        assert_eq!(app.list_offset, rows - visible);
+        // ...and past its top scrolls one row at a time.
+        press(&mut app, KeyCode::Up);
+        let text = render(&mut app, 100, 16);
+        assert_eq!(app.list_offset, rows - visible - 1);
+        let first = text.lines().position(|line| line.contains(glyphs::UNICODE.pointer)).unwrap();
+        assert_eq!(first, 4, "{text}");
 
-    loop {
-        terminal.draw(|f| draw_settings(f, &app))?;
+        // The top end.
+        for _ in 0..rows {
+            press(&mut app, KeyCode::Up);
+        }
+        render(&mut app, 100, 16);
+        assert_eq!((app.cursor, app.list_offset), (0, 0));
+    }
 
-        if let Event::Key(key) = event::read()? {
-            if key.kind != KeyEventKind::Press {
-                continue;
+    #[test]
+    fn resizing_keeps_the_cursor_in_view_without_blank_lines() {
+        let mut app = app();
+        let rows = app.rows().len();
+        select(&mut app, Field::DlssQuality);
+        render(&mut app, 100, 30);
+        assert_eq!(app.list_offset, 0);
+        // Shrinking scrolls just enough to keep the selected row.
+        let text = render(&mut app, 100, 16);
+        assert_eq!(app.list_offset, rows - 8);
+        assert!(selected_line(&text).contains("DLSS Quality"), "{text}");
+        // Growing again pulls rows back in from the top rather than
+        // leaving blank lines under the last one.
+        let text = render(&mut app, 100, 20);
+        assert_eq!(app.list_offset, rows - 12);
+        assert!(selected_line(&text).contains("DLSS Quality"), "{text}");
+        render(&mut app, 100, 30);
+        assert_eq!(app.list_offset, 0);
+    }
+
+    #[test]
+    fn small_terminals_show_the_size_message() {
+        let mut app = app();
+        for (width, height) in [(1, 1), (MIN_WIDTH - 1, 40), (120, MIN_HEIGHT - 1)] {
+            let text = render(&mut app, width, height);
+            assert!(!text.contains(&app.t().title), "{width}x{height}");
+            if width >= 40 {
+                assert!(text.contains("Terminal too small"), "{width}x{height}");
             }
-            match key.code {
-                KeyCode::Char('q') | KeyCode::Esc => break,
-                KeyCode::Up | KeyCode::Char('k') => {
-                    if app.cursor > 0 {
-                        app.cursor -= 1;
-                    }
-                }
-                KeyCode::Down | KeyCode::Char('j') => {
-                    if app.cursor + 1 < app.defs.len() {
-                        app.cursor += 1;
-                    }
-                }
-                KeyCode::Right | KeyCode::Char('l') => app.cycle(1),
-                KeyCode::Left | KeyCode::Char('h') => app.cycle(-1),
-                KeyCode::Char('s') => app.save(),
-                _ => {}
+        }
+        for (width, height) in [(MIN_WIDTH, MIN_HEIGHT), (120, 40)] {
+            let text = render(&mut app, width, height);
+            assert!(text.contains(&app.t().title), "{width}x{height}");
+            assert!(!text.contains("Terminal too small"), "{width}x{height}");
+        }
+    }
+
+    #[test]
+    fn list_without_room_for_rows_still_renders() {
+        let mut app = app();
+        app.show_log = true;
+        app.cursor = app.rows().len() - 1;
+        let text = render(&mut app, MIN_WIDTH, MIN_HEIGHT);
+        assert!(text.contains(&app.t().title));
+        // Growing again brings the rows back.
+        app.show_log = false;
+        let text = render(&mut app, 80, 40);
+        assert!(text.contains(app.defs[0].label(app.t())));
+    }
+
+    #[test]
+    fn settings_serialize_like_the_game_writes_them() {
+        let golden = include_str!("../tests/data/game_settings.json").trim_end();
+        let settings: GraphicsSettings = serde_json::from_str(golden).unwrap();
+        assert_eq!(settings_json(&settings, false).unwrap(), golden);
+        assert_eq!(registry_bytes(&settings, true).unwrap(), [golden.as_bytes(), &[0]].concat());
+    }
+
+    /// Puts the cursor on `field`'s row.
+    fn select(app: &mut App, field: Field) {
+        let idx = app.defs.iter().position(|d| d.field == field).unwrap();
+        app.cursor = app.rows().iter().position(|r| *r == Row::Setting(idx)).unwrap();
+    }
+
+    #[test]
+    fn held_arrows_speed_up_on_quality_tiers_without_wrapping() {
+        let mut app = app();
+        select(&mut app, Field::ShadowQuality);
+        app.settings.shadow_quality = 1;
+        for expected in [2, 3, 4, 5, 1] {
+            app.cycle(1);
+            assert_eq!(app.settings.shadow_quality, expected);
+        }
+        // Past the threshold the step doubles, but stops at the last tier.
+        app.cycle(1);
+        assert_eq!(app.settings.shadow_quality, 3);
+        app.cycle(1);
+        assert_eq!(app.settings.shadow_quality, 5);
+        app.cycle(1);
+        assert_eq!(app.settings.shadow_quality, 1);
+    }
+
+    #[test]
+    fn changing_a_value_back_is_not_unsaved() {
+        let mut app = app();
+        select(&mut app, Field::ShadowQuality);
+        app.cycle(1);
+        assert!(app.is_dirty());
+        app.cycle(-1);
+        assert!(!app.is_dirty());
+
+        // Keys without a row and unknown keys count as well.
+        app.settings.enable_metal_fxsu = !app.settings.enable_metal_fxsu;
+        assert!(app.is_dirty());
+        app.settings.enable_metal_fxsu = !app.settings.enable_metal_fxsu;
+        app.settings.enable_half_res_transparent = !app.settings.enable_half_res_transparent;
+        assert!(app.is_dirty());
+        app.settings.enable_half_res_transparent = !app.settings.enable_half_res_transparent;
+        app.settings.extra.insert("NewKey".into(), 1.into());
+        assert!(app.is_dirty());
+        app.settings.extra.remove("NewKey");
+        assert!(!app.is_dirty());
+    }
+
+    #[test]
+    fn saving_without_changes_skips_the_write() {
+        let mut app = app();
+        let value = stored(&mut app, &GraphicsSettings { fps: 120, ..GraphicsSettings::default() });
+        assert_eq!(app.settings.fps, 120);
+        select(&mut app, Field::Fps);
+        app.cycle(1);
+        app.cycle(-1);
+        value.lock().unwrap()[0] = None;
+        app.request_save();
+        assert!(!app.busy() && app.modal.is_none());
+        assert_eq!(app.last_notice.as_ref().unwrap().text, app.t().no_changes);
+        assert!(value.lock().unwrap()[0].is_none());
+    }
+
+    #[test]
+    fn saves_check_the_hive_they_write_to() {
+        let mut app = app();
+        let loaded = GraphicsSettings { fps: 120, ..GraphicsSettings::default() };
+        let values = stored(&mut app, &loaded);
+        app.hive = Hive::LocalMachine;
+        values.lock().unwrap()[1] = Some(registry_bytes(&loaded, true).unwrap());
+        // Only HKCU changes: the HKLM value being replaced is still the one loaded.
+        values.lock().unwrap()[0] = Some(registry_bytes(&GraphicsSettings::default(), true).unwrap());
+        app.settings.shadow_quality = 1;
+        app.write(true);
+        wait_io(&mut app);
+        assert!(app.changed_outside.is_none());
+        let written = values.lock().unwrap()[1].clone().unwrap();
+        let written = parse_value(&written, Hive::LocalMachine);
+        assert!(matches!(written, ReadResult::Found(s) if s.settings.shadow_quality == 1));
+
+        values.lock().unwrap()[1] = Some(registry_bytes(&GraphicsSettings::default(), true).unwrap());
+        app.settings.shadow_quality = 2;
+        app.write(true);
+        wait_io(&mut app);
+        assert!(app.changed_outside.is_some());
+    }
+
+    #[test]
+    fn navigation_stops_at_the_ends_unless_it_wraps() {
+        let mut app = app();
+        let last = app.rows().len() - 1;
+        app.handle(Action::MoveUp);
+        assert_eq!(app.cursor, 0);
+        for _ in 0..last + 3 {
+            app.handle(Action::MoveDown);
+        }
+        assert_eq!(app.cursor, last);
+
+        app.config.wrap_navigation = true;
+        app.handle(Action::MoveDown);
+        assert_eq!(app.cursor, 0);
+        app.handle(Action::MoveUp);
+        assert_eq!(app.cursor, last);
+    }
+
+    #[test]
+    fn cycling_wraps_past_the_first_and_last_option() {
+        let mut app = app();
+        select(&mut app, Field::ShadowQuality);
+        assert_eq!(app.settings.shadow_quality, 3);
+        app.handle(Action::CycleNext);
+        assert_eq!(app.settings.shadow_quality, 4);
+        app.handle(Action::CycleMax);
+        assert_eq!(app.settings.shadow_quality, 5);
+        app.handle(Action::CycleNext);
+        assert_eq!(app.settings.shadow_quality, 1);
+        app.handle(Action::CyclePrev);
+        assert_eq!(app.settings.shadow_quality, 5);
+
+        // Toggles flip both ways, and Enter flips them too.
+        select(&mut app, Field::VSync);
+        let vsync = app.settings.enable_vsync;
+        app.handle(Action::CycleNext);
+        assert_eq!(app.settings.enable_vsync, !vsync);
+        app.handle(Action::Activate);
+        assert_eq!(app.settings.enable_vsync, vsync);
+    }
+
+    #[test]
+    fn digits_set_the_tier_they_name() {
+        let mut app = app();
+        let digit = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+        assert!(app.keymap.action_for(digit('4')) == Some(Action::SetTier(4)));
+        assert!(app.keymap.action_for(digit('0')) == Some(Action::SetTier(0)));
+
+        select(&mut app, Field::ShadowQuality);
+        press(&mut app, KeyCode::Char('1'));
+        assert_eq!(app.settings.shadow_quality, 1);
+        app.handle(Action::SetTier(5));
+        assert_eq!(app.settings.shadow_quality, 5);
+        // Not one of the options: nothing changes.
+        app.handle(Action::SetTier(9));
+        assert_eq!(app.settings.shadow_quality, 5);
+    }
+
+    #[test]
+    fn saving_writes_the_edits_to_the_store() {
+        let mut app = app();
+        let values = stored(&mut app, &GraphicsSettings::default());
+        app.handle(Action::Save);
+        assert!(app.modal.is_none(), "nothing to save");
+
+        select(&mut app, Field::ShadowQuality);
+        app.handle(Action::SetTier(1));
+        assert!(app.is_dirty());
+        app.handle(Action::Save);
+        if app.modal.is_some() {
+            press(&mut app, KeyCode::Enter);
+        }
+        wait_io(&mut app);
+        assert!(!app.is_dirty());
+        let written = values.lock().unwrap()[Hive::CurrentUser as usize].clone().unwrap();
+        assert!(matches!(parse_value(&written, Hive::CurrentUser), ReadResult::Found(s) if s.settings.shadow_quality == 1));
+        assert_eq!(app.original.shadow_quality, 1);
+
+        // Read-only sessions leave the store alone.
+        app.read_only = true;
+        app.handle(Action::SetTier(2));
+        app.handle(Action::Save);
+        wait_io(&mut app);
+        assert_eq!(values.lock().unwrap()[Hive::CurrentUser as usize], Some(written));
+    }
+
+    /// The inside of the modal box whose first line starts with `question`.
+    fn modal_lines(text: &str, question: &str) -> Vec<String> {
+        let lines: Vec<Vec<char>> = text.lines().map(|l| l.chars().collect()).collect();
+        let (top, left) = lines
+            .iter()
+            .enumerate()
+            .find_map(|(y, l)| {
+                let line: String = l.iter().collect();
+                line.find(question).map(|x| (y, line[..x].chars().count()))
+            })
+            .unwrap();
+        let right = left + lines[top][left..].iter().position(|&c| c == '\u{2502}').unwrap();
+        lines[top..]
+            .iter()
+            .take_while(|l| l[left - 1] == '\u{2502}')
+            .map(|l| l[left..right].iter().collect())
+            .collect()
+    }
+
+    #[test]
+    fn modals_clear_what_they_cover() {
+        let mut app = app();
+        let t = app.t();
+        let modals = [
+            (Modal::Confirm(Confirm::Reload), Confirm::Reload.question(t)),
+            (Modal::Choice(ChoiceKind::Quit, 0), ChoiceKind::Quit.question(t)),
+        ];
+        for (modal, question) in modals {
+            app.modal = Some(modal);
+            let text = render(&mut app, 80, 30);
+            let inside = modal_lines(&text, question);
+            assert_eq!(inside[0].trim_end(), question);
+            assert!(inside[1].trim().is_empty(), "{text}");
+            assert!(!inside.last().unwrap().trim().is_empty(), "{text}");
+            for line in &inside {
+                assert!(!line.contains("Quality"), "{text}");
             }
         }
     }
 
-    ratatui::restore();
-    Ok(())
+    #[test]
+    fn selected_buttons_stay_visible_in_every_theme() {
+        let mut app = app();
+        app.modal = Some(Modal::Choice(ChoiceKind::Quit, 1));
+        let label = format!(" {} ", app.t().quit_discard);
+        for (accessible, no_color) in [(false, false), (true, false), (false, true)] {
+            app.accessible = accessible;
+            app.no_color = no_color;
+            let buffer = draw(&mut app, 80, 30);
+            let text = snapshot::to_text(&buffer, false).unwrap();
+            let (y, line) = text.lines().enumerate().find(|(_, line)| line.contains(&label)).unwrap();
+            let x = line[..line.find(&label).unwrap()].width() as u16 + 1;
+            let style = buffer[(x, y as u16)].style();
+            let theme = app.theme();
+            if theme.highlight == Color::Reset {
+                assert!(style.add_modifier.contains(Modifier::REVERSED));
+            } else {
+                assert_eq!(style.bg, Some(theme.highlight));
+                assert!(!style.add_modifier.contains(Modifier::REVERSED), "accessible: {accessible}");
+            }
+        }
+    }
+
+    #[test]
+    fn locked_vsync_is_never_offered_or_turned_off() {
+        let mut app = app();
+        app.config.fps_vsync_autofix = true;
+        app.config.locked.push(Field::VSync);
+        app.refresh_rate = Some(60);
+        select(&mut app, Field::Fps);
+        app.settings.fps = 60;
+        app.cycle(1);
+        assert!(app.settings.fps > 60 && app.modal.is_none());
+        app.answer_confirm(Confirm::DisableVsync, true);
+        assert!(app.settings.enable_vsync);
+    }
+
+    #[test]
+    fn undo_and_revert_leave_locked_settings_alone() {
+        let mut app = app();
+        select(&mut app, Field::ShadowQuality);
+        app.cycle(1);
+        let edited = app.settings.shadow_quality;
+        app.config.locked.push(Field::ShadowQuality);
+        app.undo_redo(false);
+        assert_eq!(app.settings.shadow_quality, edited);
+        app.revert_field();
+        app.revert_all();
+        assert_eq!(app.settings.shadow_quality, edited);
+    }
+
+    #[test]
+    fn markdown_summary_lists_every_setting_by_group() {
+        let t = l10n(Lang::En);
+        let defs = setting_defs(&fps_options(&Config::default()));
+        let summary = markdown_summary(&defs, &GraphicsSettings::default(), t);
+        let bullets: Vec<&str> = summary.lines().filter(|l| l.starts_with("- ")).collect();
+        assert_eq!(bullets.len(), defs.len());
+        assert!(bullets.contains(&"- FPS: 60"), "{summary}");
+        assert!(summary.contains(&format!("*{}*", Group::Display.label(t))));
+    }
+
+    #[test]
+    fn blocked_autosave_doesnt_wake_the_loop() {
+        let mut app = app();
+        app.config.autosave_secs = 1;
+        app.autosave_from = Instant::now() - Duration::from_secs(5);
+        app.settings.fps = 120;
+        assert_eq!(app.next_wakeup(), Some(Duration::ZERO));
+        app.modal = Some(Modal::Confirm(Confirm::Reload));
+        assert_eq!(app.next_wakeup(), None);
+        assert!(!app.tick());
+
+        app.modal = None;
+        app.config.autosave_secs = u64::MAX;
+        assert_eq!(app.next_wakeup(), None);
+    }
+
+    #[test]
+    fn favorites_count_the_rows_shown() {
+        let mut app = app();
+        app.config.favorites = vec![Field::ShadowQuality, Field::Fps, Field::ShadowQuality];
+        let shown = app.rows().iter().skip(1).take_while(|r| matches!(r, Row::Setting(_))).count();
+        assert_eq!(shown, 2);
+        assert_eq!(app.group_len(Group::Favorites), shown);
+    }
+
+    #[test]
+    fn theme_flag_is_used_but_not_saved() {
+        let mut app = App::with_config(
+            Lang::En,
+            &Options { theme: Some(ThemeName::HighContrast), no_color: false, ..options() },
+            Config::default(),
+        );
+        assert!(std::ptr::eq(app.theme(), ThemeName::HighContrast.theme()));
+        assert_eq!(app.config.theme, Config::default().theme);
+        app.theme_override = None;
+        assert!(std::ptr::eq(app.theme(), Config::default().theme.theme()));
+    }
+
+    #[test]
+    fn display_calibration_keys_are_kept_as_they_are() {
+        let golden = include_str!("../tests/data/game_settings.json").trim_end();
+        let json = golden.replacen('}', r#","EnableHDR":true,"Brightness":1.2,"Gamma":2.4}"#, 1);
+        let settings: GraphicsSettings = serde_json::from_str(&json).unwrap();
+        assert_eq!(settings_json(&settings, false).unwrap(), json);
+    }
+
+    #[test]
+    fn preset_codes_stage_any_value_the_game_accepts() {
+        let mut app = app();
+        app.settings.enable_metal_fxsu = true;
+        let shared = GraphicsSettings { shadow_quality: 1, ..GraphicsSettings::default() };
+        app.apply_code(&code::encode_code(&shared)).unwrap();
+        assert_eq!(app.settings.shadow_quality, 1);
+        assert!(app.settings.enable_metal_fxsu);
+
+        // A scale typed with `:` and a cap from someone else's fps_options.
+        app.set_typed_value("render scale 1.3").unwrap();
+        let own = code::encode_code(&app.settings);
+        let custom = GraphicsSettings { fps: 144, ..GraphicsSettings::default() };
+        app.apply_code(&code::encode_code(&custom)).unwrap();
+        assert_eq!(app.settings.fps, 144);
+        app.apply_code(&own).unwrap();
+        assert!((app.settings.render_scale - 1.3).abs() < 0.001);
+
+        for bad in [
+            GraphicsSettings { shadow_quality: 9, ..GraphicsSettings::default() },
+            GraphicsSettings { render_scale: 2.5, ..GraphicsSettings::default() },
+            GraphicsSettings { fps: 0, ..GraphicsSettings::default() },
+        ] {
+            let error = app.apply_code(&code::encode_code(&bad)).unwrap_err();
+            assert!(error.contains("can't use"), "{error}");
+        }
+        assert_eq!(app.settings.shadow_quality, 1);
+    }
+
+    #[test]
+    fn vsync_only_conflicts_with_caps_above_the_refresh_rate() {
+        let mut s = GraphicsSettings { enable_vsync: true, ..GraphicsSettings::default() };
+        for (fps, hz, conflict) in [(30, 60, false), (60, 60, false), (120, 60, true), (120, 144, false), (240, 144, true)] {
+            s.fps = fps;
+            assert_eq!(validate(&s, Some(hz)) == [Conflict::VsyncFpsCap(hz)], conflict, "{fps} FPS at {hz} Hz");
+        }
+        // Nothing to compare against.
+        assert!(validate(&s, None).is_empty());
+        s.enable_vsync = false;
+        assert!(validate(&s, Some(60)).is_empty());
+    }
+
+    #[test]
+    fn vsync_autofix_is_offered_only_above_the_refresh_rate() {
+        let mut app = app();
+        app.config.fps_vsync_autofix = true;
+        app.config.fps_options = Some(vec![60, 120, 144, 165]);
+        app.defs = setting_defs(&fps_options(&app.config));
+        app.settings.enable_vsync = true;
+        app.refresh_rate = Some(144);
+        select(&mut app, Field::Fps);
+        app.settings.fps = 60;
+        app.cycle(1);
+        app.cycle(1);
+        assert!(app.settings.fps == 144 && app.modal.is_none());
+        app.cycle(1);
+        assert!(matches!(app.modal, Some(Modal::Confirm(Confirm::DisableVsync))));
+
+        app.modal = None;
+        app.refresh_rate = None;
+        app.cycle(-1);
+        app.cycle(1);
+        assert!(app.modal.is_none());
+    }
+
+    #[test]
+    fn ticks_only_report_visible_changes() {
+        let mut app = app();
+        app.notify(Severity::Info, |t| t.no_changes.clone());
+        assert!(!app.tick());
+        app.toasts[0].at = Instant::now() - (TOAST_TTL - TOAST_FADE);
+        assert!(app.tick());
+        assert!(!app.tick());
+        app.toasts[0].at = Instant::now() - TOAST_TTL;
+        assert!(app.next_wakeup().is_some_and(|wait| wait.is_zero()));
+        assert!(app.tick());
+        assert!(app.toasts.is_empty() && app.next_wakeup().is_none());
+        assert!(!app.tick());
+    }
+
+    #[test]
+    fn outside_changes_to_edited_settings_ask_once() {
+        let mut app = app();
+        let value = |fps| {
+            let settings = GraphicsSettings { fps, ..GraphicsSettings::default() };
+            watch::Change::Value(Hive::CurrentUser, registry_bytes(&settings, true).unwrap())
+        };
+        assert!(app.outside_change(value(30)));
+        assert!(app.modal.is_none() && app.settings.fps == 30);
+
+        app.settings.shadow_quality = 1;
+        assert!(app.outside_change(value(120)));
+        assert!(matches!(app.modal, Some(Modal::Choice(ChoiceKind::ChangedOutside, 0))));
+        app.modal = Some(Modal::Choice(ChoiceKind::ChangedOutside, 2));
+        assert!(app.outside_change(value(60)));
+        assert!(matches!(app.modal, Some(Modal::Choice(ChoiceKind::ChangedOutside, 2))));
+        assert_eq!((app.original.fps, app.settings.shadow_quality), (30, 1));
+
+        app.answer_choice(ChoiceKind::ChangedOutside, 0);
+        assert!(app.modal.is_none());
+        assert_eq!((app.original.fps, app.settings.fps, app.settings.shadow_quality), (60, 60, 1));
+    }
+
+    #[test]
+    fn vsync_never_raises_the_fps_it_runs_at() {
+        let mut s = GraphicsSettings { enable_vsync: true, fps: 30, ..GraphicsSettings::default() };
+        assert!(runtime_value(&s, Field::Fps, Some(60)).is_none());
+        s.fps = 120;
+        assert!(matches!(runtime_value(&s, Field::Fps, Some(60)), Some(Value::Int(60))));
+        assert!(runtime_value(&s, Field::Fps, Some(144)).is_none());
+        s.fps = 240;
+        assert!(matches!(runtime_value(&s, Field::Fps, Some(144)), Some(Value::Int(144))));
+        // Without a known rate there is nothing to say it runs as.
+        assert!(runtime_value(&s, Field::Fps, None).is_none());
+    }
+
+    #[test]
+    fn the_list_notes_vsync_caps_only_above_the_refresh_rate() {
+        let mut app = app();
+        app.settings.enable_vsync = true;
+        app.settings.fps = 120;
+        app.refresh_rate = Some(144);
+        assert!(!render(&mut app, 100, 30).contains("may run as"));
+        app.refresh_rate = None;
+        assert!(!render(&mut app, 100, 30).contains("may run as"));
+        app.refresh_rate = Some(60);
+        assert!(render(&mut app, 100, 30).contains("(may run as 60)"));
+    }
 }
@@ -0,0 +1,111 @@
+use std::fmt::{Display, Write};
+
+// ---------------------------------------------------------------------------
+// Message templates (named placeholders and plural forms)
+// ---------------------------------------------------------------------------
+
+/// Fills a localized template. `{name}` is replaced by the argument of that
+/// name; `{name|form|form…}` by one of the forms, picked for the argument's
+/// number by `rule` (the table's `plural_rule`). Placeholders without an
+/// argument are left as written, so a mistake shows instead of vanishing.
+pub fn fill(template: &str, rule: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        let inner = &rest[start + 1..start + len];
+        let mut parts = inner.split('|');
+        let name = parts.next().unwrap_or_default();
+        let forms: Vec<&str> = parts.collect();
+        match args.iter().find(|(n, _)| *n == name) {
+            Some((_, value)) if forms.is_empty() => {
+                let _ = write!(out, "{value}");
+            }
+            Some((_, value)) => {
+                let n = value.to_string().parse().unwrap_or(0);
+                out.push_str(forms[plural_form(rule, n).min(forms.len() - 1)]);
+            }
+            None => out.push_str(&rest[start..=start + len]),
+        }
+        rest = &rest[start + len + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Which of a template's plural forms `n` takes.
+fn plural_form(rule: &str, n: u64) -> usize {
+    match rule {
+        // Korean, Japanese: one form.
+        "none" => 0,
+        // Portuguese (Brazil), French: 0 and 1 are singular.
+        "zero_one" => usize::from(n > 1),
+        // Russian, Ukrainian: 1, 21 / 2-4, 22-24 / 0, 5-20, 25…
+        "slavic" => match (n % 10, n % 100) {
+            (1, r) if r != 11 => 0,
+            (2..=4, r) if !(12..=14).contains(&r) => 1,
+            _ => 2,
+        },
+        // English, Spanish and unknown rules: only 1 is singular.
+        _ => usize::from(n != 1),
+    }
+}
+
+/// Names of the placeholders in `template`, for checking translations.
+pub fn placeholders(template: &str) -> impl Iterator<Item = &str> {
+    template
+        .split('{')
+        .skip(1)
+        .filter_map(|part| part.split_once('}'))
+        .map(|(inner, _)| inner.split('|').next().unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{l10n, Lang};
+
+    #[test]
+    fn named_placeholders_are_filled() {
+        assert_eq!(fill("{a} and {b}, {a}", "one", &[("a", &1), ("b", &"two")]), "1 and two, 1");
+        assert_eq!(fill("{missing} stays", "one", &[("a", &1)]), "{missing} stays");
+        assert_eq!(fill("unclosed {a", "one", &[("a", &1)]), "unclosed {a");
+    }
+
+    #[test]
+    fn plural_forms_follow_the_rule() {
+        let files = |rule, n: u64| fill("{n} {n|file|files}", rule, &[("n", &n)]);
+        assert_eq!([1, 0, 2].map(|n| files("one", n)), ["1 file", "0 files", "2 files"]);
+        assert_eq!([0, 1, 2].map(|n| files("zero_one", n)), ["0 file", "1 file", "2 files"]);
+        let forms = |n| plural_form("slavic", n);
+        assert_eq!([1, 21, 101].map(forms), [0, 0, 0]);
+        assert_eq!([2, 4, 22, 34].map(forms), [1, 1, 1, 1]);
+        assert_eq!([0, 5, 11, 12, 14, 20, 111].map(forms), [2; 7]);
+        assert_eq!([0, 1, 7].map(|n| plural_form("none", n)), [0; 3]);
+        // Fewer forms than the rule has: the last one is used.
+        assert_eq!(fill("{n|file|files}", "slavic", &[("n", &5)]), "files");
+    }
+
+    #[test]
+    fn built_in_templates_only_use_their_english_placeholders() {
+        let english = serde_json::to_value(l10n(Lang::En)).unwrap();
+        for lang in Lang::BUILT_IN {
+            let table = l10n(lang);
+            let forms = (0..200).map(|n| plural_form(&table.plural_rule, n)).max().unwrap_or(0) + 1;
+            let strings = serde_json::to_value(table).unwrap();
+            for (key, text) in strings.as_object().unwrap() {
+                let (text, known) = (text.as_str().unwrap(), english[key].as_str().unwrap());
+                for name in placeholders(text) {
+                    assert!(placeholders(known).any(|k| k == name), "{lang:?} {key}: {{{name}}}");
+                }
+                for inner in text.split('{').skip(1).filter_map(|part| part.split_once('}')).map(|(inner, _)| inner) {
+                    let count = inner.split('|').count() - 1;
+                    assert!(count == 0 || count >= forms, "{lang:?} {key}: {{{inner}}} needs {forms} forms");
+                }
+            }
+        }
+    }
+}
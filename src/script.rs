@@ -0,0 +1,112 @@
+// ---------------------------------------------------------------------------
+// Registry scripts: written by `--emit-script`, .reg files read back
+// ---------------------------------------------------------------------------
+
+/// Where a script writes: hive, key path and value name.
+pub struct Target<'a> {
+    /// `HKEY_CURRENT_USER` or `HKEY_LOCAL_MACHINE`.
+    pub hive: &'a str,
+    /// `HKCU` or `HKLM`, the PowerShell drive.
+    pub drive: &'a str,
+    pub key: &'a str,
+    pub value: &'a str,
+}
+
+/// Regedit's own line length for `hex:` values.
+const REG_LINE: usize = 80;
+
+/// A `.reg` file setting `bytes` as REG_BINARY, in UTF-16 with a byte
+/// order mark like the files regedit exports.
+pub fn reg_file(target: &Target, bytes: &[u8]) -> Vec<u8> {
+    let mut text = String::from("Windows Registry Editor Version 5.00\r\n\r\n");
+    text.push_str("; Written by hsr-settings. Double-click to apply the graphics settings.\r\n");
+    text.push_str(&format!("[{}\\{}]\r\n", target.hive, target.key));
+    let mut line = format!("\"{}\"=hex:", target.value);
+    for (i, byte) in bytes.iter().enumerate() {
+        let last = i + 1 == bytes.len();
+        let item = if last { format!("{byte:02x}") } else { format!("{byte:02x},") };
+        // Continuations end in a backslash and start indented by two.
+        if line.len() + item.len() > REG_LINE - 1 {
+            text.push_str(&line);
+            text.push_str("\\\r\n");
+            line = String::from("  ");
+        }
+        line.push_str(&item);
+    }
+    text.push_str(&line);
+    text.push_str("\r\n");
+    let mut out = vec![0xff, 0xfe];
+    out.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+    out
+}
+
+/// A PowerShell script setting `bytes` as REG_BINARY, creating the key
+/// when it's missing and leaving its other values alone.
+pub fn powershell(target: &Target, bytes: &[u8]) -> String {
+    let hex: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    let quote = |s: &str| format!("'{}'", s.replace('\'', "''"));
+    format!(
+        "# Written by hsr-settings. Run with: powershell -ExecutionPolicy Bypass -File <this file>\r\n\
+         $path = {path}\r\n\
+         $hex = {hex}\r\n\
+         $bytes = [byte[]] -split ($hex -replace '..', '0x$& ')\r\n\
+         if (-not (Test-Path $path)) {{ New-Item -Path $path -Force | Out-Null }}\r\n\
+         New-ItemProperty -Path $path -Name {value} -PropertyType Binary -Value $bytes -Force | Out-Null\r\n\
+         Write-Host \"Settings written ($($bytes.Length) bytes).\"\r\n",
+        path = quote(&format!("{}:\\{}", target.drive, target.key)),
+        hex = quote(&hex),
+        value = quote(target.value),
+    )
+}
+
+/// Text of a file that may be UTF-16 with a byte order mark, as regedit
+/// exports are, or UTF-8.
+pub fn decode_text(bytes: &[u8]) -> Result<String, String> {
+    match bytes {
+        [0xff, 0xfe, rest @ ..] => {
+            let units: Vec<u16> = rest.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect();
+            String::from_utf16(&units).map_err(|e| e.to_string())
+        }
+        [0xef, 0xbb, 0xbf, rest @ ..] => String::from_utf8(rest.to_vec()).map_err(|e| e.to_string()),
+        _ => String::from_utf8(bytes.to_vec()).map_err(|e| e.to_string()),
+    }
+}
+
+/// Whether `text` is a regedit export (version 5 or the older REGEDIT4).
+pub fn is_reg_file(text: &str) -> bool {
+    let first = text.lines().next().unwrap_or_default().trim();
+    first == "Windows Registry Editor Version 5.00" || first == "REGEDIT4"
+}
+
+/// Every `"name"=hex:…` value in a .reg file, in file order, with lines
+/// ending in `\` joined to the next. Other value types are skipped.
+pub fn reg_binary_values(text: &str) -> Result<Vec<(String, Vec<u8>)>, String> {
+    let mut values = Vec::new();
+    let mut logical = String::new();
+    for (n, line) in text.lines().enumerate() {
+        let line = line.trim();
+        match line.strip_suffix('\\') {
+            Some(start) => {
+                logical.push_str(start);
+                continue;
+            }
+            None => logical.push_str(line),
+        }
+        let entry = std::mem::take(&mut logical);
+        let Some((name, data)) = entry.strip_prefix('"').and_then(|e| e.split_once("\"=")) else {
+            continue;
+        };
+        // `hex(3):` is the long spelling of REG_BINARY.
+        let Some(hex) = data.strip_prefix("hex:").or_else(|| data.strip_prefix("hex(3):")) else {
+            continue;
+        };
+        let bytes = hex
+            .split(',')
+            .map(str::trim)
+            .filter(|b| !b.is_empty())
+            .map(|b| u8::from_str_radix(b, 16).map_err(|_| format!("line {}: {name}: bad byte '{b}'", n + 1)))
+            .collect::<Result<Vec<u8>, String>>()?;
+        values.push((name.replace("\\\"", "\"").replace("\\\\", "\\"), bytes));
+    }
+    Ok(values)
+}
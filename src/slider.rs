@@ -0,0 +1,30 @@
+// ---------------------------------------------------------------------------
+// Inline gauge for continuous settings
+// ---------------------------------------------------------------------------
+
+/// Left-aligned partial blocks, one to seven eighths of a cell wide.
+const EIGHTHS: [char; 7] = [
+    '\u{258f}', '\u{258e}', '\u{258d}', '\u{258c}', '\u{258b}', '\u{258a}', '\u{2589}',
+];
+const FULL: char = '\u{2588}';
+
+/// A gauge exactly `width` cells wide, filled in proportion to where `value`
+/// sits in `min..=max`, to an eighth of a cell. Values outside the range pin
+/// the fill at the nearest end; the caller shows the true number next to it.
+/// Without `fine`, whole cells of `#` on a `-` track, in plain ASCII.
+pub fn gauge(value: f64, min: f64, max: f64, width: usize, fine: bool) -> String {
+    let fraction = if max > min { ((value - min) / (max - min)).clamp(0.0, 1.0) } else { 0.0 };
+    if !fine {
+        let filled = (fraction * width as f64).round() as usize;
+        return "#".repeat(filled) + &"-".repeat(width - filled);
+    }
+    let eighths = (fraction * (width * 8) as f64).round() as usize;
+    let mut out: String = std::iter::repeat_n(FULL, eighths / 8).collect();
+    let partial = eighths % 8;
+    if partial > 0 {
+        out.push(EIGHTHS[partial - 1]);
+    }
+    let filled = eighths.div_ceil(8);
+    out.extend(std::iter::repeat_n(' ', width - filled));
+    out
+}
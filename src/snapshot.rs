@@ -0,0 +1,70 @@
+use std::io;
+
+use ratatui::{
+    buffer::Buffer,
+    crossterm::{
+        queue,
+        style::{Attribute, Print, SetAttribute, SetBackgroundColor, SetForegroundColor},
+    },
+    style::{Modifier, Style},
+};
+use unicode_width::UnicodeWidthStr;
+
+// ---------------------------------------------------------------------------
+// Text captures of a rendered frame
+// ---------------------------------------------------------------------------
+
+/// Attributes written for each modifier, in SGR order.
+const ATTRIBUTES: [(Modifier, Attribute); 7] = [
+    (Modifier::BOLD, Attribute::Bold),
+    (Modifier::DIM, Attribute::Dim),
+    (Modifier::ITALIC, Attribute::Italic),
+    (Modifier::UNDERLINED, Attribute::Underlined),
+    (Modifier::SLOW_BLINK, Attribute::SlowBlink),
+    (Modifier::REVERSED, Attribute::Reverse),
+    (Modifier::CROSSED_OUT, Attribute::CrossedOut),
+];
+
+/// The buffer as lines of text. With `ansi`, each change of style is
+/// written as SGR escapes so `cat` in a terminal shows the frame as drawn;
+/// without, it's plain text. Trailing blanks are trimmed from plain lines.
+pub fn to_text(buffer: &Buffer, ansi: bool) -> io::Result<String> {
+    let mut out = Vec::new();
+    let area = buffer.area;
+    for y in area.top()..area.bottom() {
+        let mut line = Vec::new();
+        let mut current: Option<Style> = None;
+        // Cells covered by the previous wide character hold no symbol of their own.
+        let mut skip: usize = 0;
+        for x in area.left()..area.right() {
+            let cell = &buffer[(x, y)];
+            if skip > 0 || cell.skip {
+                skip = skip.saturating_sub(1);
+                continue;
+            }
+            skip = cell.symbol().width().saturating_sub(1);
+            if ansi && current != Some(cell.style()) {
+                let style = cell.style();
+                queue!(line, SetAttribute(Attribute::Reset))?;
+                for (modifier, attribute) in ATTRIBUTES {
+                    if style.add_modifier.contains(modifier) {
+                        queue!(line, SetAttribute(attribute))?;
+                    }
+                }
+                queue!(line, SetForegroundColor(cell.fg.into()), SetBackgroundColor(cell.bg.into()))?;
+                current = Some(style);
+            }
+            queue!(line, Print(cell.symbol()))?;
+        }
+        if ansi {
+            queue!(line, SetAttribute(Attribute::Reset))?;
+        } else {
+            while line.last() == Some(&b' ') {
+                line.pop();
+            }
+        }
+        out.extend(line);
+        out.push(b'\n');
+    }
+    String::from_utf8(out).map_err(io::Error::other)
+}
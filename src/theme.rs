@@ -0,0 +1,170 @@
+use ratatui::style::{Color, Modifier};
+use serde::{Deserialize, Serialize};
+
+// ---------------------------------------------------------------------------
+// Color themes
+// ---------------------------------------------------------------------------
+
+/// Colors by role; every widget draws from one of these instead of naming
+/// a color itself.
+pub struct Theme {
+    /// Titles, group headers and informational badges.
+    pub accent: Color,
+    /// The selected row, dialog questions and highlighted buttons.
+    pub highlight: Color,
+    /// Text drawn on a `highlight` background.
+    pub on_highlight: Color,
+    pub text: Color,
+    /// Hints, defaults and other secondary text.
+    pub muted: Color,
+    /// Values that differ from what is stored in the registry.
+    pub pending: Color,
+    /// The selected value, success messages and the safe-mode badge.
+    pub positive: Color,
+    pub warning: Color,
+    pub error: Color,
+    /// The modified-from-default marker and value, and the preset badge.
+    pub marker: Color,
+    /// Background of text inputs and the render scale gauge.
+    pub field_bg: Color,
+    /// Setting labels by category, in `Category` order, when
+    /// `category_colors` is on.
+    pub categories: [Color; 5],
+    /// Added to the selected row and button on top of `highlight`, for
+    /// themes that can't rely on color to show the selection.
+    pub selected: Modifier,
+}
+
+/// The original palette, for dark terminal backgrounds.
+const DARK: Theme = Theme {
+    accent: Color::Cyan,
+    highlight: Color::Yellow,
+    on_highlight: Color::Black,
+    text: Color::White,
+    muted: Color::DarkGray,
+    pending: Color::LightRed,
+    positive: Color::Green,
+    warning: Color::Yellow,
+    error: Color::Red,
+    marker: Color::Magenta,
+    field_bg: Color::DarkGray,
+    categories: [Color::LightBlue, Color::LightYellow, Color::LightGreen, Color::LightMagenta, Color::LightCyan],
+    selected: Modifier::empty(),
+};
+
+/// No yellow or white text, which vanish on light backgrounds.
+const LIGHT: Theme = Theme {
+    accent: Color::Blue,
+    highlight: Color::Magenta,
+    on_highlight: Color::White,
+    text: Color::Black,
+    muted: Color::DarkGray,
+    pending: Color::Red,
+    positive: Color::Green,
+    warning: Color::Rgb(154, 103, 0),
+    error: Color::Red,
+    marker: Color::Rgb(128, 0, 128),
+    field_bg: Color::Gray,
+    categories: [Color::Blue, Color::Rgb(154, 103, 0), Color::Rgb(0, 120, 0), Color::Rgb(128, 0, 128), Color::Rgb(0, 120, 120)],
+    selected: Modifier::empty(),
+};
+
+/// Solarized dark accents; needs a truecolor terminal.
+const SOLARIZED: Theme = Theme {
+    accent: Color::Rgb(0x26, 0x8b, 0xd2),
+    highlight: Color::Rgb(0xb5, 0x89, 0x00),
+    on_highlight: Color::Rgb(0x00, 0x2b, 0x36),
+    text: Color::Rgb(0x93, 0xa1, 0xa1),
+    muted: Color::Rgb(0x58, 0x6e, 0x75),
+    pending: Color::Rgb(0xcb, 0x4b, 0x16),
+    positive: Color::Rgb(0x85, 0x99, 0x00),
+    warning: Color::Rgb(0xb5, 0x89, 0x00),
+    error: Color::Rgb(0xdc, 0x32, 0x2f),
+    marker: Color::Rgb(0xd3, 0x36, 0x82),
+    field_bg: Color::Rgb(0x07, 0x36, 0x42),
+    categories: [
+        Color::Rgb(0x26, 0x8b, 0xd2),
+        Color::Rgb(0xb5, 0x89, 0x00),
+        Color::Rgb(0x85, 0x99, 0x00),
+        Color::Rgb(0xd3, 0x36, 0x82),
+        Color::Rgb(0x2a, 0xa1, 0x98),
+    ],
+    selected: Modifier::empty(),
+};
+
+/// Bold, reverse-video selection and the Okabe-Ito palette, whose hues
+/// stay distinct under the common forms of color blindness.
+const HIGH_CONTRAST: Theme = Theme {
+    accent: Color::Rgb(0x56, 0xb4, 0xe9),
+    highlight: Color::White,
+    on_highlight: Color::Black,
+    text: Color::White,
+    muted: Color::Gray,
+    pending: Color::Rgb(0xe6, 0x9f, 0x00),
+    positive: Color::Rgb(0x00, 0x9e, 0x73),
+    warning: Color::Rgb(0xf0, 0xe4, 0x42),
+    error: Color::Rgb(0xd5, 0x5e, 0x00),
+    marker: Color::Rgb(0xcc, 0x79, 0xa7),
+    field_bg: Color::Black,
+    categories: [
+        Color::Rgb(0x56, 0xb4, 0xe9),
+        Color::Rgb(0xe6, 0x9f, 0x00),
+        Color::Rgb(0x00, 0x9e, 0x73),
+        Color::Rgb(0xcc, 0x79, 0xa7),
+        Color::Rgb(0xf0, 0xe4, 0x42),
+    ],
+    selected: Modifier::REVERSED,
+};
+
+/// No colors at all, for NO_COLOR and `--no-color`: the terminal's own
+/// foreground and background, with the selection shown in reverse video.
+pub const MONO: Theme = Theme {
+    accent: Color::Reset,
+    highlight: Color::Reset,
+    on_highlight: Color::Reset,
+    text: Color::Reset,
+    muted: Color::Reset,
+    pending: Color::Reset,
+    positive: Color::Reset,
+    warning: Color::Reset,
+    error: Color::Reset,
+    marker: Color::Reset,
+    field_bg: Color::Reset,
+    categories: [Color::Reset; 5],
+    selected: Modifier::REVERSED,
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemeName {
+    #[default]
+    Dark,
+    Light,
+    Solarized,
+    HighContrast,
+}
+
+impl ThemeName {
+    pub const ALL: [ThemeName; 4] =
+        [ThemeName::Dark, ThemeName::Light, ThemeName::Solarized, ThemeName::HighContrast];
+
+    /// Parses a `--theme` argument.
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "dark" => Some(ThemeName::Dark),
+            "light" => Some(ThemeName::Light),
+            "solarized" => Some(ThemeName::Solarized),
+            "high-contrast" | "high_contrast" => Some(ThemeName::HighContrast),
+            _ => None,
+        }
+    }
+
+    pub fn theme(self) -> &'static Theme {
+        match self {
+            ThemeName::Dark      => &DARK,
+            ThemeName::Light     => &LIGHT,
+            ThemeName::Solarized => &SOLARIZED,
+            ThemeName::HighContrast => &HIGH_CONTRAST,
+        }
+    }
+}
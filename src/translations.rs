@@ -0,0 +1,253 @@
+use std::{fs, io, path::Path, sync::OnceLock};
+
+use serde::Deserialize;
+
+use crate::{config, l10n, message, L10n, Lang};
+
+// ---------------------------------------------------------------------------
+// Community translations (%APPDATA%\hsr-settings\lang\<code>.toml)
+// ---------------------------------------------------------------------------
+
+const LANG_DIR: &str = "lang";
+/// The key holding the language's own name; every other key is an `L10n` field.
+const NAME_KEY: &str = "name";
+
+/// A language loaded from a file, listed after the built-in ones.
+pub struct Translation {
+    /// The file name without `.toml`, as used for `--lang` and `language`.
+    pub code: String,
+    pub name: String,
+    pub table: L10n,
+    /// Keys the file lacks, for the preferences screen.
+    pub missing: usize,
+}
+
+/// Something wrong with a translation file, reported on startup.
+pub enum Problem {
+    /// Keys the file lacks, and the name of the language they show in instead.
+    Missing(String, Vec<String>, String),
+    /// Keys that aren't strings the UI uses, e.g. typos or renamed keys.
+    Unknown(String, Vec<String>),
+    /// Strings using placeholders the English text doesn't have, e.g. a
+    /// translated `{n}`; the fallback text is shown instead.
+    Placeholders(String, Vec<String>),
+    /// Named after a built-in language, which takes precedence.
+    BuiltIn(String),
+    /// Not readable or not valid TOML; the file was skipped.
+    Invalid(String, String),
+}
+
+static LOADED: OnceLock<(Vec<Translation>, Vec<Problem>)> = OnceLock::new();
+
+/// Every translation loaded by `load`, in file name order.
+pub fn all() -> &'static [Translation] {
+    LOADED.get().map_or(&[], |(translations, _)| translations)
+}
+
+/// What `load` found wrong with the files, for the startup notices.
+pub fn problems() -> &'static [Problem] {
+    LOADED.get().map_or(&[], |(_, problems)| problems)
+}
+
+/// Reads every `.toml` file in the `lang` folder of the config directory.
+/// Runs once, before the config is parsed, so configured codes resolve.
+pub fn load() {
+    LOADED.get_or_init(|| {
+        let mut translations = Vec::new();
+        let mut problems = Vec::new();
+        let Some(dir) = config::config_dir().map(|d| d.join(LANG_DIR)) else {
+            return (translations, problems);
+        };
+        let mut paths: Vec<_> = fs::read_dir(dir)
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+            .collect();
+        // Plain codes first, so `de.toml` is loaded by the time `de-at.toml`
+        // looks for the language it falls back to.
+        paths.sort_by_key(|path| (stem(path).is_some_and(|s| s.contains(['-', '_'])), path.clone()));
+        for path in paths {
+            let Some(code) = stem(&path).map(str::to_string) else {
+                continue;
+            };
+            let file = format!("{code}.toml");
+            if Lang::BUILT_IN.iter().any(|l| l.code().eq_ignore_ascii_case(&code)) {
+                problems.push(Problem::BuiltIn(file));
+                continue;
+            }
+            let (fallback, base) = related(&code, &translations);
+            match parse(&path, base) {
+                Ok(parsed) => {
+                    let missing = parsed.missing.len();
+                    if missing > 0 {
+                        problems.push(Problem::Missing(file.clone(), parsed.missing, fallback));
+                    }
+                    if !parsed.unknown.is_empty() {
+                        problems.push(Problem::Unknown(file.clone(), parsed.unknown));
+                    }
+                    if !parsed.misplaced.is_empty() {
+                        problems.push(Problem::Placeholders(file, parsed.misplaced));
+                    }
+                    let name = parsed.name.unwrap_or_else(|| code.clone());
+                    translations.push(Translation { code, name, table: parsed.table, missing });
+                }
+                Err(e) => problems.push(Problem::Invalid(file, e.to_string())),
+            }
+        }
+        // Listed in file name order, whatever order they loaded in.
+        translations.sort_by(|a, b| a.code.cmp(&b.code));
+        (translations, problems)
+    });
+}
+
+fn stem(path: &Path) -> Option<&str> {
+    path.file_stem().and_then(|s| s.to_str())
+}
+
+/// What a missing key falls back to: the language `code` is a variant of,
+/// e.g. `es` for `es-mx` or `de.toml` for `de-at`, or else English. The
+/// table returned is complete, so the chain ends there.
+fn related<'a>(code: &str, loaded: &'a [Translation]) -> (String, &'a L10n) {
+    let english = || (Lang::En.name().to_string(), l10n(Lang::En));
+    let Some((primary, _)) = code.split_once(['-', '_']) else {
+        return english();
+    };
+    // `Lang::parse` only knows the built-in languages while loading.
+    if let Some(lang) = Lang::parse(primary) {
+        return (lang.name().to_string(), l10n(lang));
+    }
+    match loaded.iter().find(|t| t.code.eq_ignore_ascii_case(primary)) {
+        Some(t) => (t.name.clone(), &t.table),
+        None => english(),
+    }
+}
+
+/// A translation file as read, before it's listed.
+struct Parsed {
+    name: Option<String>,
+    table: L10n,
+    missing: Vec<String>,
+    unknown: Vec<String>,
+    misplaced: Vec<String>,
+}
+
+fn parse(path: &Path, base: &L10n) -> io::Result<Parsed> {
+    let text = fs::read_to_string(path)?;
+    let file: toml::Table = toml::from_str(&text).map_err(|e| io::Error::other(e.message().to_string()))?;
+    overlay(base, file)
+}
+
+/// A built-in language's strings laid over English, and how many of them
+/// English fills in.
+pub fn lay_over(english: &L10n, strings: &[(&str, &str)]) -> (L10n, usize) {
+    let file = strings.iter().map(|&(key, text)| (key.to_string(), toml::Value::String(text.to_string()))).collect();
+    let parsed = overlay(english, file).expect("strings laid over a complete table deserialize");
+    (parsed.table, parsed.missing.len())
+}
+
+/// Lays the file's strings over the `base` table, so a missing key shows
+/// in the related language rather than leaving a hole in the UI.
+fn overlay(base: &L10n, file: toml::Table) -> io::Result<Parsed> {
+    let mut table = serde_json::to_value(base).map_err(io::Error::other)?;
+    let Some(fields) = table.as_object_mut() else {
+        return Err(io::Error::other("L10n is not a table"));
+    };
+    let mut missing: Vec<String> = fields.keys().filter(|k| !file.contains_key(*k)).cloned().collect();
+    let mut unknown = Vec::new();
+    let mut misplaced = Vec::new();
+    let mut name = None;
+    for (key, value) in file {
+        match (key.as_str(), value) {
+            (NAME_KEY, toml::Value::String(s)) => name = Some(s),
+            (_, toml::Value::String(s)) if fields.contains_key(&key) => {
+                let known = fields[&key].as_str().unwrap_or_default();
+                if message::placeholders(&s).all(|p| message::placeholders(known).any(|k| k == p)) {
+                    fields.insert(key, s.into());
+                } else {
+                    misplaced.push(key);
+                }
+            }
+            // Right key, wrong type: treat it as missing.
+            (_, _) if fields.contains_key(&key) => missing.push(key),
+            _ => unknown.push(key),
+        }
+    }
+    let table = L10n::deserialize(table).map_err(io::Error::other)?;
+    Ok(Parsed { name, table, missing, unknown, misplaced })
+}
+
+/// `lang template`: every key with its English text, as a starting point
+/// for a new translation.
+pub fn write_template(path: &Path) -> io::Result<()> {
+    let mut table = toml::Table::new();
+    table.insert(NAME_KEY.to_string(), toml::Value::String("English".to_string()));
+    let english = toml::Table::try_from(l10n(Lang::En)).map_err(io::Error::other)?;
+    table.extend(english);
+    let body = toml::to_string(&table).map_err(io::Error::other)?;
+    let header = "# hsr-settings translation template.\n\
+                  # Translate each value, set `name` to the language's own name, and save\n\
+                  # the file as lang\\<code>.toml in the hsr-settings config folder.\n\
+                  # Keys left out are shown in the language the code is a variant of\n\
+                  # (es for es-mx.toml), or else English. Keep {placeholders} as they are.\n\n";
+    fs::write(path, format!("{header}{body}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{JAPANESE, KOREAN, PORTUGUESE_BR, RUSSIAN, SPANISH};
+
+    fn translation(code: &str, title: &str) -> Translation {
+        let mut table = l10n(Lang::En).clone();
+        table.title = title.to_string();
+        Translation { code: code.to_string(), name: code.to_uppercase(), table, missing: 0 }
+    }
+
+    #[test]
+    fn variants_fall_back_to_their_language_then_english() {
+        let loaded = [translation("de", "Titel")];
+        let (name, table) = related("es-mx", &loaded);
+        assert_eq!((name.as_str(), table.title.as_str()), ("Español", l10n(Lang::Es).title.as_str()));
+        let (name, table) = related("de_at", &loaded);
+        assert_eq!((name.as_str(), table.title.as_str()), ("DE", "Titel"));
+        for code in ["de", "fr-ca"] {
+            let (name, table) = related(code, &loaded);
+            assert_eq!((name.as_str(), table.title.as_str()), ("English", l10n(Lang::En).title.as_str()));
+        }
+    }
+
+    #[test]
+    fn files_are_laid_over_their_base() {
+        let base = translation("de", "Titel").table;
+        let mut file = toml::Table::new();
+        file.insert(NAME_KEY.into(), "Deutsch (Österreich)".into());
+        file.insert("on".into(), "Ein".into());
+        file.insert("off".into(), toml::Value::Integer(0));
+        file.insert("saved_bytes".into(), "{bytes} Bytes".into());
+        file.insert("no_such_key".into(), "?".into());
+        let parsed = overlay(&base, file).ok().unwrap();
+        assert_eq!(parsed.name.as_deref(), Some("Deutsch (Österreich)"));
+        assert_eq!(parsed.table.on, "Ein");
+        assert_eq!(parsed.table.title, "Titel");
+        assert_eq!(parsed.table.off, base.off);
+        assert_eq!(parsed.table.saved_bytes, base.saved_bytes);
+        assert!(parsed.missing.contains(&"off".to_string()) && parsed.missing.contains(&"title".to_string()));
+        assert_eq!(parsed.unknown, ["no_such_key"]);
+        assert_eq!(parsed.misplaced, ["saved_bytes"]);
+    }
+
+    #[test]
+    fn built_in_strings_are_per_key() {
+        let english = l10n(Lang::En);
+        let (table, missing) = lay_over(english, &[("on", "켜기")]);
+        assert_eq!((table.on.as_str(), table.off.as_str()), ("켜기", english.off.as_str()));
+        let keys = serde_json::to_value(english).unwrap().as_object().unwrap().len();
+        assert_eq!(missing, keys - 1);
+        for strings in [KOREAN, JAPANESE, RUSSIAN, SPANISH, PORTUGUESE_BR] {
+            let file = strings.iter().map(|&(k, v)| (k.to_string(), toml::Value::String(v.to_string()))).collect();
+            let parsed = overlay(english, file).ok().unwrap();
+            assert!(parsed.unknown.is_empty(), "{:?}", parsed.unknown);
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use std::{
+    ffi::c_void,
+    io,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use winreg::RegKey;
+
+use crate::{Hive, REG_PATH, REG_VALUE};
+
+// ---------------------------------------------------------------------------
+// Registry watcher (reports changes the game makes while the app is open)
+// ---------------------------------------------------------------------------
+
+/// How long the value has to stay the same before a change is reported. The
+/// game writes it several times in a row when its settings menu closes.
+const DEBOUNCE: Duration = Duration::from_millis(500);
+/// Between reads when notifications are unavailable or the key is missing,
+/// and the longest a notification is trusted to arrive.
+const POLL: Duration = Duration::from_secs(2);
+/// How often waits check whether the watcher is shutting down.
+const WAKE: Duration = Duration::from_millis(100);
+
+/// The settings value after a change settled, as the raw bytes read.
+pub enum Change {
+    Value(Hive, Vec<u8>),
+    /// The key or value was deleted.
+    Missing,
+}
+
+/// The watcher thread and the changes it found. Dropping it stops the
+/// thread and waits for it.
+pub struct Watcher {
+    changes: mpsc::Receiver<Change>,
+    stop: Arc<AtomicBool>,
+    thread: Option<thread::JoinHandle<()>>,
+}
+
+impl Watcher {
+    /// Watches the key reads use: HKCU's, or HKLM's when HKCU has none.
+    /// Only changes after the value is first read are reported.
+    pub fn spawn() -> io::Result<Watcher> {
+        let (tx, changes) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = Arc::clone(&stop);
+        let thread = thread::Builder::new().name("registry watcher".into()).spawn(move || run(&tx, &thread_stop))?;
+        Ok(Watcher { changes, stop, thread: Some(thread) })
+    }
+
+    /// The latest change since the last call; earlier ones are superseded.
+    pub fn latest(&self) -> Option<Change> {
+        self.changes.try_iter().last()
+    }
+}
+
+impl Drop for Watcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn run(tx: &mpsc::Sender<Change>, stop: &AtomicBool) {
+    let event = Event::new();
+    let mut last = read(open().as_ref());
+    while !stop.load(Ordering::Relaxed) {
+        let key = open();
+        // Armed before reading, so a write in between isn't missed. The
+        // key is reopened every round, which follows it being deleted and
+        // created again.
+        let armed = match (&key, &event) {
+            (Some((_, key)), Some(event)) => event.arm(key),
+            _ => false,
+        };
+        let mut current = read(key.as_ref());
+        if current != last {
+            loop {
+                if !pause(DEBOUNCE, stop) {
+                    return;
+                }
+                let again = read(open().as_ref());
+                if again == current {
+                    break;
+                }
+                current = again;
+            }
+            let change = match current.clone() {
+                Some((hive, bytes)) => Change::Value(hive, bytes),
+                None => Change::Missing,
+            };
+            if tx.send(change).is_err() {
+                return;
+            }
+            last = current;
+            continue;
+        }
+        let waited = match (armed, &event) {
+            (true, Some(event)) => event.wait(POLL, stop),
+            _ => pause(POLL, stop),
+        };
+        if !waited {
+            return;
+        }
+    }
+}
+
+/// The game's key in the hive reads use, as `read_settings_from` picks it.
+fn open() -> Option<(Hive, RegKey)> {
+    Hive::ALL.into_iter().find_map(|hive| hive.root().open_subkey(REG_PATH).ok().map(|key| (hive, key)))
+}
+
+fn read(key: Option<&(Hive, RegKey)>) -> Option<(Hive, Vec<u8>)> {
+    let (hive, key) = key?;
+    key.get_raw_value(REG_VALUE).ok().map(|value| (*hive, value.bytes))
+}
+
+/// Sleeps for `duration`; false when the watcher stopped meanwhile.
+fn pause(duration: Duration, stop: &AtomicBool) -> bool {
+    let until = Instant::now() + duration;
+    while !stop.load(Ordering::Relaxed) {
+        let left = until.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            return true;
+        }
+        thread::sleep(left.min(WAKE));
+    }
+    false
+}
+
+#[link(name = "advapi32")]
+unsafe extern "system" {
+    fn RegNotifyChangeKeyValue(key: *mut c_void, subtree: i32, filter: u32, event: *mut c_void, asynchronous: i32) -> i32;
+}
+
+#[link(name = "kernel32")]
+unsafe extern "system" {
+    fn CreateEventW(attributes: *const c_void, manual_reset: i32, initial_state: i32, name: *const u16) -> *mut c_void;
+    fn WaitForSingleObject(handle: *mut c_void, milliseconds: u32) -> u32;
+    fn CloseHandle(handle: *mut c_void) -> i32;
+}
+
+const REG_NOTIFY_CHANGE_NAME: u32 = 0x1;
+const REG_NOTIFY_CHANGE_LAST_SET: u32 = 0x4;
+const WAIT_OBJECT_0: u32 = 0;
+
+/// An auto-reset event that registry notifications signal.
+struct Event(*mut c_void);
+
+impl Event {
+    fn new() -> Option<Event> {
+        // SAFETY: no attributes or name; the handle is checked before use.
+        let handle = unsafe { CreateEventW(std::ptr::null(), 0, 0, std::ptr::null()) };
+        (!handle.is_null()).then_some(Event(handle))
+    }
+
+    /// Asks for one signal when a value of `key` is set, or a value or
+    /// subkey is added or deleted. False where notifications aren't
+    /// supported, e.g. under Wine or for remote keys.
+    fn arm(&self, key: &RegKey) -> bool {
+        let filter = REG_NOTIFY_CHANGE_NAME | REG_NOTIFY_CHANGE_LAST_SET;
+        // SAFETY: both handles are open; the registration ends when the key is closed.
+        unsafe { RegNotifyChangeKeyValue(key.raw_handle(), 0, filter, self.0, 1) == 0 }
+    }
+
+    /// Waits up to `timeout` for a signal; false when the watcher stopped.
+    fn wait(&self, timeout: Duration, stop: &AtomicBool) -> bool {
+        let until = Instant::now() + timeout;
+        while !stop.load(Ordering::Relaxed) {
+            let left = until.saturating_duration_since(Instant::now());
+            // SAFETY: the handle stays open for the life of `self`.
+            if left.is_zero() || unsafe { WaitForSingleObject(self.0, left.min(WAKE).as_millis() as u32) } == WAIT_OBJECT_0 {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+impl Drop for Event {
+    fn drop(&mut self) {
+        // SAFETY: the handle came from CreateEventW and is closed only here.
+        unsafe { CloseHandle(self.0) };
+    }
+}